@@ -1,7 +1,6 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::fs;
+
+use parse::Span;
 
 use crate::Result;
 
@@ -75,15 +74,11 @@ fn part2(dimensions: &[Cuboid]) -> u64 {
 }
 
 pub fn main() -> Result<()> {
-    let fl = File::open("res/input02")?;
-    let reader = BufReader::new(fl);
+    let input = fs::read_to_string("res/input02")?;
     let mut dimensions: Vec<Cuboid> = Vec::with_capacity(1000);
-    let mut dim = vec![0, 0, 0];
-    for line in reader.lines() {
-        for (i, ch) in line?.split('x').enumerate() {
-            dim[i] = ch.parse()?;
-        }
-        dimensions.push(Cuboid::new(dim[0], dim[1], dim[2]));
+    for line in Span::new(&input).lines() {
+        let [l, w, h] = line.integer_array()?;
+        dimensions.push(Cuboid::new(l, w, h));
     }
     println!("Day 02");
     println!("Part 1: {}", part1(&dimensions));
@@ -1,52 +1,24 @@
-use std::convert::TryFrom;
-use std::ops::{Add, AddAssign, Neg};
-use std::{
-    collections::HashSet,
-    fs::File,
-    io::{BufReader, Read},
-};
+use std::{collections::HashSet, fs};
 
-use crate::{InputParseError, Result};
+use geometry::{Direction, Vector2};
+use parse::{ErrorKind, ParseError, Span};
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
-struct Vector2<T> {
-    x: T,
-    y: T,
-}
-
-impl<T: Add<Output = T> + Copy> AddAssign for Vector2<T> {
-    fn add_assign(&mut self, rhs: Self) {
-        *self = Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
-}
+use crate::Result;
 
-impl<T: Neg<Output = T>> Neg for Vector2<T> {
-    type Output = Self;
-    fn neg(self) -> Self::Output {
-        Self::Output {
-            x: -self.x,
-            y: -self.y,
-        }
-    }
-}
-
-fn part1(moves: &[Vector2<i64>]) -> Result<u64> {
-    let mut pos = Vector2 { x: 0, y: 0 };
+fn part1(moves: &[Vector2<i64>]) -> usize {
+    let mut pos = Vector2::ZERO;
     let mut houses: HashSet<Vector2<i64>> = HashSet::new();
     houses.insert(pos);
     moves.iter().for_each(|v| {
         pos += *v;
         houses.insert(pos);
     });
-    Ok(u64::try_from(houses.len())?)
+    houses.len()
 }
 
-fn part2(moves: &[Vector2<i64>]) -> Result<u64> {
-    let mut santa_pos = Vector2 { x: 0, y: 0 };
-    let mut robot_pos = Vector2 { x: 0, y: 0 };
+fn part2(moves: &[Vector2<i64>]) -> usize {
+    let mut santa_pos = Vector2::ZERO;
+    let mut robot_pos = Vector2::ZERO;
     let mut houses: HashSet<Vector2<i64>> = HashSet::new();
     houses.insert(santa_pos);
     moves.iter().enumerate().for_each(|(i, v)| {
@@ -58,29 +30,28 @@ fn part2(moves: &[Vector2<i64>]) -> Result<u64> {
             houses.insert(robot_pos);
         }
     });
-    Ok(u64::try_from(houses.len())?)
+    houses.len()
 }
 
 pub fn main() -> Result<()> {
-    let fl = File::open("res/input03")?;
-    let mut reader = BufReader::new(fl);
-    let mut moves_buf = String::with_capacity(1024);
-    reader.read_to_string(&mut moves_buf)?;
-
-    let moves: std::result::Result<Vec<Vector2<i64>>, _> = moves_buf
-        .trim()
-        .chars()
-        .map(|ch| match ch {
-            '^' => Ok(Vector2 { x: 0, y: 1 }),
-            'v' => Ok(Vector2 { x: 0, y: -1 }),
-            '>' => Ok(Vector2 { x: 1, y: 0 }),
-            '<' => Ok(Vector2 { x: -1, y: 0 }),
-            _ => Err(InputParseError::new("Unexpected character in input")),
+    let input = fs::read_to_string("res/input03")?;
+    let moves = Span::new(&input).trim();
+    let moves: Vec<Vector2<i64>> = (moves.column()..)
+        .zip(moves.as_str().chars())
+        .map(|(column, ch)| match ch {
+            '^' => Ok(Direction::North.to_vector()),
+            'v' => Ok(Direction::South.to_vector()),
+            '>' => Ok(Direction::East.to_vector()),
+            '<' => Ok(Direction::West.to_vector()),
+            _ => Err(ParseError {
+                line: moves.line(),
+                column,
+                kind: ErrorKind::UnexpectedChar(ch),
+            }),
         })
-        .collect();
-    let moves = moves?;
+        .collect::<Result<_, _>>()?;
     println!("Day 03");
-    println!("Part 01: {}", part1(&moves)?);
-    println!("Part 02: {}", part2(&moves)?);
+    println!("Part 01: {}", part1(&moves));
+    println!("Part 02: {}", part2(&moves));
     Ok(())
 }
@@ -1,11 +1,9 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::fs;
 
-use ndarray::{s, Array2};
+use geometry::{Grid, Vector2};
+use parse::{ParseError, Span};
 
-use crate::{InputParseError, Result};
+use crate::Result;
 
 #[derive(Default)]
 struct Region<T> {
@@ -26,103 +24,85 @@ impl<T> Region<T> {
     }
 }
 
+impl Region<usize> {
+    fn positions(&self) -> impl Iterator<Item = Vector2<i64>> + '_ {
+        (self.xmin..=self.xmax).flat_map(move |x| {
+            (self.ymin..=self.ymax).map(move |y| Vector2::new(x as i64, y as i64))
+        })
+    }
+}
+
+fn update<T>(grid: &mut Grid<T>, region: &Region<usize>, f: impl Fn(&mut T)) {
+    for position in region.positions() {
+        f(&mut grid[position]);
+    }
+}
+
 enum Instruction {
     On(Region<usize>),
     Off(Region<usize>),
     Toggle(Region<usize>),
 }
 
-fn parse_region<'a, T: Iterator<Item = &'a str>>(mut words: T) -> Result<Region<usize>> {
-    let min_points = words
-        .next()
-        .ok_or_else(|| InputParseError::new("Unexpected end of input"))?;
-    let mut points = min_points.split(',');
-    let xmin = points
-        .next()
-        .ok_or_else(|| InputParseError::new("Unexpected end of input"))?
-        .parse()?;
-    let ymin = points
-        .next()
-        .ok_or_else(|| InputParseError::new("Unexpected end of input"))?
-        .parse()?;
-    let max_points = words
-        .nth(1)
-        .ok_or_else(|| InputParseError::new("Unexpected end of input"))?;
-    let mut points = max_points.split(',');
-    let xmax = points
-        .next()
-        .ok_or_else(|| InputParseError::new("Unexpected end of input"))?
-        .parse()?;
-    let ymax = points
-        .next()
-        .ok_or_else(|| InputParseError::new("Unexpected end of input"))?
-        .parse()?;
+fn parse_corner(corner: Span) -> Result<(usize, usize), ParseError> {
+    let (x, y) = corner.split_once(",")?;
+    Ok((x.parse()?, y.parse()?))
+}
+
+fn parse_region(region: Span) -> Result<Region<usize>, ParseError> {
+    let (min, max) = region.split_once(" through ")?;
+    let (xmin, ymin) = parse_corner(min)?;
+    let (xmax, ymax) = parse_corner(max)?;
     Ok(Region::new(xmin, ymin, xmax, ymax))
 }
 
-fn parse_instruction(line: &str) -> Result<Instruction> {
-    let mut words = line.split(' ');
-    match words.next() {
-        Some("turn") => match words.next() {
-            Some("on") => Ok(Instruction::On(parse_region(words)?)),
-            Some("off") => Ok(Instruction::Off(parse_region(words)?)),
-            _ => Err(InputParseError::new("Unexpected input").into()),
-        },
-        Some("toggle") => Ok(Instruction::Toggle(parse_region(words)?)),
-        _ => Err(InputParseError::new("Unexpected input").into()),
+fn parse_instruction(line: Span) -> Result<Instruction, ParseError> {
+    if let Ok(region) = line.strip_prefix("turn on ") {
+        Ok(Instruction::On(parse_region(region)?))
+    } else if let Ok(region) = line.strip_prefix("turn off ") {
+        Ok(Instruction::Off(parse_region(region)?))
+    } else if let Ok(region) = line.strip_prefix("toggle ") {
+        Ok(Instruction::Toggle(parse_region(region)?))
+    } else {
+        Err(line.expected("\"turn on\", \"turn off\" or \"toggle\""))
     }
 }
 
 fn part1(instructions: &[Instruction]) -> u32 {
-    let mut grid = Array2::from_elem((1000, 1000), false);
+    let mut grid = Grid::filled(1000, 1000, false);
     for ins in instructions {
         match ins {
-            Instruction::On(region) => grid
-                .slice_mut(s![region.xmin..=region.xmax, region.ymin..=region.ymax])
-                .map_inplace(|el| *el = true),
-            Instruction::Off(region) => grid
-                .slice_mut(s![region.xmin..=region.xmax, region.ymin..=region.ymax])
-                .map_inplace(|el| *el = false),
-            Instruction::Toggle(region) => grid
-                .slice_mut(s![region.xmin..=region.xmax, region.ymin..=region.ymax])
-                .map_inplace(|el| *el = !*el),
+            Instruction::On(region) => update(&mut grid, region, |el| *el = true),
+            Instruction::Off(region) => update(&mut grid, region, |el| *el = false),
+            Instruction::Toggle(region) => update(&mut grid, region, |el| *el = !*el),
         }
     }
     grid.iter().map(|el| if *el { 1 } else { 0 }).sum()
 }
 
 fn part2(instructions: &[Instruction]) -> u64 {
-    let mut grid = Array2::from_elem((1000, 1000), 0u64);
+    let mut grid = Grid::filled(1000, 1000, 0u64);
     for ins in instructions {
         match ins {
-            Instruction::On(region) => grid
-                .slice_mut(s![region.xmin..=region.xmax, region.ymin..=region.ymax])
-                .map_inplace(|el| *el += 1),
-            Instruction::Off(region) => grid
-                .slice_mut(s![region.xmin..=region.xmax, region.ymin..=region.ymax])
-                .map_inplace(|el| {
-                    *el = match *el {
-                        0 => 0,
-                        el => el - 1,
-                    }
-                }),
-            Instruction::Toggle(region) => grid
-                .slice_mut(s![region.xmin..=region.xmax, region.ymin..=region.ymax])
-                .map_inplace(|el| *el += 2),
+            Instruction::On(region) => update(&mut grid, region, |el| *el += 1),
+            Instruction::Off(region) => update(&mut grid, region, |el| {
+                *el = match *el {
+                    0 => 0,
+                    el => el - 1,
+                }
+            }),
+            Instruction::Toggle(region) => update(&mut grid, region, |el| *el += 2),
         }
     }
-    grid.sum()
+    grid.iter().sum()
 }
 
 pub fn main() -> Result<()> {
-    let fl = File::open("res/input06")?;
-    let reader = BufReader::new(fl);
-
-    let instructions: std::result::Result<Vec<Instruction>, _> = reader
+    let input = fs::read_to_string("res/input06")?;
+    let instructions: Vec<Instruction> = Span::new(&input)
         .lines()
-        .map(|line| parse_instruction(&line?))
-        .collect();
-    let instructions = instructions?;
+        .map(parse_instruction)
+        .collect::<Result<_, _>>()?;
 
     println!("Day 06");
     println!("Part 1: {}", part1(&instructions));
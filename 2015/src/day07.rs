@@ -1,13 +1,8 @@
-use std::{
-    collections::HashMap,
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-    num::ParseIntError,
-    str::FromStr,
-};
+use std::{collections::HashMap, convert::Infallible, fs, str::FromStr};
 
-use crate::{InputParseError, Result};
+use parse::{ParseError, Span};
+
+use crate::Result;
 
 enum Input {
     Signal(u16),
@@ -15,7 +10,7 @@ enum Input {
 }
 
 impl FromStr for Input {
-    type Err = ParseIntError;
+    type Err = Infallible;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         if let Ok(n) = s.parse::<u16>() {
@@ -55,48 +50,22 @@ enum Gate {
     I { x: Input },
 }
 
-impl FromStr for Gate {
-    type Err = Box<dyn Error>;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        let mut inputs = s.split(' ');
-        let inp1 = inputs
-            .next()
-            .ok_or_else(|| InputParseError::new("Unexpectedly empty input"))?;
-        if inp1 == "NOT" {
-            let notted_input = inputs
-                .next()
-                .ok_or_else(|| InputParseError::new("Nothing to NOT"))?
-                .parse()?;
-            return Ok(Self::Not { x: notted_input });
-        }
-        let x = inp1.parse()?;
-        let opt_gate = inputs.next();
-        let gate;
-        match opt_gate {
-            None => return Ok(Self::I { x }),
-            Some(g) => gate = g,
-        }
-        let y = inputs
-            .next()
-            .ok_or_else(|| InputParseError::new("Second operand missing"))?;
-        match gate {
-            "AND" => Ok(Self::And { x, y: y.parse()? }),
-            "OR" => Ok(Self::Or { x, y: y.parse()? }),
-            "LSHIFT" => {
-                let shift = y
-                    .parse()
-                    .map_err(|_| InputParseError::new("Couldn't parse shift"))?;
-                Ok(Self::Lshift { x, shift })
-            }
-            "RSHIFT" => {
-                let shift = y
-                    .parse()
-                    .map_err(|_| InputParseError::new("Couldn't parse shift"))?;
-                Ok(Self::Rshift { x, shift })
+fn parse_gate(s: Span) -> Result<Gate, ParseError> {
+    let words: Vec<Span> = s.words().collect();
+    match words[..] {
+        [x] => Ok(Gate::I { x: x.parse()? }),
+        [not, x] if not.as_str() == "NOT" => Ok(Gate::Not { x: x.parse()? }),
+        [x, gate, y] => {
+            let (x, y) = (x.parse()?, y.parse()?);
+            match gate.as_str() {
+                "AND" => Ok(Gate::And { x, y }),
+                "OR" => Ok(Gate::Or { x, y }),
+                "LSHIFT" => Ok(Gate::Lshift { x, shift: y }),
+                "RSHIFT" => Ok(Gate::Rshift { x, shift: y }),
+                _ => Err(gate.expected("AND, OR, LSHIFT or RSHIFT")),
             }
-            _ => Err(InputParseError::new("Unknown gate").into()),
         }
+        _ => Err(s.expected("a gate")),
     }
 }
 
@@ -136,21 +105,12 @@ fn part2(gate_lookup: &HashMap<String, Gate>) -> u16 {
 }
 
 pub fn main() -> Result<()> {
-    let fl = File::open("res/input07")?;
-    let reader = BufReader::new(fl);
+    let input = fs::read_to_string("res/input07")?;
 
     let mut gate_lookup: HashMap<String, Gate> = HashMap::new();
-    for line in reader.lines() {
-        let line = line?;
-        let mut sides = line.split(" -> ");
-        let lhs = sides
-            .next()
-            .ok_or_else(|| InputParseError::new("Unexpected end of input"))?;
-        let rhs = sides
-            .next()
-            .ok_or_else(|| InputParseError::new("Unexpected end of input"))?;
-
-        gate_lookup.insert(rhs.to_owned(), lhs.parse()?);
+    for line in Span::new(&input).lines() {
+        let (lhs, rhs) = line.split_once(" -> ")?;
+        gate_lookup.insert(rhs.to_string(), parse_gate(lhs)?);
     }
 
     println!("Day 07");
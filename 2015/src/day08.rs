@@ -1,73 +1,76 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::fs;
 
-use crate::{InputParseError, Result};
+use parse::{ErrorKind, ParseError, Span};
+
+use crate::Result;
 
 struct EscapeStringInterpreter<'a> {
     inner_iter: std::str::Chars<'a>,
 }
 
 impl<'a> Iterator for EscapeStringInterpreter<'_> {
-    type Item = std::result::Result<char, InputParseError>;
+    type Item = Result<char, ErrorKind>;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.inner_iter.next().map(|c| match c {
             '\\' => match self.inner_iter.next() {
-                None => Err(InputParseError::new("Incomplete escape sequence")),
+                None => Err(ErrorKind::Custom("incomplete escape sequence".to_string())),
                 Some('\\') => Ok('\\'),
                 Some('"') => Ok('"'),
                 Some('x') => match self.inner_iter.next() {
-                    None => Err(InputParseError::new("Incomplete escape sequence")),
+                    None => Err(ErrorKind::Custom("incomplete escape sequence".to_string())),
                     Some(c1 @ ('0'..='9' | 'a'..='f')) => match self.inner_iter.next() {
-                        None => Err(InputParseError::new("Incomplete escape sequence")),
+                        None => Err(ErrorKind::Custom("incomplete escape sequence".to_string())),
                         Some(c2 @ ('0'..='9' | 'a'..='f')) => {
                             match char::from_u32(
                                 c1.to_digit(0x10).unwrap() * 0x10 + c2.to_digit(0x10).unwrap(),
                             ) {
-                                None => Err(InputParseError::new("Unknown escape sequence")),
+                                None => {
+                                    Err(ErrorKind::Custom("unknown escape sequence".to_string()))
+                                }
                                 Some(c) => Ok(c),
                             }
                         }
-                        _ => Err(InputParseError::new("Unknown escape sequence")),
+                        _ => Err(ErrorKind::Custom("unknown escape sequence".to_string())),
                     },
-                    _ => Err(InputParseError::new("Unknown escape sequence")),
+                    _ => Err(ErrorKind::Custom("unknown escape sequence".to_string())),
                 },
-                _ => Err(InputParseError::new("Unknown escape sequence")),
+                _ => Err(ErrorKind::Custom("unknown escape sequence".to_string())),
             },
             c => Ok(c),
         })
     }
 }
 
-fn part1(strings: &[String]) -> usize {
+fn part1(strings: &[Span]) -> Result<usize, ParseError> {
     let mut total = 0;
     for s in strings {
         let unescaper = EscapeStringInterpreter {
-            inner_iter: s.chars(),
+            inner_iter: s.as_str().chars(),
         };
-        total += s.chars().count() - unescaper.count() + 2;
+        let unescaped: Vec<char> = unescaper
+            .collect::<Result<_, _>>()
+            .map_err(|kind| s.error(kind))?;
+        total += s.as_str().chars().count() - unescaped.len() + 2;
     }
-    total
+    Ok(total)
 }
 
-fn part2(strings: &[String]) -> usize {
+fn part2(strings: &[Span]) -> usize {
     let mut total = 0;
     for s in strings {
+        let s = s.as_str();
         total += s.escape_default().count() - s.chars().count() + 2;
     }
     total
 }
 
 pub fn main() -> Result<()> {
-    let fl = File::open("res/input08")?;
-    let reader = BufReader::new(fl);
-    let strings: std::result::Result<Vec<String>, _> = reader.lines().collect();
-    let strings = strings?;
+    let input = fs::read_to_string("res/input08")?;
+    let strings: Vec<Span> = Span::new(&input).lines().collect();
 
     println!("Day 08");
-    println!("Part 1: {}", part1(&strings));
+    println!("Part 1: {}", part1(&strings)?);
     println!("Part 2: {}", part2(&strings));
     Ok(())
 }
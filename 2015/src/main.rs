@@ -1,16 +1,18 @@
+use std::process::ExitCode;
+
 use adventofcode2015::*;
 
-fn main() -> Result<()> {
+fn main() -> ExitCode {
     println!("Advent of Code, 2015");
 
-    day01::main()?;
-    day02::main()?;
-    day03::main()?;
-    day04::main()?;
-    day05::main()?;
-    day06::main()?;
-    day07::main()?;
-    day08::main()?;
-
-    Ok(())
+    aoc::run_all([
+        ("day01", day01::main as aoc::DayMain),
+        ("day02", day02::main),
+        ("day03", day03::main),
+        ("day04", day04::main),
+        ("day05", day05::main),
+        ("day06", day06::main),
+        ("day07", day07::main),
+        ("day08", day08::main),
+    ])
 }
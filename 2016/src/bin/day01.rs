@@ -1,118 +1,22 @@
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::Read,
-    ops::{Add, Mul},
-};
+use std::{collections::HashMap, fs};
 
-trait VectorComponents<'a> {
-    type Iter: Iterator<Item = &'a i64>;
+use geometry::Vector2;
+use parse::{ParseError, Span};
 
-    fn components(&'a self) -> Self::Iter;
-}
-
-#[derive(Default, Clone, Copy, Hash, PartialEq, Eq)]
-struct Vec2 {
-    x: i64,
-    y: i64,
-}
-
-impl Vec2 {
-    const fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
-    }
-}
-
-impl Mul<i64> for Vec2 {
-    type Output = Vec2;
-
-    fn mul(self, rhs: i64) -> Self::Output {
-        Vec2::new(self.x * rhs, self.y * rhs)
-    }
-}
-
-impl Add for Vec2 {
-    type Output = Vec2;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Vec2::new(self.x + rhs.x, self.y + rhs.y)
-    }
-}
-
-struct Component2<'a> {
-    v: &'a Vec2,
-    idx: usize,
-}
-
-impl<'a> Component2<'a> {
-    const fn new(v: &'a Vec2) -> Self {
-        Self { v, idx: 0 }
-    }
-}
-
-impl<'a> Iterator for Component2<'a> {
-    type Item = &'a i64;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.idx {
-            0 => {
-                self.idx += 1;
-                Some(&self.v.x)
-            }
-            1 => {
-                self.idx += 1;
-                Some(&self.v.y)
-            }
-            _ => None,
-        }
-    }
-}
-
-impl<'a> VectorComponents<'a> for Vec2 {
-    type Iter = Component2<'a>;
-
-    fn components(&'a self) -> Self::Iter {
-        Self::Iter::new(&self)
-    }
-}
-
-trait L1Norm<'a>: VectorComponents<'a> {
-    type Norm;
-
-    fn l1norm(&self) -> Self::Norm;
-}
-
-impl<'a> L1Norm<'a> for Vec2 {
-    type Norm = i64;
-
-    fn l1norm(&self) -> Self::Norm {
-        self.components().map(|c| c.abs()).sum()
-    }
-}
-
-const fn rotate_left(v: Vec2) -> Vec2 {
-    Vec2::new(-v.y, v.x)
-}
-
-const fn rotate_right(v: Vec2) -> Vec2 {
-    Vec2::new(v.y, -v.x)
-}
-
-fn part1(directions: &[Vec2]) -> i64 {
-    let mut final_pos = Vec2::default();
+fn part1(directions: &[Vector2<i64>]) -> i64 {
+    let mut final_pos = Vector2::ZERO;
     for d in directions {
-        final_pos = final_pos + *d;
+        final_pos += *d;
     }
-    final_pos.l1norm()
+    final_pos.manhattan_norm()
 }
 
-fn part2(directions: &[Vec2]) -> i64 {
-    let mut visited_positions: HashMap<Vec2, u8> = HashMap::new();
-    let mut curr_pos = Vec2::default();
+fn part2(directions: &[Vector2<i64>]) -> i64 {
+    let mut visited_positions: HashMap<Vector2<i64>, u8> = HashMap::new();
+    let mut curr_pos = Vector2::ZERO;
     'outer: for d in directions {
-        let normed = Vec2::new(d.x / d.l1norm(), d.y / d.l1norm());
-        for _ in 0..d.l1norm() {
-            curr_pos = curr_pos + normed;
+        for _ in 0..d.manhattan_norm() {
+            curr_pos += d.signum();
             let visit_count = visited_positions
                 .entry(curr_pos)
                 .and_modify(|e| *e += 1)
@@ -122,29 +26,30 @@ fn part2(directions: &[Vec2]) -> i64 {
             }
         }
     }
-    curr_pos.l1norm()
+    curr_pos.manhattan_norm()
 }
 
-fn main() {
-    let mut fl = File::open("res/day01/input").unwrap();
-    let mut s = String::new();
-    fl.read_to_string(&mut s).unwrap();
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/day01/input")?;
 
-    let mut facing = Vec2::new(0, 1);
-    let directions: Vec<Vec2> = s
+    let mut facing = Vector2::new(0, 1);
+    let directions: Vec<Vector2<i64>> = Span::new(&input)
         .trim()
         .split(", ")
-        .map(|s| {
-            if &s[..1] == "L" {
-                facing = rotate_left(facing);
+        .map(|step| -> Result<_, ParseError> {
+            let distance = if let Ok(distance) = step.strip_prefix("L") {
+                facing = facing.rotate_left();
+                distance
             } else {
-                facing = rotate_right(facing);
-            }
-            facing * s[1..].parse().unwrap()
+                facing = facing.rotate_right();
+                step.strip_prefix("R")?
+            };
+            Ok(facing * distance.parse()?)
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
     println!("Day 01");
     println!("Part 1: {}", part1(&directions));
     println!("Part 2: {}", part2(&directions));
+    Ok(())
 }
@@ -1,4 +1,6 @@
-use std::{fs::File, io::{BufRead, BufReader}};
+use std::fs;
+
+use parse::{ErrorKind, ParseError, Span};
 
 #[derive(Debug)]
 struct Num(u8);
@@ -148,7 +150,7 @@ fn part1(directions: &[String]) -> Vec<Num> {
             'R' => a.right(),
             'U' => a.up(),
             'D' => a.down(),
-            _ => panic!("Unknown direction"),
+            _ => unreachable!("directions are checked while parsing"),
         }
     })).collect()
 }
@@ -160,15 +162,32 @@ fn part2(directions: &[String]) -> Vec<Num> {
             'R' => a.right2(),
             'U' => a.up2(),
             'D' => a.down2(),
-            _ => panic!("Unknown direction"),
+            _ => unreachable!("directions are checked while parsing"),
         }
     })).collect()
 }
 
-fn main() {
-    let fl = File::open("res/day02/input").unwrap();
-    let reader = BufReader::new(fl);
-    let directions: Vec<String> = reader.lines().map(|s| s.unwrap()).collect();
+fn parse_directions(input: &str) -> Result<Vec<String>, ParseError> {
+    Span::new(input)
+        .lines()
+        .map(|line| {
+            let mut chars = (line.column()..).zip(line.as_str().chars());
+            match chars.find(|(_, ch)| !"LRUD".contains(*ch)) {
+                Some((column, ch)) => Err(ParseError {
+                    line: line.line(),
+                    column,
+                    kind: ErrorKind::UnexpectedChar(ch),
+                }),
+                None => Ok(line.to_string()),
+            }
+        })
+        .collect()
+}
+
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/day02/input")?;
+    let directions = parse_directions(&input)?;
     println!("Part 1: {:?}", part1(&directions));
     println!("Part 2: {:?}", part2(&directions));
+    Ok(())
 }
@@ -1,7 +1,6 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::fs;
+
+use parse::Span;
 
 fn part1(mut possible_triangles: Vec<u64>) -> usize {
     let mut count = 0;
@@ -33,23 +32,14 @@ fn part2(possible_triangles: &[u64]) -> usize {
     count
 }
 
-fn main() {
-    let fl = File::open("res/day03/input").expect("couldn't open file");
-    let reader = BufReader::new(fl);
-    let numbers: Vec<u64> = reader
-        .lines()
-        .map(|line| {
-            let line = line.expect("couldn't read line from file");
-            line.split_whitespace()
-                .map(|s| {
-                    s.parse()
-                        .expect("couldn't interpret string from file as a u64")
-                })
-                .collect::<Vec<u64>>()
-        })
-        .flatten()
-        .collect();
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/day03/input")?;
+    let numbers: Vec<u64> = Span::new(&input)
+        .words()
+        .map(|word| word.parse())
+        .collect::<Result<_, _>>()?;
     println!("Day 03");
     println!("Part 1: {}", part1(numbers.clone()));
     println!("Part 2: {}", part2(&numbers));
+    Ok(())
 }
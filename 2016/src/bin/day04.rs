@@ -1,96 +1,146 @@
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufRead, BufReader},
-};
-
-fn main() {
-    let fl = File::open("res/day04/input").expect("couldn't open file");
-    let reader = BufReader::new(fl);
-    let maybe_rooms: Result<Vec<String>, _> = reader.lines().collect();
-    let rooms = maybe_rooms.expect("couldn't read lines from file");
-    println!("Day 04");
-    let real_rooms = get_real_rooms(&rooms);
-    println!("Part 1: {}", part1(&real_rooms));
-    println!("Part 2:");
-    part2(&real_rooms);
+use std::{collections::HashMap, fs};
+
+use aoc::Error;
+use parse::{ErrorKind, ParseError, Span};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Room {
+    name: Vec<String>,
+    sector_id: u64,
+    checksum: String,
 }
 
-fn get_real_rooms(rooms: &[String]) -> Vec<String> {
-    let mut real_rooms: Vec<String> = Vec::with_capacity(rooms.len() / 2);
-    for room in rooms {
+impl Room {
+    fn is_real(&self) -> bool {
         let mut letter_counts: HashMap<char, usize> = HashMap::new();
-        let mut parts = room.split('-').rev();
-        let id_checksum = parts.next().unwrap();
-        for part in parts {
-            part.chars().for_each(|ch| {
-                letter_counts
-                    .entry(ch)
-                    .and_modify(|count| *count += 1)
-                    .or_insert(1);
-            });
+        for ch in self.name.iter().flat_map(|word| word.chars()) {
+            *letter_counts.entry(ch).or_insert(0) += 1;
         }
-        let (_, checksum) = id_checksum.split_once('[').unwrap();
-        let checksum = &checksum[..(checksum.len() - 1)];
-        let mut prevalence: Vec<usize> = letter_counts.values().copied().collect();
-        prevalence.sort_unstable();
-        prevalence.reverse();
-        let mut is_decoy = false;
-        for (idx, ch) in checksum.chars().enumerate() {
-            if !letter_counts.contains_key(&ch) {
-                is_decoy = true;
-                break;
-            }
-            if letter_counts[&ch] != prevalence[idx] {
-                is_decoy = true;
-                break;
-            }
-        }
-        if !is_decoy {
-            real_rooms.push(room.clone());
+        let mut letters: Vec<(char, usize)> = letter_counts.into_iter().collect();
+        letters
+            .sort_unstable_by(|(ch1, count1), (ch2, count2)| count2.cmp(count1).then(ch1.cmp(ch2)));
+        letters
+            .iter()
+            .take(5)
+            .map(|&(ch, _)| ch)
+            .eq(self.checksum.chars())
+    }
+
+    fn decrypt(&self) -> Vec<String> {
+        self.name
+            .iter()
+            .map(|word| {
+                word.chars()
+                    .map(|ch| reverse_shift_cipher(ch, self.sector_id))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/day04/input")?;
+    let rooms = parse_input(&input)?;
+    println!("Day 04");
+    let real_rooms: Vec<Room> = rooms.into_iter().filter(Room::is_real).collect();
+    println!("Part 1: {}", part1(&real_rooms));
+    println!("Part 2: {}", part2(&real_rooms)?);
+    Ok(())
+}
+
+/// Check that `letters` is made of lowercase letters only.
+fn lowercase(letters: Span) -> Result<String, ParseError> {
+    let mut rest = letters;
+    while let Some((ch, after)) = rest.split_first() {
+        if !ch.is_ascii_lowercase() {
+            return Err(rest.error(ErrorKind::UnexpectedChar(ch)));
         }
+        rest = after;
     }
-    real_rooms
+    Ok(letters.as_str().to_string())
 }
 
-fn part1(rooms: &[String]) -> u64 {
-    let mut sector_sum = 0;
-    for room in rooms {
-        let id_checksum = room.split('-').last().unwrap();
-        let (sector_id, _) = id_checksum.split_once('[').unwrap();
-        let sector_id: u64 = sector_id.parse().expect("couldn't parse sector id as u64");
-        sector_sum += sector_id;
+fn parse_room(line: Span) -> Result<Room, ParseError> {
+    let (rest, checksum) = line.strip_suffix("]")?.split_once("[")?;
+    let parts: Vec<Span> = rest.split("-").collect();
+    match parts.split_last() {
+        Some((sector_id, name)) if !name.is_empty() => Ok(Room {
+            name: name
+                .iter()
+                .map(|&word| lowercase(word))
+                .collect::<Result<_, _>>()?,
+            sector_id: sector_id.parse()?,
+            checksum: lowercase(checksum)?,
+        }),
+        _ => Err(rest.expected("a name followed by a sector id")),
     }
-    sector_sum
+}
+
+fn parse_input(input: &str) -> Result<Vec<Room>, ParseError> {
+    Span::new(input)
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(parse_room)
+        .collect()
+}
+
+fn part1(rooms: &[Room]) -> u64 {
+    rooms.iter().map(|room| room.sector_id).sum()
 }
 
 fn reverse_shift_cipher(ch: char, shift: u64) -> char {
-    let i_a = 'a' as u64;
-    let i_ch = ch as u64;
-    let shift = shift % 26;
-    let i_ch = ((i_ch - i_a) + shift) % 26 + i_a;
-    char::from_u32(i_ch as u32).unwrap()
+    let offset = (u64::from(ch as u8 - b'a') + shift) % 26;
+    char::from(b'a' + offset as u8)
 }
 
-fn part2(rooms: &[String]) {
-    for room in rooms {
-        let mut parts = room.split('-').rev();
-        let id_checksum = parts.next().unwrap();
-        let (sector_id, _) = id_checksum.split_once('[').unwrap();
-        let sector_id: u64 = sector_id.parse().expect("couldn't parse sector id as u64");
-        let mut words = Vec::new();
-        let mut to_be_printed = false;
-        for part in parts {
-            let word: String = part
-                .chars()
-                .map(|ch| reverse_shift_cipher(ch, sector_id))
-                .collect();
-            to_be_printed = word == "northpole";
-            words.push(word);
-        }
-        if to_be_printed {
-            println!("{words:?} id: {sector_id}");
-            break;
-        }
+fn part2(rooms: &[Room]) -> aoc::Result<u64> {
+    rooms
+        .iter()
+        .find(|room| room.decrypt().iter().any(|word| word == "northpole"))
+        .map(|room| room.sector_id)
+        .ok_or_else(|| Error::no_solution("no room stores the north pole objects"))
+}
+
+#[cfg(test)]
+mod test {
+    use parse::ErrorKind;
+
+    use crate::{parse_input, reverse_shift_cipher};
+
+    #[test]
+    fn test_case_1() {
+        let rooms = parse_input(
+            "aaaaa-bbb-z-y-x-123[abxyz]
+a-b-c-d-e-f-g-h-987[abcde]
+not-a-real-room-404[oarel]
+totally-real-room-200[decoy]
+",
+        )
+        .unwrap();
+        let real: Vec<bool> = rooms.iter().map(|room| room.is_real()).collect();
+        assert_eq!(real, [true, true, true, false]);
+    }
+
+    #[test]
+    fn test_case_2() {
+        let word: String = "qzmtzixmtkozyivhz"
+            .chars()
+            .map(|ch| reverse_shift_cipher(ch, 343))
+            .collect();
+        assert_eq!(word, "veryencryptedname");
+    }
+
+    #[test]
+    fn test_case_invalid_input() {
+        let error = parse_input("aaaaa-bbb-123[abxyz]\naa-bB-123[abxyz]").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.kind),
+            (2, 5, ErrorKind::UnexpectedChar('B'))
+        );
+        let error = parse_input("aaaaa-bbb-12x[abxyz]").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 11));
+        let error = parse_input("123[abxyz]").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
     }
 }
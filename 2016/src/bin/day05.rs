@@ -2,12 +2,13 @@ use std::collections::HashMap;
 
 use md5::{Digest, Md5};
 
-const INPUT: &str = "ffykfhsq";
-
-fn main() {
+fn main() -> aoc::Result<()> {
+    let input = aoc::puzzle_input(2016, 5)?;
+    let door_id = input.trim();
     println!("Day 05");
-    println!("Part 1: {}", part1(INPUT.to_string()));
-    println!("Part 2: {}", part2(INPUT.to_string()));
+    println!("Part 1: {}", part1(door_id.to_string()));
+    println!("Part 2: {}", part2(door_id.to_string()));
+    Ok(())
 }
 
 fn part1(door_id: String) -> String {
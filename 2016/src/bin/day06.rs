@@ -1,72 +1,109 @@
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::{collections::HashMap, fs};
 
-fn main() {
-    let fl = File::open("res/day06/input").expect("couldn't open file");
-    let reader = BufReader::new(fl);
-    let messages: Vec<Vec<char>> = reader
-        .lines()
-        .map(|line| {
-            let line = line.expect("couldn't read line from file");
-            line.chars().collect()
-        })
-        .collect();
+use aoc::Error;
+use geometry::Grid;
+use parse::{ParseError, Span};
+
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/day06/input")?;
+    let messages = parse_input(&input)?;
     println!("Day 06");
-    println!("Part 1: {}", part1(&messages));
-    println!("Part 2: {}", part2(&messages));
+    println!("Part 1: {}", part1(&messages)?);
+    println!("Part 2: {}", part2(&messages)?);
+    Ok(())
+}
+
+/// The messages as the rows of a grid, so that they all have the same length.
+fn parse_input(input: &str) -> Result<Grid<char>, ParseError> {
+    Span::new(input).grid(|ch| ch.is_ascii_lowercase().then_some(ch))
 }
 
-fn part1(messages: &[Vec<char>]) -> String {
-    if messages.is_empty() {
-        return "".to_string();
+fn letter_counts<'a>(column: impl Iterator<Item = &'a char>) -> HashMap<char, u64> {
+    let mut counts = HashMap::new();
+    for &ch in column {
+        *counts.entry(ch).or_insert(0) += 1;
     }
-    let width = messages[0].len();
-    let mut chars_counts = vec![HashMap::new(); width];
-    for message in messages {
-        for i in 0..width {
-            chars_counts[i]
-                .entry(message[i])
-                .and_modify(|count| *count += 1)
-                .or_insert(1u64);
-        }
+    counts
+}
+
+/// The message made of the letter `pick` chooses from each column's letter
+/// counts.
+fn correct(
+    messages: &Grid<char>,
+    pick: impl Fn(HashMap<char, u64>) -> Option<char>,
+) -> aoc::Result<String> {
+    if messages.height() == 0 {
+        return Err(Error::no_solution("there are no messages to correct"));
     }
-    chars_counts
-        .into_iter()
-        .map(|ch_counts| {
-            ch_counts
-                .into_iter()
-                .max_by_key(|(_, count)| *count)
-                .unwrap()
-                .0
-        })
-        .collect()
+    messages
+        .columns()
+        .map(|column| pick(letter_counts(column)))
+        .collect::<Option<String>>()
+        .ok_or_else(|| Error::invalid_state("a column of the messages has no letters"))
+}
+
+fn part1(messages: &Grid<char>) -> aoc::Result<String> {
+    correct(messages, |counts| {
+        counts
+            .into_iter()
+            .max_by_key(|(_, count)| *count)
+            .map(|(ch, _)| ch)
+    })
 }
 
-fn part2(messages: &[Vec<char>]) -> String {
-    if messages.is_empty() {
-        return "".to_string();
+fn part2(messages: &Grid<char>) -> aoc::Result<String> {
+    correct(messages, |counts| {
+        counts
+            .into_iter()
+            .min_by_key(|(_, count)| *count)
+            .map(|(ch, _)| ch)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use parse::ErrorKind;
+
+    use crate::{parse_input, part1, part2};
+
+    const MESSAGES: &str = "eedadn
+drvtee
+eandsr
+raavrd
+atevrs
+tsrnev
+sdttsa
+rasrtv
+nssdts
+ntnada
+svetve
+tesnvt
+vntsnd
+vrdear
+dvrsen
+enarar
+";
+
+    #[test]
+    fn test_case_1() {
+        let messages = parse_input(MESSAGES).unwrap();
+        assert_eq!(part1(&messages).unwrap(), "easter");
+        assert_eq!(part2(&messages).unwrap(), "advent");
     }
-    let width = messages[0].len();
-    let mut chars_counts = vec![HashMap::new(); width];
-    for message in messages {
-        for i in 0..width {
-            chars_counts[i]
-                .entry(message[i])
-                .and_modify(|count| *count += 1)
-                .or_insert(1u64);
-        }
+
+    #[test]
+    fn test_case_invalid_input() {
+        let error = parse_input("eedadn\ndrvte\n").unwrap_err();
+        assert_eq!(
+            (error.line, error.kind),
+            (
+                2,
+                ErrorKind::RaggedRow {
+                    expected: 6,
+                    found: 5
+                }
+            )
+        );
+        assert!(part1(&parse_input("").unwrap()).is_err());
     }
-    chars_counts
-        .into_iter()
-        .map(|ch_counts| {
-            ch_counts
-                .into_iter()
-                .min_by_key(|(_, count)| *count)
-                .unwrap()
-                .0
-        })
-        .collect()
 }
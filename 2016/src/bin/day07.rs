@@ -47,13 +47,13 @@ impl Ip {
     }
 }
 
-fn main() {
-    let fl = File::open("res/day07/input").expect("couldn't read file");
+fn main() -> aoc::Result<()> {
+    let fl = File::open("res/day07/input")?;
     let reader = BufReader::new(fl);
     let ips: Vec<Ip> = reader
         .lines()
         .map(|line| {
-            let line = line.expect("couldn't read line from file");
+            let line = line?;
             let mut supernet_sequences = Vec::with_capacity(4);
             let mut hypernet_sequences = Vec::with_capacity(4);
             let mut ord = true;
@@ -65,12 +65,13 @@ fn main() {
                 }
                 ord = !ord;
             }
-            Ip::new(supernet_sequences, hypernet_sequences)
+            Ok(Ip::new(supernet_sequences, hypernet_sequences))
         })
-        .collect();
+        .collect::<aoc::Result<_>>()?;
     println!("Day 07");
     println!("Part 1: {}", part1(&ips));
     println!("Part 2: {}", part2(&ips));
+    Ok(())
 }
 
 /// Check whether given string has Autonomous Bridge Bypass Annotation (ABBA)
@@ -1,10 +1,6 @@
-use std::{
-    fmt,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::{fmt, fs};
 
-use regex::Regex;
+use parse::{ParseError, Span};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Screen {
@@ -98,36 +94,36 @@ enum Instruction {
     Column { column: usize, shift: usize },
 }
 
-fn main() {
-    let fl = File::open("res/day08/input").expect("couldn't open file");
-    let reader = BufReader::new(fl);
-
-    let rect_regex = Regex::new(r"^rect (\d+)x(\d+)$").unwrap();
-    let row_regex = Regex::new(r"^rotate row y=(\d+) by (\d+)$").unwrap();
-    let column_regex = Regex::new(r"^rotate column x=(\d+) by (\d+)$").unwrap();
+fn parse_instruction(line: Span) -> Result<Instruction, ParseError> {
+    if let Ok(size) = line.strip_prefix("rect ") {
+        let (width, height) = size.split_once("x")?;
+        Ok(Instruction::Rect {
+            width: width.parse()?,
+            height: height.parse()?,
+        })
+    } else if let Ok(rotation) = line.strip_prefix("rotate row y=") {
+        let (row, shift) = rotation.split_once(" by ")?;
+        Ok(Instruction::Row {
+            row: row.parse()?,
+            shift: shift.parse()?,
+        })
+    } else if let Ok(rotation) = line.strip_prefix("rotate column x=") {
+        let (column, shift) = rotation.split_once(" by ")?;
+        Ok(Instruction::Column {
+            column: column.parse()?,
+            shift: shift.parse()?,
+        })
+    } else {
+        Err(line.expected("a rect, rotate row or rotate column instruction"))
+    }
+}
 
-    let instructions: Vec<Instruction> = reader
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/day08/input")?;
+    let instructions: Vec<Instruction> = Span::new(&input)
         .lines()
-        .map(|line| {
-            let line = line.expect("couldn't read line from file");
-            if let Some(captures) = rect_regex.captures(&line) {
-                let width: usize = captures.get(1).unwrap().as_str().parse().unwrap();
-                let height: usize = captures.get(2).unwrap().as_str().parse().unwrap();
-                return Instruction::Rect { width, height };
-            }
-            if let Some(captures) = row_regex.captures(&line) {
-                let row: usize = captures.get(1).unwrap().as_str().parse().unwrap();
-                let shift: usize = captures.get(2).unwrap().as_str().parse().unwrap();
-                return Instruction::Row { row, shift };
-            }
-            if let Some(captures) = column_regex.captures(&line) {
-                let column: usize = captures.get(1).unwrap().as_str().parse().unwrap();
-                let shift: usize = captures.get(2).unwrap().as_str().parse().unwrap();
-                return Instruction::Column { column, shift };
-            }
-            panic!("line did not match any instruction pattern");
-        })
-        .collect();
+        .map(parse_instruction)
+        .collect::<Result<_, _>>()?;
 
     println!("Day 08");
     let screen = part1(&instructions);
@@ -137,6 +133,7 @@ fn main() {
     );
     println!("Part 2:");
     println!("{screen}");
+    Ok(())
 }
 
 fn part1(instructions: &[Instruction]) -> Screen {
@@ -1,7 +1,6 @@
-use std::{
-    fs::File,
-    io::{BufReader, Read},
-};
+use std::{fs, num::ParseIntError};
+
+use parse::{ErrorKind, ParseError, Span};
 
 #[derive(Debug, Clone, Copy)]
 enum Stage {
@@ -11,51 +10,61 @@ enum Stage {
     CollectMultiChars,
 }
 
-fn main() {
-    let fl = File::open("res/day09/input").expect("couldn't open the file");
-    let mut reader = BufReader::new(fl);
-    let mut data = String::new();
-    reader
-        .read_to_string(&mut data)
-        .expect("couldn't read the file");
-    data = data.trim().to_string();
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/day09/input")?;
+    let data = Span::new(&input).trim();
     println!("Day 09");
-    let decompressed_str = part1(&data);
+    let decompressed_str = part1(data)?;
     println!("Part 1: {}", decompressed_str.len());
-    println!("Part 2: {}", part2(&data));
+    println!("Part 2: {}", part2(data)?);
+    Ok(())
+}
+
+/// Parse a number in a marker, whose digits start at `column` on the line of
+/// `data`.
+fn parse_marker_number(data: Span, column: usize, digits: &str) -> Result<usize, ParseError> {
+    digits.parse().map_err(|err: ParseIntError| ParseError {
+        line: data.line(),
+        column,
+        kind: ErrorKind::InvalidValue {
+            found: digits.to_string(),
+            reason: err.to_string(),
+        },
+    })
 }
 
-fn part1(data: &str) -> String {
-    let mut decompressed = Vec::with_capacity(data.len());
+fn part1(data: Span) -> Result<String, ParseError> {
+    let mut decompressed = Vec::with_capacity(data.as_str().len());
     let mut stage = Stage::FindOpenBracket;
     let mut char_count_str = String::new();
     let mut char_count = 0;
     let mut multiplier_str = String::new();
     let mut multiplier = 0;
     let mut multi_chars = vec![];
-    for ch in data.chars() {
+    let mut digits_column = 0;
+    for (column, ch) in (data.column()..).zip(data.as_str().chars()) {
         match stage {
             Stage::FindOpenBracket => {
                 if ch == '(' {
                     stage = Stage::FindCharCount;
+                    digits_column = column + 1;
                 } else {
                     decompressed.push(ch);
                 }
             }
             Stage::FindCharCount => {
                 if ch == 'x' {
-                    char_count = char_count_str
-                        .parse()
-                        .expect("couldn't parse character count");
+                    char_count = parse_marker_number(data, digits_column, &char_count_str)?;
                     char_count_str.clear();
                     stage = Stage::FindMultiplier;
+                    digits_column = column + 1;
                 } else {
                     char_count_str.push(ch);
                 }
             }
             Stage::FindMultiplier => {
                 if ch == ')' {
-                    multiplier = multiplier_str.parse().expect("couldn't parse multiplier");
+                    multiplier = parse_marker_number(data, digits_column, &multiplier_str)?;
                     multiplier_str.clear();
                     stage = Stage::CollectMultiChars;
                 } else {
@@ -75,10 +84,12 @@ fn part1(data: &str) -> String {
             }
         }
     }
-    decompressed.iter().collect()
+    Ok(decompressed.iter().collect())
 }
 
-fn decompress_length(data: &[char]) -> usize {
+/// The decompressed length of `data`, whose first character is at `column`
+/// on the line of `span`.
+fn decompress_length(span: Span, data: &[char], column: usize) -> Result<usize, ParseError> {
     let mut length = 0;
     let mut stage = Stage::FindOpenBracket;
     let mut char_count_str = String::new();
@@ -86,31 +97,33 @@ fn decompress_length(data: &[char]) -> usize {
     let mut multiplier_str = String::new();
     let mut multiplier: usize;
     let mut i = 0;
+    let mut digits_start = 0;
     while i < data.len() {
         match stage {
             Stage::FindOpenBracket => {
                 if data[i] == '(' {
                     stage = Stage::FindCharCount;
+                    digits_start = i + 1;
                 } else {
                     length += 1;
                 }
             }
             Stage::FindCharCount => {
                 if data[i] == 'x' {
-                    char_count = char_count_str
-                        .parse()
-                        .expect("couldn't parse character count");
+                    char_count = parse_marker_number(span, column + digits_start, &char_count_str)?;
                     char_count_str.clear();
                     stage = Stage::FindMultiplier;
+                    digits_start = i + 1;
                 } else {
                     char_count_str.push(data[i]);
                 }
             }
             Stage::FindMultiplier => {
                 if data[i] == ')' {
-                    multiplier = multiplier_str.parse().expect("couldn't parse multiplier");
+                    multiplier = parse_marker_number(span, column + digits_start, &multiplier_str)?;
                     multiplier_str.clear();
-                    length += multiplier * decompress_length(&data[(i + 1)..(i + 1 + char_count)]);
+                    let repeated = &data[(i + 1)..(i + 1 + char_count)];
+                    length += multiplier * decompress_length(span, repeated, column + i + 1)?;
                     i += char_count;
                     stage = Stage::FindOpenBracket;
                 } else {
@@ -121,48 +134,58 @@ fn decompress_length(data: &[char]) -> usize {
         }
         i += 1;
     }
-    length
+    Ok(length)
 }
 
-fn part2(data: &str) -> usize {
-    let chars: Vec<char> = data.chars().collect();
-    decompress_length(&chars)
+fn part2(data: Span) -> Result<usize, ParseError> {
+    let chars: Vec<char> = data.as_str().chars().collect();
+    decompress_length(data, &chars, data.column())
 }
 
 #[cfg(test)]
 mod tests {
+    use parse::Span;
+
     use crate::{part1, part2};
 
     #[test]
     fn test_case_1() {
-        let data = "ADVENT";
-        assert_eq!(data, part1(data));
-        assert_eq!(6, part2(data));
+        let data = Span::new("ADVENT");
+        assert_eq!(data.as_str(), part1(data).unwrap());
+        assert_eq!(6, part2(data).unwrap());
     }
 
     #[test]
     fn test_case_2() {
-        let data = "A(1x5)BC";
+        let data = Span::new("A(1x5)BC");
         let expected = "ABBBBBC";
-        assert_eq!(expected, part1(data));
-        assert_eq!(7, part2(data));
+        assert_eq!(expected, part1(data).unwrap());
+        assert_eq!(7, part2(data).unwrap());
     }
 
     #[test]
     fn test_case_3() {
-        let data = "(3x3)XYZ";
-        assert_eq!(9, part2(data));
+        let data = Span::new("(3x3)XYZ");
+        assert_eq!(9, part2(data).unwrap());
     }
 
     #[test]
     fn test_case_4() {
-        let data = "X(8x2)(3x3)ABCY";
-        assert_eq!(20, part2(data));
+        let data = Span::new("X(8x2)(3x3)ABCY");
+        assert_eq!(20, part2(data).unwrap());
     }
 
     #[test]
     fn test_case_5() {
-        let data = "(27x12)(20x12)(13x14)(7x10)(1x12)A";
-        assert_eq!(241920, part2(data));
+        let data = Span::new("(27x12)(20x12)(13x14)(7x10)(1x12)A");
+        assert_eq!(241920, part2(data).unwrap());
+    }
+
+    #[test]
+    fn test_case_invalid_marker() {
+        let error = part2(Span::new("AB(3xq)CDE")).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 6));
+        let error = part1(Span::new("A(1x5)B(2xq)C")).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 11));
     }
 }
@@ -1,11 +1,7 @@
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::{collections::HashMap, fs};
 
-use regex::Regex;
-use thiserror::Error;
+use aoc::Error;
+use parse::{ParseError, Span};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Destination {
@@ -23,24 +19,20 @@ struct Bot {
 
 type BotOutput = (Destination, u8);
 
-#[derive(Debug, Error)]
-enum BotError {
-    #[error("attempt to add chip to a full bot")]
-    FullCapacity,
-}
-
 impl Bot {
     const fn is_full(&self) -> bool {
         self.chip1.is_some() && self.chip2.is_some()
     }
 
-    fn add_chip(&mut self, chip: u8) -> Result<(), BotError> {
+    fn add_chip(&mut self, chip: u8) -> aoc::Result<()> {
         if self.chip1.is_none() {
             self.chip1 = Some(chip);
         } else if self.chip2.is_none() {
             self.chip2 = Some(chip);
         } else {
-            return Err(BotError::FullCapacity);
+            return Err(Error::invalid_state(format!(
+                "chip {chip} given to a bot already holding two chips"
+            )));
         }
         Ok(())
     }
@@ -69,152 +61,124 @@ struct Factory {
 }
 
 impl Factory {
-    fn step(&mut self) -> usize {
+    fn step(&mut self) -> aoc::Result<usize> {
         let mut new_bots = self.bots.clone();
         let mut new_outputs = self.outputs.clone();
         let mut worked = 0;
         for i in 0..self.bots.len() {
-            match self.bots.get_mut(&i).unwrap().work() {
-                Some((out1, out2)) => {
-                    match out1 {
-                        (Destination::Bot(d1), c1) => {
-                            new_bots.entry(d1).and_modify(|bot| {
-                                bot.add_chip(c1).expect("bot should not be full")
-                            });
-                        }
-                        (Destination::Output(d1), c1) => {
-                            new_outputs.entry(d1).and_modify(|output| output.push(c1));
+            let bot = self
+                .bots
+                .get_mut(&i)
+                .ok_or_else(|| Error::invalid_state(format!("there is no bot {i}")))?;
+            if let Some((out1, out2)) = bot.work() {
+                for (destination, chip) in [out1, out2] {
+                    match destination {
+                        Destination::Bot(d) => {
+                            if let Some(bot) = new_bots.get_mut(&d) {
+                                bot.add_chip(chip)?;
+                            }
                         }
-                    }
-                    match out2 {
-                        (Destination::Bot(d2), c2) => {
-                            new_bots.entry(d2).and_modify(|bot| {
-                                bot.add_chip(c2).expect("bot should not be full")
-                            });
-                        }
-                        (Destination::Output(d2), c2) => {
-                            new_outputs.entry(d2).and_modify(|output| output.push(c2));
+                        Destination::Output(d) => {
+                            new_outputs.entry(d).and_modify(|output| output.push(chip));
                         }
                     }
-                    new_bots.insert(i, self.bots[&i]);
-                    worked += 1;
                 }
-                _ => (),
+                new_bots.insert(i, *bot);
+                worked += 1;
             }
         }
         self.bots = new_bots;
         self.outputs = new_outputs;
-        worked
+        Ok(worked)
     }
 }
 
-fn main() {
-    let fl = File::open("res/day10/input").expect("couldn't open the file");
-    let reader = BufReader::new(fl);
-    let factory = parse_input(reader);
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/day10/input")?;
+    let factory = parse_input(&input)?;
     println!("Day 10");
-    println!("Part 1: {}", part1(factory.clone(), 61, 17));
-    let finished_factory = part2(factory);
+    println!("Part 1: {}", part1(factory.clone(), 61, 17)?);
+    let finished_factory = part2(factory)?;
     println!("Part 2: {:?}", finished_factory.outputs);
+    Ok(())
 }
 
-fn parse_input<T: BufRead>(reader: T) -> Factory {
-    let input_pattern = Regex::new(r"^value (?P<value>\d+) goes to bot (?P<bot>\d+)$").unwrap();
-    let instruction_pattern = Regex::new(r"^bot (?P<bot>\d+) gives low to (?P<low>[a-z]+) (?P<low_n>\d+) and high to (?P<high>[a-z]+) (?P<high_n>\d+)$").unwrap();
+const EMPTY_BOT: Bot = Bot {
+    chip1: None,
+    chip2: None,
+    low: Destination::Output(0),
+    high: Destination::Output(0),
+};
 
+fn parse_destination(destination: Span) -> Result<Destination, ParseError> {
+    let (kind, number) = destination.split_once(" ")?;
+    match kind.as_str() {
+        "output" => Ok(Destination::Output(number.parse()?)),
+        "bot" => Ok(Destination::Bot(number.parse()?)),
+        _ => Err(kind.expected("output or bot")),
+    }
+}
+
+fn parse_input(input: &str) -> aoc::Result<Factory> {
     let mut outputs: HashMap<usize, Vec<u8>> = HashMap::new();
     let mut bots: HashMap<usize, Bot> = HashMap::new();
-    for maybe_line in reader.lines() {
-        let line = maybe_line.expect("couldn't read line from file");
-        if let Some(caps) = input_pattern.captures(&line) {
-            let bot_num = caps.name("bot").unwrap().as_str().parse().unwrap();
-            let chip_value = caps.name("value").unwrap().as_str().parse().unwrap();
-            bots.entry(bot_num).and_modify(|bot| {
-                if bot.chip1.is_none() {
-                    bot.chip1 = Some(chip_value);
-                } else if bot.chip2.is_none() {
-                    bot.chip2 = Some(chip_value);
-                } else {
-                    panic!("invalid initialization found, both slots of bot {bot_num} already filled");
-                }
-            }).or_insert(Bot { chip1: Some(chip_value), chip2: None, low: Destination::Output(0), high: Destination::Output(0) });
-        } else if let Some(caps) = instruction_pattern.captures(&line) {
-            let bot_num = caps.name("bot").unwrap().as_str().parse().unwrap();
-            let low = caps.name("low").unwrap().as_str();
-            let low_num = caps.name("low_n").unwrap().as_str().parse().unwrap();
-            let high = caps.name("high").unwrap().as_str();
-            let high_num = caps.name("high_n").unwrap().as_str().parse().unwrap();
-            let low_dest = match low {
-                "output" => {
-                    outputs.entry(low_num).or_default();
-                    Destination::Output(low_num)
-                }
-                "bot" => {
-                    bots.entry(low_num).or_insert(Bot {
-                        chip1: None,
-                        chip2: None,
-                        low: Destination::Output(0),
-                        high: Destination::Output(0),
-                    });
-                    Destination::Bot(low_num)
-                }
-                dest => panic!("invalid destination found {dest}"),
-            };
-            let high_dest = match high {
-                "output" => {
-                    outputs.entry(high_num).or_default();
-                    Destination::Output(high_num)
-                }
-                "bot" => {
-                    bots.entry(high_num).or_insert(Bot {
-                        chip1: None,
-                        chip2: None,
-                        low: Destination::Output(0),
-                        high: Destination::Output(0),
-                    });
-                    Destination::Bot(high_num)
-                }
-                dest => panic!("invalid destination found {dest}"),
-            };
+    for line in Span::new(input).lines() {
+        if let Ok(rest) = line.strip_prefix("value ") {
+            let (chip_value, bot_num) = rest.split_once(" goes to bot ")?;
+            let (chip_value, bot_num) = (chip_value.parse()?, bot_num.parse()?);
             bots.entry(bot_num)
-                .and_modify(|bot| {
-                    bot.low = low_dest;
-                    bot.high = high_dest;
-                })
-                .or_insert(Bot {
-                    chip1: None,
-                    chip2: None,
-                    low: low_dest,
-                    high: high_dest,
-                });
+                .or_insert(EMPTY_BOT)
+                .add_chip(chip_value)?;
+        } else if let Ok(rest) = line.strip_prefix("bot ") {
+            let (bot_num, rest) = rest.split_once(" gives low to ")?;
+            let (low, high) = rest.split_once(" and high to ")?;
+            let (low_dest, high_dest) = (parse_destination(low)?, parse_destination(high)?);
+            for dest in [low_dest, high_dest] {
+                match dest {
+                    Destination::Output(n) => {
+                        outputs.entry(n).or_default();
+                    }
+                    Destination::Bot(n) => {
+                        bots.entry(n).or_insert(EMPTY_BOT);
+                    }
+                }
+            }
+            let bot = bots.entry(bot_num.parse()?).or_insert(EMPTY_BOT);
+            bot.low = low_dest;
+            bot.high = high_dest;
+        } else {
+            return Err(line.expected("a value or bot instruction").into());
         }
     }
-    Factory { bots, outputs }
+    Ok(Factory { bots, outputs })
 }
 
-fn part1(mut factory: Factory, chip1: u8, chip2: u8) -> usize {
+fn part1(mut factory: Factory, chip1: u8, chip2: u8) -> aoc::Result<usize> {
     loop {
         for (idx, bot) in &factory.bots {
-            if bot.is_full() {
-                if (bot.chip1 == Some(chip1) && bot.chip2 == Some(chip2))
-                    || (bot.chip1 == Some(chip2) && bot.chip2 == Some(chip1))
-                {
-                    return *idx;
-                }
+            if bot.is_full()
+                && ((bot.chip1 == Some(chip1) && bot.chip2 == Some(chip2))
+                    || (bot.chip1 == Some(chip2) && bot.chip2 == Some(chip1)))
+            {
+                return Ok(*idx);
             }
         }
-        factory.step();
+        if factory.step()? == 0 {
+            return Err(Error::no_solution(format!(
+                "no bot ever compares chips {chip1} and {chip2}"
+            )));
+        }
     }
 }
 
-fn part2(mut factory: Factory) -> Factory {
-    while factory.step() != 0 {}
-    factory
+fn part2(mut factory: Factory) -> aoc::Result<Factory> {
+    while factory.step()? != 0 {}
+    Ok(factory)
 }
 
 #[cfg(test)]
 mod test {
-    use std::io::Cursor;
+    use aoc::Error;
 
     use crate::{parse_input, part1, Factory};
 
@@ -226,14 +190,13 @@ bot 1 gives low to output 1 and high to bot 0
 bot 0 gives low to output 2 and high to output 0
 value 2 goes to bot 2
 ";
-        let reader = Cursor::new(instructions);
-        parse_input(reader)
+        parse_input(instructions).unwrap()
     }
 
     #[test]
     fn test_case_1() {
         let mut factory = setup_factory();
-        factory.step();
+        factory.step().unwrap();
         assert_eq!(factory.bots[&2].chip1, None);
         assert_eq!(factory.bots[&2].chip2, None);
         assert_eq!(factory.bots[&1].chip1, Some(3));
@@ -245,6 +208,32 @@ value 2 goes to bot 2
     #[test]
     fn test_case_2() {
         let factory = setup_factory();
-        assert_eq!(part1(factory, 5, 2), 2);
+        assert_eq!(part1(factory, 5, 2).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_case_no_comparison() {
+        let factory = setup_factory();
+        assert!(matches!(part1(factory, 5, 1), Err(Error::NoSolution(_))));
+    }
+
+    #[test]
+    fn test_case_invalid_input() {
+        let error =
+            parse_input("value 1 goes to bot 0\nbot 0 gives low to robot 1 and high to bot 2")
+                .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 20: expected output or bot, found \"robot\""
+        );
+        let error =
+            parse_input("value 1 goes to bot 0\nvalue 2 goes to bot 0\nvalue 3 goes to bot 0")
+                .unwrap_err();
+        assert!(matches!(error, Error::InvalidState(_)));
+        let mut factory = parse_input(
+            "value 1 goes to bot 1\nvalue 2 goes to bot 1\nbot 1 gives low to output 0 and high to output 1",
+        )
+        .unwrap();
+        assert!(matches!(factory.step(), Err(Error::InvalidState(_))));
     }
 }
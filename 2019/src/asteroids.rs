@@ -0,0 +1,280 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashSet, VecDeque},
+    str::FromStr,
+};
+
+use parse::{ErrorKind, ParseError, Span};
+
+/// A map location, `x` growing to the right and `y` growing downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+}
+
+/// The direction from one point to another, reduced so that all points on
+/// the same ray share a direction.
+///
+/// Directions are ordered clockwise starting from straight up, using only
+/// integer arithmetic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Direction {
+    dx: i64,
+    dy: i64,
+}
+
+impl Direction {
+    /// The direction from `from` to `to`, `None` if they are the same point.
+    pub fn between(from: Point, to: Point) -> Option<Self> {
+        let (dx, dy) = (to.x - from.x, to.y - from.y);
+        if dx == 0 && dy == 0 {
+            return None;
+        }
+        let divisor = gcd(dx.unsigned_abs(), dy.unsigned_abs()) as i64;
+        Some(Direction {
+            dx: dx / divisor,
+            dy: dy / divisor,
+        })
+    }
+
+    pub fn dx(&self) -> i64 {
+        self.dx
+    }
+
+    pub fn dy(&self) -> i64 {
+        self.dy
+    }
+
+    // 0 for straight up and everything clockwise of it up to but excluding
+    // straight down, 1 for the rest
+    fn half(&self) -> u8 {
+        if self.dx > 0 || (self.dx == 0 && self.dy < 0) {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+impl Ord for Direction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.half().cmp(&other.half()).then_with(|| {
+            // with y pointing down, a positive cross product means `other` is
+            // clockwise of `self`
+            let cross = self.dx * other.dy - self.dy * other.dx;
+            0.cmp(&cross)
+        })
+    }
+}
+
+impl PartialOrd for Direction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsteroidField {
+    asteroids: Vec<Point>,
+}
+
+impl AsteroidField {
+    pub fn new(asteroids: Vec<Point>) -> Self {
+        AsteroidField { asteroids }
+    }
+
+    pub fn asteroids(&self) -> &[Point] {
+        &self.asteroids
+    }
+
+    /// Number of other asteroids in direct line of sight from `station`.
+    pub fn visible_from(&self, station: Point) -> usize {
+        self.asteroids
+            .iter()
+            .filter_map(|&asteroid| Direction::between(station, asteroid))
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// The asteroid that can see the most other asteroids, along with that
+    /// count.
+    pub fn best_station(&self) -> Option<(Point, usize)> {
+        self.asteroids
+            .iter()
+            .map(|&asteroid| (asteroid, self.visible_from(asteroid)))
+            .max_by_key(|&(_, visible)| visible)
+    }
+
+    /// The order in which a laser at `station`, starting pointed up and
+    /// rotating clockwise, destroys all other asteroids.
+    pub fn vaporization_order(&self, station: Point) -> Vaporization {
+        let mut rays: BTreeMap<Direction, Vec<Point>> = BTreeMap::new();
+        for &asteroid in &self.asteroids {
+            if let Some(direction) = Direction::between(station, asteroid) {
+                rays.entry(direction).or_default().push(asteroid);
+            }
+        }
+        let distance = |p: &Point| (p.x - station.x).abs() + (p.y - station.y).abs();
+        let rays = rays
+            .into_values()
+            .map(|mut ray| {
+                // farthest first so that the nearest can be popped off the end
+                ray.sort_by_key(|p| std::cmp::Reverse(distance(p)));
+                ray
+            })
+            .collect();
+        Vaporization { rays }
+    }
+}
+
+impl FromStr for AsteroidField {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut asteroids = Vec::new();
+        for (row, line) in Span::new(s).lines().map(|line| line.trim()).enumerate() {
+            for (col, ch) in line.as_str().chars().enumerate() {
+                match ch {
+                    '#' | 'X' => asteroids.push(Point::new(col as i64, row as i64)),
+                    '.' => (),
+                    found => {
+                        return Err(ParseError {
+                            line: line.line(),
+                            column: line.column() + col,
+                            kind: ErrorKind::UnexpectedChar(found),
+                        })
+                    }
+                }
+            }
+        }
+        Ok(AsteroidField::new(asteroids))
+    }
+}
+
+/// Iterator over asteroids in the order they are vaporized, see
+/// [`AsteroidField::vaporization_order`].
+#[derive(Debug, Clone)]
+pub struct Vaporization {
+    // remaining rays in the order the laser will reach them
+    rays: VecDeque<Vec<Point>>,
+}
+
+impl Iterator for Vaporization {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut ray = self.rays.pop_front()?;
+        let asteroid = ray.pop();
+        if !ray.is_empty() {
+            self.rays.push_back(ray);
+        }
+        asteroid
+    }
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AsteroidField, Direction, Point};
+
+    fn test_input_1() -> String {
+        ".#..#
+.....
+#####
+....#
+...##
+"
+        .to_string()
+    }
+
+    fn test_input_2() -> String {
+        ".#..##.###...#######
+##.############..##.
+.#.######.########.#
+.###.#######.####.#.
+#####.##.#.##.###.##
+..#####..#.#########
+####################
+#.####....###.#.#.##
+##.#################
+#####.##.###..####..
+..######..##.#######
+####.##.####...##..#
+.#####..#.######.###
+##...#.##########...
+#.##########.#######
+.####.#.###.###.#.##
+....##.##.###..#####
+.#.#.###########.###
+#.#.#.#####.####.###
+###.##.####.##.#..##
+"
+        .to_string()
+    }
+
+    #[test]
+    fn direction_order_test() {
+        let origin = Point::new(0, 0);
+        let clockwise = [
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ];
+        let directions: Vec<Direction> = clockwise
+            .into_iter()
+            .map(|(x, y)| Direction::between(origin, Point::new(x, y)).unwrap())
+            .collect();
+        let mut sorted = directions.clone();
+        sorted.reverse();
+        sorted.sort();
+        assert_eq!(sorted, directions);
+    }
+
+    #[test]
+    fn part1_test_1() {
+        let field: AsteroidField = test_input_1().parse().unwrap();
+        assert_eq!(field.best_station(), Some((Point::new(3, 4), 8)));
+    }
+
+    #[test]
+    fn part1_test_2() {
+        let field: AsteroidField = test_input_2().parse().unwrap();
+        assert_eq!(field.best_station(), Some((Point::new(11, 13), 210)));
+    }
+
+    #[test]
+    fn part2_test_2() {
+        let field: AsteroidField = test_input_2().parse().unwrap();
+        let order: Vec<Point> = field.vaporization_order(Point::new(11, 13)).collect();
+        assert_eq!(order.len(), 299);
+        assert_eq!(order[0], Point::new(11, 12));
+        assert_eq!(order[1], Point::new(12, 1));
+        assert_eq!(order[2], Point::new(12, 2));
+        assert_eq!(order[9], Point::new(12, 8));
+        assert_eq!(order[19], Point::new(16, 0));
+        assert_eq!(order[49], Point::new(16, 9));
+        assert_eq!(order[99], Point::new(10, 16));
+        assert_eq!(order[198], Point::new(9, 6));
+        assert_eq!(order[199], Point::new(8, 2));
+        assert_eq!(order[200], Point::new(10, 9));
+        assert_eq!(order[298], Point::new(11, 1));
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::fs;
 
-use anyhow::Result;
+use aoc::{Error, Result};
 
 use adventofcode2019::intcode;
 use intcode::Computer;
@@ -11,19 +11,13 @@ fn main() -> Result<()> {
     part2()
 }
 
-fn load_data(fname: &str) -> HashMap<usize, i64> {
-    let program_text = fs::read_to_string(fname).expect("Couldn't read the file!");
-    let program_text = program_text.trim();
-    let program: HashMap<usize, i64> = program_text
-        .split(',')
-        .enumerate()
-        .map(|(idx, x)| (idx, x.parse::<i64>().expect("Couldn't parse instruction!")))
-        .collect();
-    program
+fn load_data(fname: &str) -> Result<HashMap<usize, i64>> {
+    let program_text = fs::read_to_string(fname)?;
+    Ok(intcode::parse_program(&program_text)?)
 }
 
 fn part1() -> Result<()> {
-    let mut program: HashMap<usize, i64> = load_data("data/Day02_input.txt");
+    let mut program: HashMap<usize, i64> = load_data("data/Day02_input.txt")?;
     program.insert(1, 12);
     program.insert(2, 2);
 
@@ -37,9 +31,8 @@ fn part1() -> Result<()> {
 }
 
 fn part2() -> Result<()> {
-    let program: HashMap<usize, i64> = load_data("data/Day02_input.txt");
+    let program: HashMap<usize, i64> = load_data("data/Day02_input.txt")?;
     let mut my_computer = Computer::new();
-    let mut found: bool = false;
     let looking_for: i64 = 19690720;
 
     for noun in 0..99 {
@@ -52,13 +45,11 @@ fn part2() -> Result<()> {
             my_computer.run()?;
             if my_computer.read_memory(&0) == looking_for {
                 println!("{}{}", noun, verb);
-                found = true;
-                break;
+                return Ok(());
             }
         }
-        if found {
-            break;
-        }
     }
-    Ok(())
+    Err(Error::no_solution(format!(
+        "no noun and verb produce {looking_for}"
+    )))
 }
@@ -1,66 +1,55 @@
-extern crate regex;
-use regex::Regex;
 use std::fs;
 
-fn main() {
-    part1();
-    part2();
-}
+use aoc::{Error, Result};
+use geometry::{Direction, Vector2};
+use parse::{ErrorKind, ParseError, Span};
 
-#[derive(Copy, Clone)]
-struct Point {
-    x: i32,
-    y: i32,
+fn main() -> Result<()> {
+    part1()?;
+    part2()
 }
 
-impl Point {
-    fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
+/// The corners of a wire, starting at the origin.
+type Wire = Vec<Vector2<i32>>;
 
-    fn manhattan_distance(&self, p: &Point) -> i32 {
-        (self.x - p.x).abs() + (self.y - p.y).abs()
+/// A wire given as moves like `R75,D30`.
+fn parse_wire(moves: Span) -> Result<Wire, ParseError> {
+    let mut wire = vec![Vector2::new(0, 0)];
+    for step in moves.split(",") {
+        let (direction, distance) = step
+            .split_first()
+            .and_then(|(direction, distance)| Some((Direction::from_char(direction)?, distance)))
+            .ok_or_else(|| step.expected("a move like R75"))?;
+        let distance: i32 = distance.parse()?;
+        let last_point = wire[wire.len() - 1];
+        wire.push(last_point + direction.to_vector() * distance);
     }
+    Ok(wire)
 }
 
-fn load_data(fname: &str) -> (Vec<Point>, Vec<Point>) {
-    let moves = fs::read_to_string(fname).expect("Couldn't read the file!");
-    let mut moves = moves.trim().split_whitespace();
-    let wire1 = moves.next().unwrap();
-    let wire2 = moves.next().unwrap();
-
-    let move_pattern = Regex::new(r"([A-Z])([0-9]+)").unwrap();
-
-    let mut wire1_vec: Vec<Point> = vec![Point::new(0, 0)];
-    for cap in move_pattern.captures_iter(wire1) {
-        let last_point: Point = *wire1_vec.last().unwrap();
-        let m: i32 = cap[2].parse::<i32>().unwrap();
-        match &cap[1] {
-            "U" => wire1_vec.push(Point::new(last_point.x, last_point.y + m)),
-            "D" => wire1_vec.push(Point::new(last_point.x, last_point.y - m)),
-            "L" => wire1_vec.push(Point::new(last_point.x - m, last_point.y)),
-            "R" => wire1_vec.push(Point::new(last_point.x + m, last_point.y)),
-            _ => println!("You probably read the file incorrectly!"),
-        }
-    }
-
-    let mut wire2_vec: Vec<Point> = vec![Point::new(0, 0)];
-    for cap in move_pattern.captures_iter(wire2) {
-        let last_point: Point = *wire2_vec.last().unwrap();
-        let m: i32 = cap[2].parse::<i32>().unwrap();
-        match &cap[1] {
-            "U" => wire2_vec.push(Point::new(last_point.x, last_point.y + m)),
-            "D" => wire2_vec.push(Point::new(last_point.x, last_point.y - m)),
-            "L" => wire2_vec.push(Point::new(last_point.x - m, last_point.y)),
-            "R" => wire2_vec.push(Point::new(last_point.x + m, last_point.y)),
-            _ => println!("You probably read the file incorrectly!"),
-        }
+fn load_data(fname: &str) -> Result<(Wire, Wire)> {
+    let moves = fs::read_to_string(fname)?;
+    let moves = Span::new(&moves);
+    let wires = moves
+        .words()
+        .map(parse_wire)
+        .collect::<Result<Vec<_>, _>>()?;
+    match <[_; 2]>::try_from(wires) {
+        Ok([wire1, wire2]) => Ok((wire1, wire2)),
+        Err(wires) => Err(moves
+            .error(ErrorKind::WrongCount {
+                expected: 2,
+                found: wires.len(),
+            })
+            .into()),
     }
-    (wire1_vec, wire2_vec)
 }
 
-fn get_crossings(wire1: &[Point], wire2: &[Point]) -> (Vec<Point>, Vec<(usize, usize)>) {
-    let mut possible_crossings: Vec<Point> = Vec::new();
+fn get_crossings(
+    wire1: &[Vector2<i32>],
+    wire2: &[Vector2<i32>],
+) -> (Vec<Vector2<i32>>, Vec<(usize, usize)>) {
+    let mut possible_crossings: Vec<Vector2<i32>> = Vec::new();
     let mut crossing_indices: Vec<(usize, usize)> = Vec::new();
     for i in 0..(wire1.len() - 1) {
         for j in 0..(wire2.len() - 1) {
@@ -73,39 +62,44 @@ fn get_crossings(wire1: &[Point], wire2: &[Point]) -> (Vec<Point>, Vec<(usize, u
             if x_diff == 0 {
                 let mut ys = [wire1[i].y, wire1[i + 1].y, wire2[j].y, wire2[j + 1].y];
                 ys.sort_unstable();
-                possible_crossings.push(Point::new(wire1[i].x, ys[1]));
-                possible_crossings.push(Point::new(wire1[i].x, ys[2]));
+                possible_crossings.push(Vector2::new(wire1[i].x, ys[1]));
+                possible_crossings.push(Vector2::new(wire1[i].x, ys[2]));
             } else if y_diff == 0 {
                 let mut xs = [wire1[i].x, wire1[i + 1].x, wire2[j].x, wire2[j + 1].x];
                 xs.sort_unstable();
-                possible_crossings.push(Point::new(xs[1], wire1[i].y));
-                possible_crossings.push(Point::new(xs[2], wire1[i].y));
+                possible_crossings.push(Vector2::new(xs[1], wire1[i].y));
+                possible_crossings.push(Vector2::new(xs[2], wire1[i].y));
             } else {
                 let mut xs = [wire1[i].x, wire1[i + 1].x, wire2[j].x, wire2[j + 1].x];
                 let mut ys = [wire1[i].y, wire1[i + 1].y, wire2[j].y, wire2[j + 1].y];
                 xs.sort_unstable();
                 ys.sort_unstable();
-                possible_crossings.push(Point::new(xs[1], ys[1]));
+                possible_crossings.push(Vector2::new(xs[1], ys[1]));
             }
         }
     }
     (possible_crossings, crossing_indices)
 }
 
-fn part1() {
-    let (wire1, wire2) = load_data("data/Day03_input.txt");
+fn part1() -> Result<()> {
+    let (wire1, wire2) = load_data("data/Day03_input.txt")?;
     let (possible_crossings, _crossing_indices) = get_crossings(&wire1, &wire2);
-    let origin: Point = Point::new(0, 0);
+    let origin: Vector2<i32> = Vector2::new(0, 0);
     let mut distances: Vec<i32> = possible_crossings
         .iter()
-        .map(|p| p.manhattan_distance(&origin))
+        .map(|p| p.manhattan_distance(origin))
         .collect();
     distances.sort_unstable();
-    println!("{}", distances.iter().find(|&&d| d != 0).unwrap());
+    let closest = distances
+        .iter()
+        .find(|&&d| d != 0)
+        .ok_or_else(|| Error::no_solution("the wires never cross"))?;
+    println!("{}", closest);
+    Ok(())
 }
 
-fn part2() {
-    let (wire1, wire2) = load_data("data/Day03_input.txt");
+fn part2() -> Result<()> {
+    let (wire1, wire2) = load_data("data/Day03_input.txt")?;
     let (_possible_crossings, crossing_indices) = get_crossings(&wire1, &wire2);
     let mut crossing_steps: Vec<i32> = Vec::new();
     for cross in crossing_indices {
@@ -114,10 +108,10 @@ fn part2() {
         }
         let mut steps: i32 = 0;
         for i in 0..cross.0 {
-            steps += wire1[i].manhattan_distance(&wire2[i + 1]);
+            steps += wire1[i].manhattan_distance(wire2[i + 1]);
         }
         for j in 0..cross.1 {
-            steps += wire2[j].manhattan_distance(&wire2[j + 1]);
+            steps += wire2[j].manhattan_distance(wire2[j + 1]);
         }
         let mut xs = [
             wire1[cross.0].x,
@@ -133,9 +127,14 @@ fn part2() {
         ];
         xs.sort_unstable();
         ys.sort_unstable();
-        steps += wire1[cross.0].manhattan_distance(&Point::new(xs[2], ys[2]));
-        steps += wire2[cross.1].manhattan_distance(&Point::new(xs[2], ys[2]));
+        steps += wire1[cross.0].manhattan_distance(Vector2::new(xs[2], ys[2]));
+        steps += wire2[cross.1].manhattan_distance(Vector2::new(xs[2], ys[2]));
         crossing_steps.push(steps);
     }
-    println!("{}", crossing_steps.iter().min().unwrap());
+    let fewest = crossing_steps
+        .iter()
+        .min()
+        .ok_or_else(|| Error::no_solution("the wires never cross"))?;
+    println!("{}", fewest);
+    Ok(())
 }
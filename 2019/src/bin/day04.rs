@@ -1,6 +1,7 @@
-fn main() {
+fn main() -> aoc::Result<()> {
     let pass_list = part1();
     part2(&pass_list);
+    Ok(())
 }
 
 fn part1() -> Vec<u32> {
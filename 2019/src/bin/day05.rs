@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::fs;
 
-use anyhow::Result;
+use aoc::Result;
 
 use adventofcode2019::intcode;
 use intcode::Computer;
@@ -11,19 +11,13 @@ fn main() -> Result<()> {
     part2()
 }
 
-fn load_data(fname: &str) -> HashMap<usize, i64> {
-    let program_text = fs::read_to_string(fname).expect("Couldn't read the file!");
-    let program_text = program_text.trim();
-    let program: HashMap<usize, i64> = program_text
-        .split(',')
-        .enumerate()
-        .map(|(idx, x)| (idx, x.parse::<i64>().expect("Couldn't parse instruction!")))
-        .collect();
-    program
+fn load_data(fname: &str) -> Result<HashMap<usize, i64>> {
+    let program_text = fs::read_to_string(fname)?;
+    Ok(intcode::parse_program(&program_text)?)
 }
 
 fn part1() -> Result<()> {
-    let program: HashMap<usize, i64> = load_data("data/Day05_input.txt");
+    let program: HashMap<usize, i64> = load_data("data/Day05_input.txt")?;
     let mut my_computer = Computer::new();
 
     my_computer.load_program(program);
@@ -33,7 +27,7 @@ fn part1() -> Result<()> {
 }
 
 fn part2() -> Result<()> {
-    let program: HashMap<usize, i64> = load_data("data/Day05_input.txt");
+    let program: HashMap<usize, i64> = load_data("data/Day05_input.txt")?;
     let mut my_computer = Computer::new();
 
     my_computer.load_program(program);
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, io};
 
-use anyhow::Result;
+use parse::{ParseError, Span};
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -259,9 +259,10 @@ impl Computer {
         self.ram.read_at(at)
     }
 
-    pub fn run(&mut self) -> Result<()> {
+    pub fn run(&mut self) -> aoc::Result<()> {
         while self.state != State::Halt {
-            let instruction = Instruction::parse(&self.ram, self.instruction_pointer)?;
+            let instruction = Instruction::parse(&self.ram, self.instruction_pointer)
+                .map_err(aoc::Error::invalid_state)?;
             // println!("{instruction:?}");
             let new_state = instruction.execute(&mut self.ram);
             match new_state {
@@ -285,6 +286,12 @@ impl Computer {
     }
 }
 
+/// Parse a comma separated Intcode program into memory, addressed from 0.
+pub fn parse_program(text: &str) -> Result<HashMap<usize, i64>, ParseError> {
+    let program: Vec<i64> = Span::new(text).trim().parse_list(",")?;
+    Ok(program.into_iter().enumerate().collect())
+}
+
 impl Default for Computer {
     fn default() -> Self {
         Self::new()
@@ -1 +1,6 @@
+pub mod asteroids;
 pub mod intcode;
+pub mod nanofactory;
+pub mod nbody;
+pub mod orbits;
+pub mod space_image;
@@ -0,0 +1,334 @@
+use std::{collections::HashMap, str::FromStr};
+
+use parse::{ParseError, Span};
+use thiserror::Error;
+
+pub const ORE: &str = "ORE";
+pub const FUEL: &str = "FUEL";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum NanofactoryError {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error("{0} is produced by more than one reaction")]
+    DuplicateReaction(String),
+    #[error("{0} is used but no reaction produces it")]
+    MissingReaction(String),
+    #[error("{0} must not be produced by any reaction")]
+    ProducedOre(String),
+    #[error("reactions form a cycle, unresolved chemicals: {0:?}")]
+    Cycle(Vec<String>),
+    #[error("no reaction produces {FUEL}")]
+    NoFuel,
+    #[error("no reaction uses {ORE}")]
+    NoOre,
+    #[error("a reaction producing {0} has an amount of zero")]
+    ZeroAmount(String),
+}
+
+impl From<NanofactoryError> for aoc::Error {
+    fn from(err: NanofactoryError) -> Self {
+        match err {
+            NanofactoryError::Parse(err) => err.into(),
+            err => aoc::Error::invalid_state(err),
+        }
+    }
+}
+
+/// An amount of a chemical, the chemical being an index into the names of a
+/// [`Nanofactory`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Quantity {
+    pub chemical: usize,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Reaction {
+    pub output: Quantity,
+    pub inputs: Vec<Quantity>,
+}
+
+/// A validated set of reactions, each chemical other than ORE being produced
+/// by exactly one reaction and no chemical being needed to produce itself.
+/// FUEL is produced, ORE is used and every amount is positive, so producing
+/// more FUEL always takes more ORE in the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nanofactory {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    producers: Vec<Option<Reaction>>,
+    // every chemical appears before all chemicals it is made from
+    order: Vec<usize>,
+}
+
+impl Nanofactory {
+    pub fn chemical(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, chemical: usize) -> &str {
+        &self.names[chemical]
+    }
+
+    /// The reaction producing `chemical`, `None` only for ORE.
+    pub fn reaction(&self, chemical: usize) -> Option<&Reaction> {
+        self.producers[chemical].as_ref()
+    }
+
+    /// Chemicals in topological order, products before their ingredients.
+    pub fn topological_order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Total ORE needed to produce `amount` of `chemical`, `None` if that or
+    /// any amount on the way does not fit in a `u64`.
+    ///
+    /// Chemicals are resolved in topological order, so by the time a chemical
+    /// is reached its total demand is known and it is produced in one go.
+    pub fn ore_for(&self, chemical: usize, amount: u64) -> Option<u64> {
+        let mut needed = vec![0u64; self.names.len()];
+        needed[chemical] = amount;
+        for &chemical in &self.order {
+            if let Some(reaction) = &self.producers[chemical] {
+                let batches = needed[chemical].div_ceil(reaction.output.amount);
+                for input in &reaction.inputs {
+                    needed[input.chemical] = batches
+                        .checked_mul(input.amount)
+                        .and_then(|amount| needed[input.chemical].checked_add(amount))?;
+                }
+            }
+        }
+        Some(needed[self.ids[ORE]])
+    }
+
+    pub fn ore_for_fuel(&self, fuel: u64) -> Option<u64> {
+        self.ore_for(self.ids[FUEL], fuel)
+    }
+
+    /// Largest amount of FUEL that can be produced with `ore` ORE.
+    pub fn max_fuel(&self, ore: u64) -> u64 {
+        let affordable = |fuel| matches!(self.ore_for_fuel(fuel), Some(needed) if needed <= ore);
+        if !affordable(1) {
+            return 0;
+        }
+        let mut low = 1;
+        let mut high = 2;
+        while affordable(high) {
+            if high == u64::MAX {
+                return high;
+            }
+            low = high;
+            high = high.saturating_mul(2);
+        }
+        // invariant: ore_for_fuel(low) <= ore < ore_for_fuel(high)
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if affordable(mid) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+}
+
+fn parse_quantity(
+    text: Span,
+    ids: &mut HashMap<String, usize>,
+    names: &mut Vec<String>,
+) -> Result<Quantity, ParseError> {
+    let (amount, name) = text.trim().split_once(" ")?;
+    let amount = amount.parse()?;
+    let name = name.trim().as_str();
+    let chemical = *ids.entry(name.to_string()).or_insert_with(|| {
+        names.push(name.to_string());
+        names.len() - 1
+    });
+    Ok(Quantity { chemical, amount })
+}
+
+impl FromStr for Nanofactory {
+    type Err = NanofactoryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ids = HashMap::new();
+        let mut names = Vec::new();
+        let mut reactions = Vec::new();
+        for line in Span::new(s).lines().filter(|line| !line.trim().is_empty()) {
+            let (inputs, output) = line.split_once("=>")?;
+            let output = parse_quantity(output, &mut ids, &mut names)?;
+            let inputs = inputs
+                .split(",")
+                .map(|input| parse_quantity(input, &mut ids, &mut names))
+                .collect::<Result<Vec<_>, _>>()?;
+            reactions.push(Reaction { output, inputs });
+        }
+
+        if !ids.contains_key(ORE) {
+            return Err(NanofactoryError::NoOre);
+        }
+        let mut producers: Vec<Option<Reaction>> = vec![None; names.len()];
+        for reaction in reactions {
+            let mut quantities = std::iter::once(&reaction.output).chain(&reaction.inputs);
+            if quantities.any(|quantity| quantity.amount == 0) {
+                return Err(NanofactoryError::ZeroAmount(
+                    names[reaction.output.chemical].clone(),
+                ));
+            }
+            let produced = reaction.output.chemical;
+            if names[produced] == ORE {
+                return Err(NanofactoryError::ProducedOre(ORE.to_string()));
+            }
+            if producers[produced].replace(reaction).is_some() {
+                return Err(NanofactoryError::DuplicateReaction(names[produced].clone()));
+            }
+        }
+        if ids.get(FUEL).is_none_or(|&fuel| producers[fuel].is_none()) {
+            return Err(NanofactoryError::NoFuel);
+        }
+        if let Some(missing) = (0..names.len())
+            .find(|&chemical| producers[chemical].is_none() && names[chemical] != ORE)
+        {
+            return Err(NanofactoryError::MissingReaction(names[missing].clone()));
+        }
+
+        // Kahn's algorithm over product -> ingredient edges
+        let mut consumers = vec![0usize; names.len()];
+        for reaction in producers.iter().flatten() {
+            for input in &reaction.inputs {
+                consumers[input.chemical] += 1;
+            }
+        }
+        let mut ready: Vec<usize> = (0..names.len())
+            .filter(|&chemical| consumers[chemical] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(names.len());
+        while let Some(chemical) = ready.pop() {
+            order.push(chemical);
+            for input in producers[chemical].iter().flat_map(|r| &r.inputs) {
+                consumers[input.chemical] -= 1;
+                if consumers[input.chemical] == 0 {
+                    ready.push(input.chemical);
+                }
+            }
+        }
+        if order.len() < names.len() {
+            let cycle = (0..names.len())
+                .filter(|&chemical| consumers[chemical] > 0 && producers[chemical].is_some())
+                .map(|chemical| names[chemical].clone())
+                .collect();
+            return Err(NanofactoryError::Cycle(cycle));
+        }
+
+        Ok(Nanofactory {
+            names,
+            ids,
+            producers,
+            order,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parse::ParseError;
+
+    use super::{Nanofactory, NanofactoryError};
+
+    fn test_input_1() -> String {
+        "10 ORE => 10 A
+1 ORE => 1 B
+7 A, 1 B => 1 C
+7 A, 1 C => 1 D
+7 A, 1 D => 1 E
+7 A, 1 E => 1 FUEL
+"
+        .to_string()
+    }
+
+    fn test_input_2() -> String {
+        "157 ORE => 5 NZVS
+165 ORE => 6 DCFZ
+44 XJWVT, 5 KHKGT, 1 QDVJ, 29 NZVS, 9 GPVTF, 48 HKGWZ => 1 FUEL
+12 HKGWZ, 1 GPVTF, 8 PSHF => 9 QDVJ
+179 ORE => 7 PSHF
+177 ORE => 5 HKGWZ
+7 DCFZ, 7 PSHF => 2 XJWVT
+165 ORE => 2 GPVTF
+3 DCFZ, 7 NZVS, 5 HKGWZ, 10 PSHF => 8 KHKGT
+"
+        .to_string()
+    }
+
+    #[test]
+    fn part1_test_1() {
+        let factory: Nanofactory = test_input_1().parse().unwrap();
+        assert_eq!(factory.ore_for_fuel(1), Some(31));
+    }
+
+    #[test]
+    fn part1_test_2() {
+        let factory: Nanofactory = test_input_2().parse().unwrap();
+        assert_eq!(factory.ore_for_fuel(1), Some(13312));
+    }
+
+    #[test]
+    fn part2_test_2() {
+        let factory: Nanofactory = test_input_2().parse().unwrap();
+        assert_eq!(factory.max_fuel(1_000_000_000_000), 82_892_753);
+    }
+
+    #[test]
+    fn max_fuel_limits_test() {
+        let factory: Nanofactory = "1 ORE => 1000 A\n1 A => 1 FUEL".parse().unwrap();
+        assert_eq!(factory.max_fuel(0), 0);
+        assert_eq!(factory.max_fuel(u64::MAX), u64::MAX);
+        let factory: Nanofactory = "1000 ORE => 1 FUEL".parse().unwrap();
+        assert_eq!(factory.ore_for_fuel(u64::MAX), None);
+        assert_eq!(factory.max_fuel(u64::MAX), u64::MAX / 1000);
+    }
+
+    #[test]
+    fn validation_test() {
+        assert_eq!(
+            "1 ORE => 1 A\n1 A => 1 B".parse::<Nanofactory>(),
+            Err(NanofactoryError::NoFuel)
+        );
+        assert_eq!(
+            "1 ORE => 1 A\n1 A => 1 B\n1 B => 2 ORE".parse::<Nanofactory>(),
+            Err(NanofactoryError::ProducedOre("ORE".to_string()))
+        );
+        assert_eq!(
+            "1 A => 1 FUEL\n1 FUEL => 1 A".parse::<Nanofactory>(),
+            Err(NanofactoryError::NoOre)
+        );
+        assert_eq!(
+            "0 ORE => 1 FUEL".parse::<Nanofactory>(),
+            Err(NanofactoryError::ZeroAmount("FUEL".to_string()))
+        );
+        assert_eq!(
+            "1 ORE => 1 A\n1 B => 1 FUEL".parse::<Nanofactory>(),
+            Err(NanofactoryError::MissingReaction("B".to_string()))
+        );
+        assert_eq!(
+            "1 ORE, 1 B => 1 A\n1 A => 1 B\n1 A => 1 FUEL".parse::<Nanofactory>(),
+            Err(NanofactoryError::Cycle(vec![
+                "A".to_string(),
+                "B".to_string()
+            ]))
+        );
+        let error = "1 ORE => 1 A\n1 A, x B => 1 FUEL"
+            .parse::<Nanofactory>()
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            NanofactoryError::Parse(ParseError {
+                line: 2,
+                column: 6,
+                ..
+            })
+        ));
+    }
+}
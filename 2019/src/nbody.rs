@@ -0,0 +1,237 @@
+use std::str::FromStr;
+
+use geometry::Vector3;
+use parse::{ErrorKind, ParseError, Span};
+
+/// Number of axes the bodies move along.
+const AXES: usize = 3;
+
+/// Parses the puzzle's `<x=-1, y=0, z=2>` notation. The component names are
+/// ignored, only their count has to be three.
+fn parse_vector(text: Span) -> Result<Vector3<i64>, ParseError> {
+    let inner = text.trim().strip_prefix("<")?.strip_suffix(">")?;
+    let coordinates = inner
+        .split(",")
+        .map(|component| {
+            let (_, value) = component.split_once("=")?;
+            value.trim().parse()
+        })
+        .collect::<Result<Vec<i64>, _>>()?;
+    let found = coordinates.len();
+    let coordinates: [i64; AXES] = coordinates.try_into().map_err(|_| {
+        inner.error(ErrorKind::WrongCount {
+            expected: AXES,
+            found,
+        })
+    })?;
+    Ok(Vector3::from(coordinates))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Body {
+    pub position: Vector3<i64>,
+    pub velocity: Vector3<i64>,
+}
+
+impl Body {
+    /// A body at rest at `position`.
+    pub fn new(position: Vector3<i64>) -> Self {
+        Body {
+            position,
+            velocity: Vector3::ZERO,
+        }
+    }
+
+    pub fn potential_energy(&self) -> i64 {
+        self.position.manhattan_norm()
+    }
+
+    pub fn kinetic_energy(&self) -> i64 {
+        self.velocity.manhattan_norm()
+    }
+
+    pub fn total_energy(&self) -> i64 {
+        self.potential_energy() * self.kinetic_energy()
+    }
+}
+
+/// A set of bodies pulling on each other one unit per axis per step.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct System {
+    bodies: Vec<Body>,
+}
+
+impl System {
+    pub fn new(bodies: Vec<Body>) -> Self {
+        System { bodies }
+    }
+
+    pub fn bodies(&self) -> &[Body] {
+        &self.bodies
+    }
+
+    /// Advance the simulation by one time step: apply gravity between every
+    /// pair of bodies, then move every body by its velocity.
+    pub fn step(&mut self) {
+        for axis in 0..AXES {
+            step_axis(&mut self.bodies, axis);
+        }
+    }
+
+    pub fn simulate(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+
+    pub fn total_energy(&self) -> i64 {
+        self.bodies.iter().map(Body::total_energy).sum()
+    }
+
+    /// Number of steps after which the positions and velocities along `axis`
+    /// return to their current values.
+    ///
+    /// Every step is invertible, so the first repeated state of an axis is
+    /// always its starting state.
+    pub fn axis_period(&self, axis: usize) -> u64 {
+        assert!(
+            axis < AXES,
+            "axis {axis} out of range for {AXES} dimensions"
+        );
+        let initial = self.bodies.clone();
+        let mut bodies = self.bodies.clone();
+        let mut steps = 0;
+        loop {
+            step_axis(&mut bodies, axis);
+            steps += 1;
+            if bodies.iter().zip(initial.iter()).all(|(body, init)| {
+                body.position[axis] == init.position[axis]
+                    && body.velocity[axis] == init.velocity[axis]
+            }) {
+                return steps;
+            }
+        }
+    }
+
+    /// Number of steps until the whole system repeats a previous state.
+    ///
+    /// The axes evolve independently, so this is the least common multiple of
+    /// the per-axis periods.
+    pub fn period(&self) -> u64 {
+        (0..AXES).map(|axis| self.axis_period(axis)).fold(1, lcm)
+    }
+}
+
+impl FromStr for System {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bodies = Span::new(s)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| parse_vector(line).map(Body::new))
+            .collect::<Result<Vec<Body>, _>>()?;
+        Ok(System::new(bodies))
+    }
+}
+
+fn step_axis(bodies: &mut [Body], axis: usize) {
+    for i in 0..bodies.len() {
+        for j in (i + 1)..bodies.len() {
+            let pull = (bodies[j].position[axis] - bodies[i].position[axis]).signum();
+            bodies[i].velocity[axis] += pull;
+            bodies[j].velocity[axis] -= pull;
+        }
+    }
+    for body in bodies.iter_mut() {
+        body.position[axis] += body.velocity[axis];
+    }
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn lcm(a: u64, b: u64) -> u64 {
+    a / gcd(a, b) * b
+}
+
+#[cfg(test)]
+mod tests {
+    use geometry::Vector3;
+    use parse::{ErrorKind, ParseError, Span};
+
+    use super::{parse_vector, Body, System};
+
+    fn test_input_1() -> String {
+        "<x=-1, y=0, z=2>
+<x=2, y=-10, z=-7>
+<x=4, y=-8, z=8>
+<x=3, y=5, z=-1>
+"
+        .to_string()
+    }
+
+    fn test_input_2() -> String {
+        "<x=-8, y=-10, z=0>
+<x=5, y=5, z=10>
+<x=2, y=-7, z=3>
+<x=9, y=-8, z=-3>
+"
+        .to_string()
+    }
+
+    #[test]
+    fn step_test() {
+        let mut system: System = test_input_1().parse().unwrap();
+        system.step();
+        assert_eq!(
+            system.bodies()[0],
+            Body {
+                position: Vector3::new(2, -1, 1),
+                velocity: Vector3::new(3, -1, -1),
+            }
+        );
+    }
+
+    #[test]
+    fn energy_test() {
+        let mut system: System = test_input_1().parse().unwrap();
+        system.simulate(10);
+        assert_eq!(system.total_energy(), 179);
+        let mut system: System = test_input_2().parse().unwrap();
+        system.simulate(100);
+        assert_eq!(system.total_energy(), 1940);
+    }
+
+    #[test]
+    fn period_test() {
+        let system: System = test_input_1().parse().unwrap();
+        assert_eq!(system.period(), 2772);
+        let system: System = test_input_2().parse().unwrap();
+        assert_eq!(system.period(), 4_686_774_924);
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse_vector(Span::new("<x=1, y=2>")),
+            Err(ParseError {
+                line: 1,
+                column: 2,
+                kind: ErrorKind::WrongCount {
+                    expected: 3,
+                    found: 2
+                }
+            })
+        );
+        assert!(parse_vector(Span::new("x=1, y=2, z=3")).is_err());
+        let error = "<x=1, y=2, z=3>\n<x=4, y=five, z=6>"
+            .parse::<System>()
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 9));
+    }
+}
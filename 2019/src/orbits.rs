@@ -0,0 +1,245 @@
+use std::{collections::HashMap, str::FromStr};
+
+use parse::{ParseError, Span};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum OrbitMapError {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error("{body} orbits both {first} and {second}")]
+    MultipleParents {
+        body: String,
+        first: String,
+        second: String,
+    },
+    #[error("orbits form a cycle through {0}")]
+    Cycle(String),
+}
+
+impl From<OrbitMapError> for aoc::Error {
+    fn from(err: OrbitMapError) -> Self {
+        match err {
+            OrbitMapError::Parse(err) => err.into(),
+            err => aoc::Error::invalid_state(err),
+        }
+    }
+}
+
+/// A forest of bodies, every body orbiting at most one other body.
+///
+/// Bodies are interned into dense ids in order of first appearance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrbitMap {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    parents: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    depths: Vec<usize>,
+}
+
+impl OrbitMap {
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, body: usize) -> &str {
+        &self.names[body]
+    }
+
+    /// The body that `body` directly orbits.
+    pub fn parent(&self, body: usize) -> Option<usize> {
+        self.parents[body]
+    }
+
+    /// Bodies directly orbiting `body`.
+    pub fn children(&self, body: usize) -> &[usize] {
+        &self.children[body]
+    }
+
+    /// Number of direct and indirect orbits of `body`, i.e. its distance from
+    /// the root of its tree.
+    pub fn depth(&self, body: usize) -> usize {
+        self.depths[body]
+    }
+
+    /// Total number of direct and indirect orbits in the map.
+    pub fn total_orbits(&self) -> usize {
+        self.depths.iter().sum()
+    }
+
+    /// Iterate over the bodies `body` orbits, nearest first.
+    pub fn ancestors(&self, body: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.parents[body], |&body| self.parents[body])
+    }
+
+    /// The deepest body that both `a` and `b` are in the subtree of, `None` if
+    /// they belong to different trees.
+    ///
+    /// A body counts as being in its own subtree.
+    pub fn lowest_common_ancestor(&self, mut a: usize, mut b: usize) -> Option<usize> {
+        while self.depths[a] > self.depths[b] {
+            a = self.parents[a]?;
+        }
+        while self.depths[b] > self.depths[a] {
+            b = self.parents[b]?;
+        }
+        while a != b {
+            a = self.parents[a]?;
+            b = self.parents[b]?;
+        }
+        Some(a)
+    }
+
+    /// Number of orbit edges on the path between `a` and `b`.
+    pub fn distance(&self, a: usize, b: usize) -> Option<usize> {
+        let lca = self.lowest_common_ancestor(a, b)?;
+        Some(self.depths[a] + self.depths[b] - 2 * self.depths[lca])
+    }
+
+    /// Number of orbital transfers needed to move from the body `a` orbits to
+    /// the body `b` orbits.
+    pub fn transfers(&self, a: usize, b: usize) -> Option<usize> {
+        self.distance(self.parents[a]?, self.parents[b]?)
+    }
+}
+
+impl FromStr for OrbitMap {
+    type Err = OrbitMapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut names: Vec<String> = Vec::new();
+        let mut ids = HashMap::new();
+        let mut intern = |name: &str| -> usize {
+            *ids.entry(name.to_string()).or_insert_with(|| {
+                names.push(name.to_string());
+                names.len() - 1
+            })
+        };
+        let mut orbits = Vec::new();
+        for line in Span::new(s)
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+        {
+            let (center, body) = line.split_once(")")?;
+            if center.is_empty() || body.is_empty() {
+                return Err(line.expected("an orbit like A)B").into());
+            }
+            orbits.push((intern(center.as_str()), intern(body.as_str())));
+        }
+
+        let mut parents = vec![None; names.len()];
+        let mut children = vec![Vec::new(); names.len()];
+        for (center, body) in orbits {
+            if let Some(first) = parents[body].replace(center) {
+                return Err(OrbitMapError::MultipleParents {
+                    body: names[body].clone(),
+                    first: names[first].clone(),
+                    second: names[center].clone(),
+                });
+            }
+            children[center].push(body);
+        }
+
+        // every body is reached exactly once from the roots unless it sits on
+        // a cycle
+        let roots = (0..names.len()).filter(|&body| parents[body].is_none());
+        let tree = search::bfs_all(roots, |&body| children[body].iter().copied());
+        let depths = names
+            .iter()
+            .enumerate()
+            .map(|(body, name)| {
+                tree.cost(&body)
+                    .ok_or_else(|| OrbitMapError::Cycle(name.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(OrbitMap {
+            names,
+            ids,
+            parents,
+            children,
+            depths,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OrbitMap, OrbitMapError};
+
+    fn test_input_1() -> String {
+        "COM)B
+B)C
+C)D
+D)E
+E)F
+B)G
+G)H
+D)I
+E)J
+J)K
+K)L
+"
+        .to_string()
+    }
+
+    fn test_input_2() -> String {
+        test_input_1() + "K)YOU\nI)SAN\n"
+    }
+
+    #[test]
+    fn part1_test_1() {
+        let map: OrbitMap = test_input_1().parse().unwrap();
+        assert_eq!(map.total_orbits(), 42);
+        assert_eq!(map.depth(map.id("D").unwrap()), 3);
+        assert_eq!(map.depth(map.id("COM").unwrap()), 0);
+    }
+
+    #[test]
+    fn lowest_common_ancestor_test() {
+        let map: OrbitMap = test_input_1().parse().unwrap();
+        let id = |name| map.id(name).unwrap();
+        assert_eq!(map.lowest_common_ancestor(id("L"), id("I")), Some(id("D")));
+        assert_eq!(map.lowest_common_ancestor(id("H"), id("F")), Some(id("B")));
+        assert_eq!(map.lowest_common_ancestor(id("E"), id("L")), Some(id("E")));
+    }
+
+    #[test]
+    fn part2_test_1() {
+        let map: OrbitMap = test_input_2().parse().unwrap();
+        let you = map.id("YOU").unwrap();
+        let san = map.id("SAN").unwrap();
+        assert_eq!(map.transfers(you, san), Some(4));
+    }
+
+    #[test]
+    fn invalid_map_test() {
+        assert_eq!(
+            "A)B\nC)B".parse::<OrbitMap>(),
+            Err(OrbitMapError::MultipleParents {
+                body: "B".to_string(),
+                first: "A".to_string(),
+                second: "C".to_string()
+            })
+        );
+        assert_eq!(
+            "A)B\nB)A".parse::<OrbitMap>(),
+            Err(OrbitMapError::Cycle("A".to_string()))
+        );
+        let error = "COM)B\nB-C".parse::<OrbitMap>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected \")\", found \"B-C\""
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use std::fmt::{self, Display, Write};
+
+use parse::{ErrorKind, ParseError, Span};
+use thiserror::Error;
+
+const BLACK: u8 = 0;
+const WHITE: u8 = 1;
+const TRANSPARENT: u8 = 2;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SpaceImageError {
+    #[error("image dimensions must be non-zero, got {width}x{height}")]
+    InvalidDimensions { width: usize, height: usize },
+    #[error("input length {length} is not a multiple of the layer size {layer_size}")]
+    IncompleteLayer { length: usize, layer_size: usize },
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+impl From<SpaceImageError> for aoc::Error {
+    fn from(err: SpaceImageError) -> Self {
+        match err {
+            SpaceImageError::Parse(err) => err.into(),
+            err => aoc::Error::invalid_state(err),
+        }
+    }
+}
+
+/// An image in the Space Image Format: a stack of `width` x `height` layers of
+/// digits, the first layer being in front.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpaceImage {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl SpaceImage {
+    pub fn new(width: usize, height: usize, data: &str) -> Result<Self, SpaceImageError> {
+        if width == 0 || height == 0 {
+            return Err(SpaceImageError::InvalidDimensions { width, height });
+        }
+        let data = Span::new(data).trim();
+        let pixels = (data.column()..)
+            .zip(data.as_str().chars())
+            .map(|(column, digit)| match digit.to_digit(10) {
+                Some(value) => Ok(value as u8),
+                None => Err(ParseError {
+                    line: data.line(),
+                    column,
+                    kind: ErrorKind::UnexpectedChar(digit),
+                }),
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+        let layer_size = width * height;
+        if pixels.is_empty() || pixels.len() % layer_size != 0 {
+            return Err(SpaceImageError::IncompleteLayer {
+                length: pixels.len(),
+                layer_size,
+            });
+        }
+        Ok(SpaceImage {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn layer_count(&self) -> usize {
+        self.pixels.len() / (self.width * self.height)
+    }
+
+    /// Iterate over the layers from front to back.
+    pub fn layers(&self) -> impl Iterator<Item = Layer<'_>> {
+        self.pixels
+            .chunks_exact(self.width * self.height)
+            .map(|pixels| Layer { pixels })
+    }
+
+    /// The puzzle's corruption check: number of 1 digits multiplied by the
+    /// number of 2 digits on the layer containing the fewest 0 digits.
+    pub fn checksum(&self) -> usize {
+        let layer = self
+            .layers()
+            .min_by_key(|layer| layer.count(0))
+            .expect("an image has at least one layer by construction");
+        layer.count(1) * layer.count(2)
+    }
+
+    /// Stack the layers, each pixel taking the colour of the front-most
+    /// non-transparent layer.
+    pub fn decode(&self) -> DecodedImage {
+        let mut pixels = vec![TRANSPARENT; self.width * self.height];
+        for layer in self.layers() {
+            pixels
+                .iter_mut()
+                .zip(layer.pixels)
+                .filter(|(pixel, _)| **pixel == TRANSPARENT)
+                .for_each(|(pixel, value)| *pixel = *value);
+        }
+        DecodedImage {
+            width: self.width,
+            height: self.height,
+            pixels,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layer<'a> {
+    pixels: &'a [u8],
+}
+
+impl Layer<'_> {
+    pub fn pixels(&self) -> &[u8] {
+        self.pixels
+    }
+
+    pub fn count(&self, digit: u8) -> usize {
+        self.pixels.iter().filter(|&&pixel| pixel == digit).count()
+    }
+
+    /// Number of occurrences of every digit, indexed by the digit.
+    pub fn digit_counts(&self) -> [usize; 10] {
+        let mut counts = [0; 10];
+        for &pixel in self.pixels {
+            counts[pixel as usize] += 1;
+        }
+        counts
+    }
+}
+
+/// The result of compositing all layers of a [`SpaceImage`].
+///
+/// Pixels that are transparent on every layer stay transparent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedImage {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl DecodedImage {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[u8]> {
+        self.pixels.chunks_exact(self.width)
+    }
+
+    /// Render as a plain PGM (P2) greyscale image with black as 0, white as
+    /// 255 and transparent pixels as mid grey.
+    pub fn to_pgm(&self) -> String {
+        let mut pgm = format!("P2\n{} {}\n255\n", self.width, self.height);
+        for row in self.rows() {
+            let values: Vec<&str> = row
+                .iter()
+                .map(|&pixel| match pixel {
+                    BLACK => "0",
+                    WHITE => "255",
+                    _ => "128",
+                })
+                .collect();
+            pgm.push_str(&values.join(" "));
+            pgm.push('\n');
+        }
+        pgm
+    }
+}
+
+/// Text rendering with `#` for white, a space for black and `.` for
+/// transparent pixels.
+impl Display for DecodedImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for &pixel in row {
+                f.write_char(match pixel {
+                    BLACK => ' ',
+                    WHITE => '#',
+                    _ => '.',
+                })?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parse::{ErrorKind, ParseError};
+
+    use super::{SpaceImage, SpaceImageError};
+
+    #[test]
+    fn layers_test() {
+        let image = SpaceImage::new(3, 2, "123456789012").unwrap();
+        assert_eq!(image.layer_count(), 2);
+        let layers: Vec<_> = image
+            .layers()
+            .map(|layer| layer.pixels().to_vec())
+            .collect();
+        assert_eq!(layers, vec![vec![1, 2, 3, 4, 5, 6], vec![7, 8, 9, 0, 1, 2]]);
+        assert_eq!(image.checksum(), 1);
+    }
+
+    #[test]
+    fn decode_test() {
+        let image = SpaceImage::new(2, 2, "0222112222120000").unwrap();
+        let decoded = image.decode();
+        assert_eq!(decoded.pixels(), &[0, 1, 1, 0]);
+        assert_eq!(decoded.to_string(), " #\n# \n");
+        assert_eq!(decoded.to_pgm(), "P2\n2 2\n255\n0 255\n255 0\n");
+    }
+
+    #[test]
+    fn invalid_input_test() {
+        assert_eq!(
+            SpaceImage::new(3, 2, "1234567"),
+            Err(SpaceImageError::IncompleteLayer {
+                length: 7,
+                layer_size: 6
+            })
+        );
+        assert_eq!(
+            SpaceImage::new(3, 2, "12345x"),
+            Err(SpaceImageError::Parse(ParseError {
+                line: 1,
+                column: 6,
+                kind: ErrorKind::UnexpectedChar('x')
+            }))
+        );
+    }
+}
@@ -1,7 +1,18 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
 
-fn main() {}
+use aoc::Error;
+use parse::Span;
+
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/input01.txt")?;
+    let nums: Vec<u32> = Span::new(&input)
+        .lines()
+        .map(|line| line.trim().parse())
+        .collect::<Result<_, _>>()?;
+    println!("Part 1: {}", part1(&nums)?);
+    println!("Part 2: {}", part2(&nums)?);
+    Ok(())
+}
 
 fn pair_match(inp: &[u32], sum: u32) -> Option<(u32, u32)> {
     let nums = inp.len();
@@ -18,19 +29,10 @@ fn pair_match(inp: &[u32], sum: u32) -> Option<(u32, u32)> {
     None
 }
 
-pub fn part1() -> Option<u32> {
-    let f = File::open("res/input01.txt").expect("Couldn't read day 1 input");
-    let reader = BufReader::new(f);
-    let nums: Vec<u32> = reader
-        .lines()
-        .map(|line| {
-            line.expect("Couldn't read line from day 1 input")
-                .parse()
-                .expect("Couldn't parse day 1 input")
-        })
-        .collect();
-    let matched_pair = pair_match(&nums, 2020);
-    matched_pair.map(|(n1, n2)| n1 * n2)
+fn part1(nums: &[u32]) -> aoc::Result<u32> {
+    let (n1, n2) =
+        pair_match(nums, 2020).ok_or_else(|| Error::no_solution("no two entries sum to 2020"))?;
+    Ok(n1 * n2)
 }
 
 fn three_match(inp: &[u32], sum: u32) -> Option<(u32, u32, u32)> {
@@ -50,19 +52,10 @@ fn three_match(inp: &[u32], sum: u32) -> Option<(u32, u32, u32)> {
     None
 }
 
-pub fn part2() -> Option<u32> {
-    let f = File::open("res/input01.txt").expect("Couldn't read day 1 input");
-    let reader = BufReader::new(f);
-    let nums: Vec<u32> = reader
-        .lines()
-        .map(|line| {
-            line.expect("Couldn't read line from day 1 input")
-                .parse()
-                .expect("Couldn't parse day 1 input")
-        })
-        .collect();
-    let matched_threes = three_match(&nums, 2020);
-    matched_threes.map(|(n1, n2, n3)| n1 * n2 * n3)
+fn part2(nums: &[u32]) -> aoc::Result<u32> {
+    let (n1, n2, n3) = three_match(nums, 2020)
+        .ok_or_else(|| Error::no_solution("no three entries sum to 2020"))?;
+    Ok(n1 * n2 * n3)
 }
 
 #[test]
@@ -1,9 +1,16 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::str::FromStr;
+use std::fs;
 
-fn main() {}
+use parse::{ParseError, Span};
 
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/input02.txt")?;
+    let entries = parse_entries(&input)?;
+    println!("Part 1: {}", part1(&entries));
+    println!("Part 2: {}", part2(&entries));
+    Ok(())
+}
+
+#[derive(Debug)]
 struct Policy {
     min: usize,
     max: usize,
@@ -16,35 +23,37 @@ impl Policy {
     }
 }
 
-impl From<&str> for Policy {
-    fn from(s: &str) -> Self {
-        let letter_split: Vec<&str> = s.split(' ').collect();
-        let letter: char = char::from_str(letter_split[1]).expect("Couldn't convert str to char");
-        let bounds: Vec<usize> = letter_split[0]
-            .split('-')
-            .map(|n| usize::from_str(n).expect("Couldn't convert policy bound from str"))
-            .collect();
-        Policy::new(bounds[0], bounds[1], letter)
+impl Policy {
+    /// Parse a policy like `1-3 a`.
+    fn parse(s: Span) -> Result<Self, ParseError> {
+        let (bounds, letter) = s.split_once(" ")?;
+        let (min, max) = bounds.split_once("-")?;
+        Ok(Policy::new(min.parse()?, max.parse()?, letter.parse()?))
     }
 }
 
+/// The policy and password on each line, like `1-3 a: abcde`.
+fn parse_entries(input: &str) -> Result<Vec<(Policy, String)>, ParseError> {
+    Span::new(input)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (policy, password) = line.split_once(": ")?;
+            Ok((Policy::parse(policy)?, password.as_str().to_string()))
+        })
+        .collect()
+}
+
 fn is_valid_password_part1(policy: &Policy, password: &str) -> bool {
     let letter_count = password.chars().filter(|ch| ch == &policy.letter).count();
     policy.min <= letter_count && letter_count <= policy.max
 }
 
-pub fn part1() -> usize {
-    let f = File::open("res/input02.txt").expect("Couldn't read day 2 input");
-    let reader = BufReader::new(f);
-    let mut count: usize = 0;
-    for line in reader.lines() {
-        if let Ok(s) = line {
-            let policy_split: Vec<&str> = s.split(": ").collect();
-            let policy: Policy = policy_split[0].into();
-            count += is_valid_password_part1(&policy, policy_split[1]) as usize;
-        }
-    }
-    count
+fn part1(entries: &[(Policy, String)]) -> usize {
+    entries
+        .iter()
+        .filter(|(policy, password)| is_valid_password_part1(policy, password))
+        .count()
 }
 
 fn is_valid_password_part2(policy: &Policy, password: &str) -> bool {
@@ -60,18 +69,11 @@ fn is_valid_password_part2(policy: &Policy, password: &str) -> bool {
     }
 }
 
-pub fn part2() -> usize {
-    let f = File::open("res/input02.txt").expect("Couldn't read day 2 input");
-    let reader = BufReader::new(f);
-    let mut count: usize = 0;
-    for line in reader.lines() {
-        if let Ok(s) = line {
-            let policy_split: Vec<&str> = s.split(": ").collect();
-            let policy: Policy = policy_split[0].into();
-            count += is_valid_password_part2(&policy, policy_split[1]) as usize;
-        }
-    }
-    count
+fn part2(entries: &[(Policy, String)]) -> usize {
+    entries
+        .iter()
+        .filter(|(policy, password)| is_valid_password_part2(policy, password))
+        .count()
 }
 
 #[test]
@@ -83,6 +85,15 @@ fn password_validation_part1() {
     assert!(!is_valid_password_part1(&p, "aaaa"));
 }
 
+#[test]
+fn entry_parsing() {
+    let entries = parse_entries("1-3 a: abcde\n2-9 c: ccccccccc\n").unwrap();
+    assert_eq!(part1(&entries), 2);
+    assert_eq!(part2(&entries), 1);
+    let error = parse_entries("1-3 a: abcde\n1 b: cdefg").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 1));
+}
+
 #[test]
 fn password_validation_part2() {
     let p = Policy::new(1, 3, 'a');
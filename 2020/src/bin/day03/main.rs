@@ -1,54 +1,50 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
 
-fn main() {}
+use geometry::{Grid, Vector2};
+use parse::Span;
 
-fn load_data() -> Vec<Vec<bool>> {
-    let f = File::open("res/input03.txt").expect("Couldn't read day 3 input");
-    let reader = BufReader::new(f);
-    let grid: Vec<Vec<bool>> = reader
-        .lines()
-        .map(|line| {
-            line.expect("Couldn't read line from day 3 input")
-                .chars()
-                .map(|ch| ch == '#')
-                .collect()
-        })
-        .collect();
-    grid
+fn main() -> aoc::Result<()> {
+    let grid = load_data()?;
+    println!("Part 1: {}", part1(&grid));
+    println!("Part 2: {}", part2(&grid));
+    Ok(())
 }
 
-fn count_trees(slope_right: usize, slope_down: usize, grid: &[Vec<bool>]) -> usize {
-    let (rows, cols) = (grid.len(), grid[0].len());
-    let (mut i, mut j): (usize, usize) = (0, 0);
-    let mut count: usize = 0;
-    loop {
-        i += slope_down;
-        j = (j + slope_right) % cols;
-        if i >= rows {
-            break count;
-        }
-        count += grid[i][j] as usize;
-    }
+fn load_data() -> aoc::Result<Grid<bool>> {
+    let input = fs::read_to_string("res/input03.txt")?;
+    let grid = Span::new(&input).grid(|ch| match ch {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    Ok(grid)
+}
+
+fn count_trees(slope_right: usize, slope_down: usize, grid: &Grid<bool>) -> usize {
+    let slope = Vector2::new(slope_right as i64, slope_down as i64);
+    (1..)
+        .map(|step| slope * step)
+        .take_while(|position| position.y < grid.height() as i64)
+        .filter(|&position| grid.get_wrapping(position) == Some(&true))
+        .count()
 }
 
-pub fn part1() -> usize {
-    let grid = load_data();
-    count_trees(3, 1, &grid)
+fn part1(grid: &Grid<bool>) -> usize {
+    count_trees(3, 1, grid)
 }
 
-pub fn part2() -> usize {
-    let grid = load_data();
+fn part2(grid: &Grid<bool>) -> usize {
     let slopes: Vec<(usize, usize)> = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
     let mut tree_product = 1;
     for slope in slopes {
-        tree_product *= count_trees(slope.0, slope.1, &grid);
+        tree_product *= count_trees(slope.0, slope.1, grid);
     }
     tree_product
 }
 
 #[test]
+#[ignore = "needs the puzzle input in res/input03.txt"]
 fn data_loading() {
-    let grid = load_data();
-    assert_eq!(grid.len(), 323);
+    let grid = load_data().unwrap();
+    assert_eq!(grid.height(), 323);
 }
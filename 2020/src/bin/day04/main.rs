@@ -1,41 +1,49 @@
 use std::fs;
 
-fn main() {}
-
-// struct Passport {
-//     byr: String,
-//     iyr: String,
-//     eyr: String,
-//     hgt: String,
-//     hcl: String,
-//     ecl: String,
-//     pid: String,
-//     cid: String,
-// }
-
-pub fn part1() -> usize {
-    let pports = fs::read_to_string("res/input04.txt").expect("Couldn't read day 4 input");
-    let req_fields: Vec<String> = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"]
+use parse::{ParseError, Span};
+
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/input04.txt")?;
+    let passports = parse_passports(&input)?;
+    println!("Part 1: {}", part1(&passports));
+    println!("Part 2: {}", part2(&passports));
+    Ok(())
+}
+
+const REQUIRED_FIELDS: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+
+/// The `key:value` fields of each passport, passports being separated by
+/// blank lines.
+fn parse_passports(input: &str) -> Result<Vec<Vec<(&str, &str)>>, ParseError> {
+    Span::new(input)
+        .blocks()
+        .map(|passport| {
+            passport
+                .fields(":")
+                .map(|field| field.map(|(key, value)| (key.as_str(), value.as_str())))
+                .collect()
+        })
+        .collect()
+}
+
+fn has_required_fields(passport: &[(&str, &str)]) -> bool {
+    REQUIRED_FIELDS
         .iter()
-        .map(|s| s.to_string())
-        .collect();
-
-    let mut count: usize = 0;
-    for pport in pports.split("\n\n") {
-        let mut keys: Vec<String> = Vec::new();
-        for item in pport.split_whitespace() {
-            keys.push(item.split(':').next().unwrap().to_string());
-        }
-        count += req_fields.iter().all(|key| keys.contains(key)) as usize;
-    }
-    count
+        .all(|required| passport.iter().any(|(key, _)| key == required))
+}
+
+fn part1(passports: &[Vec<(&str, &str)>]) -> usize {
+    passports
+        .iter()
+        .filter(|passport| has_required_fields(passport))
+        .count()
 }
 
 fn is_year_valid(v: &str, min: i32, max: i32) -> bool {
     let vval = v.parse::<i32>();
     match vval {
         Err(_) => false,
-        Ok(n) => (min <= n) && (n <= max),
+        Ok(n) => (min..=max).contains(&n),
     }
 }
 
@@ -46,7 +54,7 @@ fn is_height_valid(v: &str) -> bool {
         } else {
             match v[..3].parse::<u32>() {
                 Err(_) => false,
-                Ok(n) => (150 <= n) && (n <= 193),
+                Ok(n) => (150..=193).contains(&n),
             }
         }
     } else if v.len() == 4 {
@@ -55,7 +63,7 @@ fn is_height_valid(v: &str) -> bool {
         } else {
             match v[..2].parse::<u32>() {
                 Err(_) => false,
-                Ok(n) => (59 <= n) && (n <= 76),
+                Ok(n) => (59..=76).contains(&n),
             }
         }
     } else {
@@ -75,110 +83,81 @@ fn is_passport_id_valid(v: &str) -> bool {
     v.len() == 9 && v.chars().all(|ch| ch.is_numeric())
 }
 
-pub fn part2() -> usize {
-    let pports = fs::read_to_string("res/input04.txt").expect("Couldn't read day 4 input");
-    let req_fields: Vec<String> = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"]
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-
-    let mut count: usize = 0;
-    for pport in pports.split("\n\n") {
-        let mut keys: Vec<String> = Vec::new();
-        let mut valid = true;
-        for item in pport.split_whitespace() {
-            let mut kv = item.split(':');
-            let k = kv.next().unwrap();
-            let v = kv.next().unwrap();
-            keys.push(k.to_string());
-            match k {
-                "byr" => {
-                    if !is_year_valid(v, 1920, 2002) {
-                        valid = false;
-                        break;
-                    }
-                }
-                "iyr" => {
-                    if !is_year_valid(v, 2010, 2020) {
-                        valid = false;
-                        break;
-                    }
-                }
-                "eyr" => {
-                    if !is_year_valid(v, 2020, 2030) {
-                        valid = false;
-                        break;
-                    }
-                }
-                "hgt" => {
-                    if !is_height_valid(v) {
-                        valid = false;
-                        break;
-                    }
-                }
-                "hcl" => {
-                    if !is_hair_color_valid(v) {
-                        valid = false;
-                        break;
-                    }
-                }
-                "ecl" => {
-                    if !is_eye_color_valid(v) {
-                        valid = false;
-                        break;
-                    }
-                }
-                "pid" => {
-                    if !is_passport_id_valid(v) {
-                        valid = false;
-                        break;
-                    }
-                }
-                _ => (),
-            }
-        }
-        if valid {
-            count += req_fields.iter().all(|key| keys.contains(key)) as usize;
-        }
+fn is_field_valid(key: &str, value: &str) -> bool {
+    match key {
+        "byr" => is_year_valid(value, 1920, 2002),
+        "iyr" => is_year_valid(value, 2010, 2020),
+        "eyr" => is_year_valid(value, 2020, 2030),
+        "hgt" => is_height_valid(value),
+        "hcl" => is_hair_color_valid(value),
+        "ecl" => is_eye_color_valid(value),
+        "pid" => is_passport_id_valid(value),
+        _ => true,
     }
-    count
+}
+
+fn part2(passports: &[Vec<(&str, &str)>]) -> usize {
+    passports
+        .iter()
+        .filter(|passport| {
+            has_required_fields(passport)
+                && passport
+                    .iter()
+                    .all(|&(key, value)| is_field_valid(key, value))
+        })
+        .count()
 }
 
 #[test]
 fn year_validation_test() {
-    assert!(is_year_valid(&"1921", 1920, 2002));
-    assert!(is_year_valid(&"2002", 1920, 2002));
-    assert!(!is_year_valid(&"2020", 1920, 2002));
-    assert!(!is_year_valid(&"abcd", 1920, 2002));
+    assert!(is_year_valid("1921", 1920, 2002));
+    assert!(is_year_valid("2002", 1920, 2002));
+    assert!(!is_year_valid("2020", 1920, 2002));
+    assert!(!is_year_valid("abcd", 1920, 2002));
 }
 
 #[test]
 fn height_validation() {
-    assert!(is_height_valid(&"155cm"));
-    assert!(is_height_valid(&"60in"));
-    assert!(!is_height_valid(&"149cm"));
-    assert!(!is_height_valid(&"77in"));
-    assert!(!is_height_valid(&"abdcm"));
+    assert!(is_height_valid("155cm"));
+    assert!(is_height_valid("60in"));
+    assert!(!is_height_valid("149cm"));
+    assert!(!is_height_valid("77in"));
+    assert!(!is_height_valid("abdcm"));
 }
 
 #[test]
 fn hair_color_validation() {
-    assert!(is_hair_color_valid(&"#a53489"));
-    assert!(!is_hair_color_valid(&"#a5348"));
-    assert!(!is_hair_color_valid(&"a53489"));
-    assert!(!is_hair_color_valid(&"#a5348g"));
+    assert!(is_hair_color_valid("#a53489"));
+    assert!(!is_hair_color_valid("#a5348"));
+    assert!(!is_hair_color_valid("a53489"));
+    assert!(!is_hair_color_valid("#a5348g"));
 }
 
 #[test]
 fn eye_color_validation() {
-    assert!(is_eye_color_valid(&"brn"));
-    assert!(!is_eye_color_valid(&"blk"));
+    assert!(is_eye_color_valid("brn"));
+    assert!(!is_eye_color_valid("blk"));
 }
 
 #[test]
 fn passport_id_validation() {
-    assert!(is_passport_id_valid(&"234893450"));
-    assert!(is_passport_id_valid(&"034893450"));
-    assert!(!is_passport_id_valid(&"23489345"));
-    assert!(!is_passport_id_valid(&"23489a450"));
+    assert!(is_passport_id_valid("234893450"));
+    assert!(is_passport_id_valid("034893450"));
+    assert!(!is_passport_id_valid("23489345"));
+    assert!(!is_passport_id_valid("23489a450"));
+}
+
+#[test]
+fn passport_parsing() {
+    let input = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+";
+    let passports = parse_passports(input).unwrap();
+    assert_eq!(passports.len(), 2);
+    assert_eq!(passports[1][6], ("byr", "1929"));
+    assert_eq!(part1(&passports), 1);
+    assert_eq!(parse_passports("ecl gry").unwrap_err().column, 1);
 }
@@ -1,7 +1,13 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
 
-fn main() {}
+use aoc::Error;
+
+fn main() -> aoc::Result<()> {
+    let seats = load_data()?;
+    println!("Part 1: {}", part1(&seats)?);
+    println!("Part 2: {}", part2(seats)?);
+    Ok(())
+}
 
 fn string_to_seat(s: &str) -> (usize, usize, usize) {
     let mut row: usize = 0;
@@ -18,33 +24,29 @@ fn string_to_seat(s: &str) -> (usize, usize, usize) {
     (row, column, row * 8 + column)
 }
 
-fn load_data() -> Vec<(usize, usize, usize)> {
-    let f = File::open("res/input05.txt").expect("Couldn't read day 5 input");
-    let reader = BufReader::new(f);
-    reader
-        .lines()
-        .map(|line| string_to_seat(&line.expect("Couldn't read line from day 5 input")))
-        .collect()
+fn load_data() -> aoc::Result<Vec<(usize, usize, usize)>> {
+    let input = fs::read_to_string("res/input05.txt")?;
+    Ok(input.lines().map(string_to_seat).collect())
 }
 
-pub fn part1() -> usize {
-    let seats = load_data();
-    match seats.iter().max_by_key(|seat| seat.2) {
-        Some(seat) => seat.2,
-        None => panic!("Couldn't find highest seat id"),
-    }
+fn part1(seats: &[(usize, usize, usize)]) -> aoc::Result<usize> {
+    seats
+        .iter()
+        .map(|seat| seat.2)
+        .max()
+        .ok_or_else(|| Error::no_solution("no boarding passes"))
 }
 
-pub fn part2() -> usize {
-    let mut seats = load_data();
+fn part2(mut seats: Vec<(usize, usize, usize)>) -> aoc::Result<usize> {
     seats.sort_by_key(|(_, _, seat_id)| *seat_id);
-    let want_seats = seats[1..]
+    let want_seats = seats
         .iter()
+        .skip(1)
         .zip(seats.iter())
         .find(|((_, _, sidx1), (_, _, sidx2))| *sidx1 - *sidx2 != 1);
     match want_seats {
-        Some(seat) => seat.0 .2 - 1,
-        None => panic!("Couldn't find my seat id"),
+        Some(seat) => Ok(seat.0 .2 - 1),
+        None => Err(Error::no_solution("no gap between the seat ids")),
     }
 }
 
@@ -1,23 +1,33 @@
 use std::collections::HashSet;
 use std::fs;
 
-fn main() {}
+use parse::Span;
 
-fn load_data() -> Vec<Vec<HashSet<char>>> {
-    let answer_list = fs::read_to_string("res/input06.txt").expect("Couldn't read day 6 input");
-    let mut groups: Vec<Vec<HashSet<char>>> = Vec::new();
-    for group in answer_list.split("\n\n") {
-        let grp_vec: Vec<HashSet<char>> = group
-            .lines()
-            .filter(|line| line.contains(|ch: char| !ch.is_whitespace()))
-            .map(|line| line.chars().filter(|ch| !ch.is_whitespace()).collect())
-            .collect();
-        groups.push(grp_vec);
-    }
-    groups
+fn main() -> aoc::Result<()> {
+    let groups = load_data()?;
+    println!("Part 1: {}", part1(&groups));
+    println!("Part 2: {}", part2(&groups));
+    Ok(())
+}
+
+fn load_data() -> aoc::Result<Vec<Vec<HashSet<char>>>> {
+    let answer_list = fs::read_to_string("res/input06.txt")?;
+    Ok(parse_groups(&answer_list))
 }
 
-fn group_union(groups: Vec<Vec<HashSet<char>>>) -> usize {
+fn parse_groups(answer_list: &str) -> Vec<Vec<HashSet<char>>> {
+    Span::new(answer_list)
+        .blocks()
+        .map(|group| {
+            group
+                .words()
+                .map(|person| person.as_str().chars().collect())
+                .collect()
+        })
+        .collect()
+}
+
+fn group_union(groups: &[Vec<HashSet<char>>]) -> usize {
     groups
         .iter()
         .map(|group| {
@@ -29,12 +39,11 @@ fn group_union(groups: Vec<Vec<HashSet<char>>>) -> usize {
         .sum()
 }
 
-pub fn part1() -> usize {
-    let groups = load_data();
+fn part1(groups: &[Vec<HashSet<char>>]) -> usize {
     group_union(groups)
 }
 
-fn group_intersection(groups: Vec<Vec<HashSet<char>>>) -> usize {
+fn group_intersection(groups: &[Vec<HashSet<char>>]) -> usize {
     groups
         .iter()
         .map(|group| {
@@ -48,8 +57,7 @@ fn group_intersection(groups: Vec<Vec<HashSet<char>>>) -> usize {
         .sum()
 }
 
-pub fn part2() -> usize {
-    let groups = load_data();
+fn part2(groups: &[Vec<HashSet<char>>]) -> usize {
     group_intersection(groups)
 }
 
@@ -62,7 +70,7 @@ fn union_test() {
             group.lines().map(|line| line.chars().collect()).collect();
         groups.push(grp_vec);
     }
-    assert_eq!(group_union(groups), 11);
+    assert_eq!(group_union(&groups), 11);
     let s = "abcx\nabcy\nabcz".to_string();
     let mut groups: Vec<Vec<HashSet<char>>> = Vec::new();
     for group in s.split("\n\n") {
@@ -70,7 +78,7 @@ fn union_test() {
             group.lines().map(|line| line.chars().collect()).collect();
         groups.push(grp_vec);
     }
-    assert_eq!(group_union(groups), 6);
+    assert_eq!(group_union(&groups), 6);
 }
 
 #[test]
@@ -82,7 +90,7 @@ fn intersection_test() {
             group.lines().map(|line| line.chars().collect()).collect();
         groups.push(grp_vec);
     }
-    assert_eq!(group_intersection(groups), 6);
+    assert_eq!(group_intersection(&groups), 6);
     let s = "abcx\nabcy\nabcz".to_string();
     let mut groups: Vec<Vec<HashSet<char>>> = Vec::new();
     for group in s.split("\n\n") {
@@ -90,7 +98,7 @@ fn intersection_test() {
             group.lines().map(|line| line.chars().collect()).collect();
         groups.push(grp_vec);
     }
-    assert_eq!(group_intersection(groups), 3);
+    assert_eq!(group_intersection(&groups), 3);
     let s: String = ('a'..='z').collect();
     let mut groups: Vec<Vec<HashSet<char>>> = Vec::new();
     for group in s.split("\n\n") {
@@ -101,5 +109,13 @@ fn intersection_test() {
             .collect();
         groups.push(grp_vec);
     }
-    assert_eq!(group_intersection(groups), 26);
+    assert_eq!(group_intersection(&groups), 26);
+}
+
+#[test]
+fn group_parsing() {
+    let groups = parse_groups("abc\n\na\nb\nc\n\n\nab\nac\n");
+    assert_eq!(groups.len(), 3);
+    assert_eq!(groups[1].len(), 3);
+    assert_eq!(group_union(&groups), 9);
 }
@@ -1,8 +1,13 @@
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
 
-fn main() {}
+use aoc::{Error, Solution};
+use parse::{ParseError, Span};
+
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/input07.txt")?;
+    aoc::solve::<Day07>(&input)
+}
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct Bag(String, String);
@@ -25,37 +30,36 @@ impl Rule {
     }
 }
 
-fn string_to_rule(s: &str) -> Rule {
-    let s = s.strip_suffix('.').unwrap();
-    let mut outer_inner_split = s.split(" bags contain ");
-    let mut quality_colour_split = outer_inner_split.next().unwrap().split(' ');
-    let quality = quality_colour_split.next().unwrap().to_string();
-    let colour = quality_colour_split.next().unwrap().to_string();
-    let outer_bag = Bag(quality, colour);
+fn parse_bag(bag: Span) -> Result<Bag, ParseError> {
+    let (quality, colour) = bag.split_once(" ")?;
+    Ok(Bag::new(quality.as_str(), colour.as_str()))
+}
+
+fn string_to_rule(line: Span) -> Result<Rule, ParseError> {
+    let (outer, inner_bags) = line
+        .trim()
+        .strip_suffix(".")?
+        .split_once(" bags contain ")?;
+    let outer_bag = parse_bag(outer)?;
     let mut contents: Vec<(Bag, u32)> = Vec::new();
-    let inner_bags = outer_inner_split.next().unwrap();
-    if !inner_bags.contains("no other bags") {
-        for bag_quant_string in inner_bags.split(", ") {
-            let mut char_iter = bag_quant_string.chars();
-            let quantity: u32 = char_iter
-                .by_ref()
-                .take_while(|ch| ch.is_numeric())
-                .collect::<String>()
-                .parse()
-                .unwrap();
-            let bag_string: String = char_iter.collect();
-            let bag_string = match bag_string.strip_suffix(" bags") {
-                Some(s) => s,
-                None => bag_string.strip_suffix(" bag").unwrap(),
-            };
-            let mut bag_iter = bag_string.split(' ');
-            let quality = bag_iter.next().unwrap().to_string();
-            let colour = bag_iter.next().unwrap().to_string();
-            let bag = Bag(quality, colour);
-            contents.push((bag, quantity));
+    if inner_bags.as_str() != "no other bags" {
+        for bag_quant in inner_bags.split(", ") {
+            let (quantity, bag) = bag_quant.split_once(" ")?;
+            let bag = bag
+                .strip_suffix(" bags")
+                .or_else(|_| bag.strip_suffix(" bag"))?;
+            contents.push((parse_bag(bag)?, quantity.parse()?));
         }
     }
-    Rule::new(outer_bag, contents)
+    Ok(Rule::new(outer_bag, contents))
+}
+
+fn parse_rules(input: &str) -> Result<Vec<Rule>, ParseError> {
+    Span::new(input)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(string_to_rule)
+        .collect()
 }
 
 fn bag_contains_bag<'a>(
@@ -84,56 +88,59 @@ fn bag_contains_bag<'a>(
     contains
 }
 
-fn load_rules() -> Vec<Rule> {
-    let f = File::open("res/input07.txt").expect("Couldn't read day 7 input");
-    let reader = BufReader::new(f);
-    reader
-        .lines()
-        .map(|line| string_to_rule(&line.expect("Couldn't read line from day 7 input")))
-        .collect()
-}
-
-pub fn part1() -> u32 {
-    let rules = load_rules();
+fn part1(rules: &[Rule]) -> u32 {
     let mut count: u32 = 0;
     let shiny_gold = Bag::new("shiny", "gold");
     let mut found_bags: HashSet<&Bag> = HashSet::new();
-    for rule in &rules {
-        count += bag_contains_bag(&rule.bag, &shiny_gold, &rules, &mut found_bags) as u32;
+    for rule in rules {
+        count += bag_contains_bag(&rule.bag, &shiny_gold, rules, &mut found_bags) as u32;
     }
     count
 }
 
-fn contents(bag: &Bag, rules: &[Rule]) -> usize {
-    let top_rule = rules.iter().find(|rule| &rule.bag == bag).unwrap();
+fn contents(bag: &Bag, rules: &[Rule]) -> aoc::Result<usize> {
+    let top_rule = rules
+        .iter()
+        .find(|rule| &rule.bag == bag)
+        .ok_or_else(|| Error::invalid_state(format!("no rule for {} {} bags", bag.0, bag.1)))?;
     let mut count: usize = 0;
     for (inner_bag, quantity) in &top_rule.contents {
-        count += (*quantity as usize) * (1 + contents(&inner_bag, rules));
+        count += (*quantity as usize) * (1 + contents(inner_bag, rules)?);
     }
-    count
+    Ok(count)
 }
 
-pub fn part2() -> usize {
-    let rules = load_rules();
+fn part2(rules: &[Rule]) -> aoc::Result<usize> {
     let shiny_gold = Bag::new("shiny", "gold");
-    contents(&shiny_gold, &rules)
+    contents(&shiny_gold, rules)
+}
+
+struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<Rule>;
+    type Answer1 = u32;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(parse_rules(input)?)
+    }
+
+    fn part1(rules: &Self::Input) -> aoc::Result<u32> {
+        Ok(part1(rules))
+    }
+
+    fn part2(rules: &Self::Input) -> aoc::Result<usize> {
+        part2(rules)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn get_example() -> &'static str {
-        "light red bags contain 1 bright white bag, 2 muted yellow bags.
-dark orange bags contain 3 bright white bags, 4 muted yellow bags.
-bright white bags contain 1 shiny gold bag.
-muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
-shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
-dark olive bags contain 3 faded blue bags, 4 dotted black bags.
-vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
-faded blue bags contain no other bags.
-dotted black bags contain no other bags.
-"
+    fn get_example() -> String {
+        aoc::example_input(2020, 7, 1)
     }
 
     #[test]
@@ -192,16 +199,13 @@ dotted black bags contain no other bags.
         rules_string
             .lines()
             .zip(rules.iter())
-            .for_each(|(s, r)| assert_eq!(string_to_rule(s), *r));
+            .for_each(|(s, r)| assert_eq!(string_to_rule(Span::new(s)).as_ref(), Ok(r)));
     }
 
     #[test]
     fn shiny_in_examples() {
         let rules_string = get_example();
-        let rules: Vec<Rule> = rules_string
-            .lines()
-            .map(|line| string_to_rule(line))
-            .collect();
+        let rules = parse_rules(&rules_string).unwrap();
         let shiny_gold = Bag::new("shiny", "gold");
         let bright_white = Bag::new("bright", "white");
         let muted_yellow = Bag::new("muted", "yellow");
@@ -273,12 +277,9 @@ dotted black bags contain no other bags.
     #[test]
     fn content_quantity() {
         let rules_string = get_example();
-        let rules: Vec<Rule> = rules_string
-            .lines()
-            .map(|line| string_to_rule(line))
-            .collect();
+        let rules = parse_rules(&rules_string).unwrap();
         let shiny_gold = Bag::new("shiny", "gold");
-        assert_eq!(contents(&shiny_gold, &rules), 32);
+        assert_eq!(contents(&shiny_gold, &rules).unwrap(), 32);
 
         let rules_string = "shiny gold bags contain 2 dark red bags.
 dark red bags contain 2 dark orange bags.
@@ -288,10 +289,10 @@ dark green bags contain 2 dark blue bags.
 dark blue bags contain 2 dark violet bags.
 dark violet bags contain no other bags.
 ";
-        let rules: Vec<Rule> = rules_string
-            .lines()
-            .map(|line| string_to_rule(line))
-            .collect();
-        assert_eq!(contents(&shiny_gold, &rules), 126);
+        let rules = parse_rules(rules_string).unwrap();
+        assert_eq!(contents(&shiny_gold, &rules).unwrap(), 126);
+        assert!(contents(&Bag::new("dark", "grey"), &rules).is_err());
     }
 }
+
+aoc::example_tests!(Day07, 2020, 7);
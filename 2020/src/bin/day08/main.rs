@@ -1,8 +1,17 @@
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
+use std::ptr;
 
-fn main() {}
+use aoc::Error;
+use parse::{ParseError, Span};
+
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/input08.txt")?;
+    let instructions = parse_instructions(&input)?;
+    println!("Part 1: {}", part1(&instructions)?);
+    println!("Part 2: {}", part2(instructions)?);
+    Ok(())
+}
 
 type ComputerOp = fn(i32, &mut i32) -> i32;
 
@@ -19,61 +28,52 @@ fn nop(_inp: i32, _accumulator: &mut i32) -> i32 {
     1
 }
 
-fn load_instructions() -> Vec<(ComputerOp, i32)> {
-    let f = File::open("res/input08.txt").expect("Couldn't open day 8 input");
-    let reader = BufReader::new(f);
-    let mut instructions: Vec<(ComputerOp, i32)> = Vec::new();
-    for line in reader.lines() {
-        let s = line.expect("Couldn't read line from day 8 input");
-        let mut instr_off_split = s.split(' ');
-        let func = match instr_off_split.next() {
-            None => unreachable!(),
-            Some(s) => {
-                if s == "acc" {
-                    acc
-                } else if s == "jmp" {
-                    jmp
-                } else if s == "nop" {
-                    nop
-                } else {
-                    unreachable!()
-                }
-            }
-        };
-        let inp: i32 = instr_off_split.next().unwrap().parse().unwrap();
-        instructions.push((func, inp));
-    }
-    instructions
+fn parse_instructions(input: &str) -> Result<Vec<(ComputerOp, i32)>, ParseError> {
+    Span::new(input)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (op, inp) = line.trim().split_once(" ")?;
+            let func: ComputerOp = match op.as_str() {
+                "acc" => acc,
+                "jmp" => jmp,
+                "nop" => nop,
+                _ => return Err(op.expected("acc, jmp or nop")),
+            };
+            Ok((func, inp.parse()?))
+        })
+        .collect()
 }
 
-fn execute_instructions(instructions: &[(ComputerOp, i32)]) -> (i32, usize) {
+/// The index `d` instructions on from `idx`, an error if that is before the
+/// first instruction.
+fn jump(idx: usize, d: i32) -> aoc::Result<usize> {
+    idx.checked_add_signed(d as isize)
+        .ok_or_else(|| Error::invalid_state(format!("jumped before the start from {}", idx)))
+}
+
+fn execute_instructions(instructions: &[(ComputerOp, i32)]) -> aoc::Result<(i32, usize)> {
     let mut executed: HashSet<usize> = HashSet::new();
     let mut accumulator: i32 = 0;
     let mut idx: usize = 0;
     loop {
         let already_done = executed.insert(idx);
         if !already_done {
-            break (accumulator, idx);
+            break Ok((accumulator, idx));
         }
         let d = instructions[idx].0(instructions[idx].1, &mut accumulator);
-        if d < 0 {
-            idx -= d.abs() as usize;
-        } else {
-            idx += d as usize;
-        }
+        idx = jump(idx, d)?;
         if idx >= instructions.len() {
-            break (accumulator, idx);
+            break Ok((accumulator, idx));
         }
     }
 }
 
-pub fn part1() -> (i32, usize) {
-    let instructions = load_instructions();
-    execute_instructions(&instructions)
+fn part1(instructions: &[(ComputerOp, i32)]) -> aoc::Result<i32> {
+    Ok(execute_instructions(instructions)?.0)
 }
 
-fn get_trace() -> Vec<usize> {
-    let instructions = load_instructions();
+fn get_trace(instructions: &[(ComputerOp, i32)]) -> aoc::Result<Vec<usize>> {
     let mut executed: HashSet<usize> = HashSet::new();
     let mut trace: Vec<usize> = Vec::new();
     let mut accumulator: i32 = 0;
@@ -82,42 +82,58 @@ fn get_trace() -> Vec<usize> {
         let already_done = executed.insert(idx);
         trace.push(idx);
         if !already_done {
-            break trace;
+            break Ok(trace);
         }
         let d = instructions[idx].0(instructions[idx].1, &mut accumulator);
-        if d < 0 {
-            idx -= d.abs() as usize;
-        } else {
-            idx += d as usize;
-        }
-        if idx == instructions.len() {
-            println!("Finished exec!! {}", accumulator);
-            break trace;
+        idx = jump(idx, d)?;
+        if idx >= instructions.len() {
+            break Ok(trace);
         }
     }
 }
 
-pub fn part2() -> i32 {
-    let trace = get_trace();
-    let mut instructions = load_instructions();
-    let mut acc: i32 = 0;
-    let mut last_idx: usize;
+fn part2(mut instructions: Vec<(ComputerOp, i32)>) -> aoc::Result<i32> {
+    let trace = get_trace(&instructions)?;
     for idx in trace {
+        if idx >= instructions.len() {
+            continue;
+        }
         let original_instr = instructions[idx].0;
-        if original_instr as usize == nop as usize {
+        if ptr::fn_addr_eq(original_instr, nop as ComputerOp) {
             instructions[idx].0 = jmp;
-        } else if original_instr as usize == jmp as usize {
+        } else if ptr::fn_addr_eq(original_instr, jmp as ComputerOp) {
             instructions[idx].0 = nop;
         } else {
             continue;
         }
-        let result = execute_instructions(&instructions);
-        last_idx = result.1;
-        if last_idx >= instructions.len() {
-            acc = result.0;
-            break;
+        // a changed jump can lead before the start, which is just another way
+        // of not terminating
+        if let Ok((acc, last_idx)) = execute_instructions(&instructions) {
+            if last_idx >= instructions.len() {
+                return Ok(acc);
+            }
         }
         instructions[idx].0 = original_instr;
     }
-    acc
+    Err(Error::no_solution(
+        "no single change makes the program terminate",
+    ))
+}
+
+#[test]
+fn example_program() {
+    let program = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6
+";
+    let instructions = parse_instructions(program).unwrap();
+    assert_eq!(part1(&instructions).unwrap(), 5);
+    assert_eq!(part2(instructions).unwrap(), 8);
+    assert_eq!(parse_instructions("mul +2").unwrap_err().column, 1);
 }
@@ -1,21 +1,18 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
 use std::ops::Add;
 
-fn main() {}
+use aoc::Error;
+use parse::Span;
 
-fn load_data() -> Vec<u64> {
-    let f = File::open("res/input09.txt").expect("Couldn't open input for day 9");
-    let reader = BufReader::new(f);
-
-    reader
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/input09.txt")?;
+    let nums: Vec<u64> = Span::new(&input)
         .lines()
-        .map(|line| {
-            line.expect("Couldn't read line from day 9 input")
-                .parse()
-                .unwrap()
-        })
-        .collect()
+        .map(|line| line.trim().parse())
+        .collect::<Result<_, _>>()?;
+    println!("Part 1: {}", part1(&nums, 25)?);
+    println!("Part 2: {}", part2(&nums, 25)?);
+    Ok(())
 }
 
 fn sum_of_nums<T: Add<Output = T> + PartialOrd + Copy>(s: T, nums: &[T]) -> bool {
@@ -29,29 +26,38 @@ fn sum_of_nums<T: Add<Output = T> + PartialOrd + Copy>(s: T, nums: &[T]) -> bool
     false
 }
 
-pub fn part1() -> Option<u64> {
-    let nums = load_data();
-    let mut cur_min = nums[0..25].iter().min().unwrap();
-    let mut cur_max = nums[0..25].iter().max().unwrap();
-    for i in 25..nums.len() {
+/// Index of the first number after the preamble that isn't the sum of two
+/// different numbers among the `preamble` before it.
+fn first_invalid(nums: &[u64], preamble: usize) -> Option<usize> {
+    let window = nums.get(..preamble)?;
+    let mut cur_min = *window.iter().min()?;
+    let mut cur_max = *window.iter().max()?;
+    for i in preamble..nums.len() {
         if (nums[i] <= 2 * cur_min)
             || (2 * cur_max <= nums[i])
-            || !sum_of_nums(nums[i], &nums[i - 25..i])
+            || !sum_of_nums(nums[i], &nums[i - preamble..i])
         {
-            return Some(nums[i]);
+            return Some(i);
         }
-        if cur_min == &nums[i - 25] {
-            cur_min = nums[i - 24..i + 1].iter().min().unwrap();
+        if cur_min == nums[i - preamble] {
+            cur_min = *nums[i + 1 - preamble..=i].iter().min()?;
         }
-        cur_max = cur_max.max(&nums[i]);
+        cur_max = cur_max.max(nums[i]);
     }
     None
 }
 
-pub fn part2() -> Option<u64> {
-    let nums: Vec<u64> = load_data();
-    let target = nums[520];
-    let nums: Vec<u64> = nums[..520].iter().copied().collect();
+fn part1(nums: &[u64], preamble: usize) -> aoc::Result<u64> {
+    first_invalid(nums, preamble)
+        .map(|i| nums[i])
+        .ok_or_else(|| Error::no_solution("every number is a sum of two before it"))
+}
+
+fn part2(nums: &[u64], preamble: usize) -> aoc::Result<u64> {
+    let invalid = first_invalid(nums, preamble)
+        .ok_or_else(|| Error::no_solution("every number is a sum of two before it"))?;
+    let target = nums[invalid];
+    let nums = &nums[..invalid];
     let partial_sums: Vec<u64> = nums
         .iter()
         .scan(0, |state, &x| {
@@ -59,15 +65,29 @@ pub fn part2() -> Option<u64> {
             Some(*state)
         })
         .collect();
-    for i in 0..(partial_sums.len() - 1) {
+    for i in 0..partial_sums.len() {
         let sub_el = if i == 0 { 0 } else { partial_sums[i - 1] };
-        for j in (i + 1)..(partial_sums.len() - 1) {
+        for j in (i + 1)..partial_sums.len() {
             if partial_sums[j] - sub_el == target {
-                let min = nums[i..(j + 1)].iter().min().unwrap();
-                let max = nums[i..(j + 1)].iter().max().unwrap();
-                return Some(min + max);
+                let range = &nums[i..=j];
+                if let (Some(min), Some(max)) = (range.iter().min(), range.iter().max()) {
+                    return Ok(min + max);
+                }
             }
         }
     }
-    None
+    Err(Error::no_solution(format!(
+        "no contiguous range sums to {}",
+        target
+    )))
+}
+
+#[test]
+fn example_numbers() {
+    let nums = [
+        35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309, 576,
+    ];
+    assert_eq!(part1(&nums, 5).unwrap(), 127);
+    assert_eq!(part2(&nums, 5).unwrap(), 62);
+    assert!(part1(&nums[..3], 5).is_err());
 }
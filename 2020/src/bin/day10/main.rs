@@ -1,23 +1,22 @@
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
 
-fn main() {}
+use aoc::Error;
+use parse::Span;
 
-fn load_joltages() -> Vec<u32> {
-    let f = File::open("res/input10.txt").expect("Couldn't open input for day 10");
-    let reader = BufReader::new(f);
-
-    reader
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/input10.txt")?;
+    let joltages: Vec<u32> = Span::new(&input)
         .lines()
-        .map(|line| line.expect("Couldn't read line from day 10 input"))
-        .filter(|line| line != &"\n".to_string())
-        .map(|line| line.parse().unwrap())
-        .collect()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.trim().parse())
+        .collect::<Result<_, _>>()?;
+    println!("Part 1: {}", part1(joltages.clone()));
+    println!("Part 2: {}", part2(joltages)?);
+    Ok(())
 }
 
-pub fn part1() -> usize {
-    let mut joltages = load_joltages();
+fn part1(mut joltages: Vec<u32>) -> usize {
     joltages.sort_unstable();
     let differences: Vec<u32> = joltages
         .iter()
@@ -53,13 +52,16 @@ fn num_ways_to_dest(dest_idx: usize, joltages: &[u32]) -> usize {
     _num_ways(dest_idx, joltages, &mut ways)
 }
 
-pub fn part2() -> usize {
-    let mut joltages = load_joltages();
-    let out_joltage = *joltages.iter().max().unwrap() + 3;
+fn part2(mut joltages: Vec<u32>) -> aoc::Result<usize> {
+    let out_joltage = joltages
+        .iter()
+        .max()
+        .ok_or_else(|| Error::no_solution("no adapters"))?
+        + 3;
     joltages.push(0);
     joltages.push(out_joltage);
     joltages.sort_unstable();
-    num_ways_to_dest(joltages.len() - 1, &joltages)
+    Ok(num_ways_to_dest(joltages.len() - 1, &joltages))
 }
 
 #[test]
@@ -73,3 +75,11 @@ fn path_counts() {
     let joltages = vec![0, 4];
     assert_eq!(num_ways_to_dest(1, &joltages), 0);
 }
+
+#[test]
+fn example_adapters() {
+    let joltages = vec![16, 10, 15, 5, 1, 11, 7, 19, 6, 12, 4];
+    assert_eq!(part1(joltages.clone()), 35);
+    assert_eq!(part2(joltages).unwrap(), 8);
+    assert!(part2(Vec::new()).is_err());
+}
@@ -1,24 +1,28 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
 
-fn main() {}
+use aoc::Error;
+use parse::{ParseError, Span};
 
-fn load_data() -> (u64, Vec<u64>) {
-    let f = File::open("res/input13.txt").expect("Couldn't open day 13 input");
-    let mut reader = BufReader::new(f);
-
-    let mut tstring = String::new();
-    reader
-        .read_line(&mut tstring)
-        .expect("Couldn't read line from day 13 input");
-    let timestamp: u64 = tstring.trim().parse().unwrap();
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/input13.txt")?;
+    let (timestamp, bus_ids) = load_data(&input)?;
+    println!("Part 1: {}", part1(timestamp, &bus_ids)?);
+    println!("Part 2: {}", part2(&bus_ids));
+    Ok(())
+}
 
-    let mut buss = String::new();
-    reader
-        .read_line(&mut buss)
-        .expect("Couldn't read line from day 13 input");
-    let bus_ids: Vec<u64> = buss.split(',').filter_map(|s| s.parse().ok()).collect();
-    (timestamp, bus_ids)
+/// The earliest departure and the bus ids, `None` for each `x`.
+fn load_data(input: &str) -> Result<(u64, Vec<Option<u64>>), ParseError> {
+    let (timestamp, buss) = Span::new(input).trim().split_once("\n")?;
+    let bus_ids = buss
+        .trim()
+        .split(",")
+        .map(|bid| match bid.as_str() {
+            "x" => Ok(None),
+            _ => bid.parse().map(Some),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((timestamp.trim().parse()?, bus_ids))
 }
 
 fn closest_multiple_higher(close_to: u64, mult_of: u64) -> u64 {
@@ -30,18 +34,15 @@ fn closest_multiple_higher(close_to: u64, mult_of: u64) -> u64 {
     div * mult_of
 }
 
-pub fn part1() -> u64 {
-    let (timestamp, bus_ids) = load_data();
-    let mut earliest_departures: Vec<u64> = Vec::new();
-    for bid in &bus_ids {
-        earliest_departures.push(closest_multiple_higher(timestamp, *bid));
-    }
-    let (idx, &depart) = earliest_departures
+fn part1(timestamp: u64, bus_ids: &[Option<u64>]) -> aoc::Result<u64> {
+    let (bid, depart) = bus_ids
         .iter()
-        .enumerate()
-        .min_by_key(|(_, ts)| *ts)
-        .unwrap();
-    bus_ids[idx] * (depart - timestamp)
+        .flatten()
+        .filter(|&&bid| bid > 0)
+        .map(|&bid| (bid, closest_multiple_higher(timestamp, bid)))
+        .min_by_key(|&(_, depart)| depart)
+        .ok_or_else(|| Error::no_solution("no buses in service"))?;
+    Ok(bid * (depart - timestamp))
 }
 
 fn extended_euclidean(a: i64, b: i64) -> (i64, i64, i64) {
@@ -91,23 +92,22 @@ fn crt_test() {
     assert_eq!(chinese_remainder(&bus_ids), 1202161486);
 }
 
-pub fn part2() -> i64 {
-    let f = File::open("res/input13.txt").expect("Couldn't open day 13 input");
-    let reader = BufReader::new(f);
-    let buss = reader.lines().nth(1).unwrap().unwrap();
-    let mut bus_ids: Vec<(i64, i64)> = buss
-        .trim()
-        .split(',')
+fn part2(bus_ids: &[Option<u64>]) -> i64 {
+    let mut bus_ids: Vec<(i64, i64)> = bus_ids
+        .iter()
         .enumerate()
-        .filter_map(|(idx, s)| {
-            if s == "x" {
-                None
-            } else {
-                Some((-(idx as i64), s.parse().unwrap()))
-            }
-        })
+        .filter_map(|(idx, bid)| bid.map(|bid| (-(idx as i64), bid as i64)))
         .collect();
     bus_ids.sort_by_key(|(_, bid)| *bid);
     bus_ids.reverse();
     chinese_remainder(&bus_ids)
 }
+
+#[test]
+fn example_schedule() {
+    let (timestamp, bus_ids) = load_data("939\n7,13,x,x,59,x,31,19\n").unwrap();
+    assert_eq!(part1(timestamp, &bus_ids).unwrap(), 295);
+    assert_eq!(part2(&bus_ids), 1068781);
+    assert!(part1(timestamp, &[None]).is_err());
+    assert_eq!(load_data("939\n7,y").unwrap_err().column, 3);
+}
@@ -1,43 +1,58 @@
-use regex::Regex;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-fn main() {}
-
-fn load_data() -> (Vec<(usize, Vec<(usize, bool)>)>, Vec<(usize, Vec<bool>)>) {
-    let f = File::open("res/input14.txt").expect("Couldn't open day 14 input");
-    let reader = BufReader::new(f);
-
-    let num_pat = Regex::new(r"\d+").unwrap();
-    let mut num_instr: usize = 0;
-    let mut masks: Vec<(usize, Vec<(usize, bool)>)> = Vec::new();
-    let mut instructions: Vec<(usize, Vec<bool>)> = Vec::new();
-    let mut i: usize = 0;
-    for line in reader.lines() {
-        let s = line.expect("Couldn't read line from day 14 input");
-        if &s[..4] == "mask" {
-            if i > 0 {
-                masks[i - 1].0 = num_instr;
+use std::fs;
+
+use parse::{ErrorKind, ParseError, Span};
+
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/input14.txt")?;
+    let (masks, instructions) = load_data(&input)?;
+    println!("Part 1: {}", part1(&masks, &instructions));
+    println!("Part 2: {}", part2(&masks, &instructions));
+    Ok(())
+}
+
+type Mask = Vec<(usize, bool)>;
+type Write = (usize, Vec<bool>);
+type Program = (Vec<(usize, Mask)>, Vec<Write>);
+
+/// Each mask with the number of writes that follow it, and every write as
+/// an address and a 36 bit value.
+fn load_data(input: &str) -> Result<Program, ParseError> {
+    let mut masks: Vec<(usize, Mask)> = Vec::new();
+    let mut instructions: Vec<Write> = Vec::new();
+    for line in Span::new(input).lines().map(|line| line.trim()) {
+        if line.is_empty() {
+            continue;
+        }
+        let (target, value) = line.key_value("=")?;
+        if target.as_str() == "mask" {
+            if value.as_str().len() != 36 {
+                return Err(value.error(ErrorKind::InvalidValue {
+                    found: value.as_str().to_string(),
+                    reason: "a mask has 36 bits".into(),
+                }));
             }
-            let mask: Vec<(usize, bool)> = s[7..]
+            let mask = value
+                .as_str()
                 .char_indices()
                 .filter(|(_, ch)| *ch != 'X')
-                .map(|(idx, ch)| (idx, ch == '1'))
-                .collect();
+                .map(|(idx, ch)| match ch {
+                    '0' | '1' => Ok((idx, ch == '1')),
+                    _ => Err(value.error(ErrorKind::UnexpectedChar(ch))),
+                })
+                .collect::<Result<_, _>>()?;
             masks.push((0, mask));
-            i += 1;
-            num_instr = 0;
             continue;
         }
-        num_instr += 1;
-        let mut mats = num_pat.find_iter(&s);
-        let addr: usize = mats.next().unwrap().as_str().parse().unwrap();
-        let num: u64 = mats.next().unwrap().as_str().parse().unwrap();
+        let addr = target.strip_prefix("mem[")?.strip_suffix("]")?.parse()?;
+        let num: u64 = value.parse()?;
+        match masks.last_mut() {
+            Some((num_instr, _)) => *num_instr += 1,
+            None => return Err(line.expected("a mask before the first write")),
+        }
         instructions.push((addr, num_to_binarray(num, 36)));
     }
-    masks[i - 1].0 = num_instr;
-    (masks, instructions)
+    Ok((masks, instructions))
 }
 
 fn num_to_binarray(mut num: u64, len: usize) -> Vec<bool> {
@@ -77,13 +92,12 @@ fn execute_part1(
     memory.insert(addr, value);
 }
 
-pub fn part1() -> u64 {
-    let (masks, instructions) = load_data();
+fn part1(masks: &[(usize, Mask)], instructions: &[Write]) -> u64 {
     let mut memory: HashMap<usize, Vec<bool>> = HashMap::new();
     let mut j: usize = 0;
     for (nins, mask) in masks {
         for (addr, val) in &instructions[j..j + nins] {
-            execute_part1(*addr, val, &mask, &mut memory);
+            execute_part1(*addr, val, mask, &mut memory);
         }
         j += nins;
     }
@@ -132,13 +146,12 @@ fn execute_part2(
     }
 }
 
-pub fn part2() -> u64 {
-    let (masks, instructions) = load_data();
+fn part2(masks: &[(usize, Mask)], instructions: &[Write]) -> u64 {
     let mut memory: HashMap<usize, Vec<bool>> = HashMap::new();
     let mut j: usize = 0;
     for (nins, mask) in masks {
         for (addr, val) in &instructions[j..j + nins] {
-            execute_part2(*addr, val, &mask, &mut memory);
+            execute_part2(*addr, val, mask, &mut memory);
         }
         j += nins;
     }
@@ -235,3 +248,28 @@ fn masking_part2_test() {
         assert!(res.contains(&a));
     }
 }
+
+#[test]
+fn example_program() {
+    let (masks, instructions) = load_data(
+        "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0
+",
+    )
+    .unwrap();
+    assert_eq!(masks[0].0, 3);
+    assert_eq!(part1(&masks, &instructions), 165);
+    let (masks, instructions) = load_data(
+        "mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1
+",
+    )
+    .unwrap();
+    assert_eq!(part2(&masks, &instructions), 208);
+    assert!(load_data("mem[8] = 11").is_err());
+    assert!(load_data("mask = 1X0").is_err());
+}
@@ -1,21 +1,35 @@
 use std::collections::HashMap;
 use std::fs::read_to_string;
 
-fn main() {}
+use aoc::Error;
+use parse::{ParseError, Span};
 
-fn str_to_map(s: &str) -> HashMap<usize, usize> {
-    s.trim()
-        .split(',')
+fn main() -> aoc::Result<()> {
+    let input = read_to_string("res/input15.txt")?;
+    let (nums, n) = load_data(&input)?;
+    println!("Part 1: {}", part1(nums.clone(), n));
+    println!("Part 2: {}", part2(nums, n));
+    Ok(())
+}
+
+fn str_to_map(s: &str) -> Result<HashMap<usize, usize>, ParseError> {
+    let nums: Vec<usize> = Span::new(s).trim().parse_list(",")?;
+    Ok(nums
+        .into_iter()
         .enumerate()
-        .map(|(idx, i)| (i.parse().unwrap(), idx))
-        .collect()
+        .map(|(idx, i)| (i, idx))
+        .collect())
 }
 
-fn load_data() -> (HashMap<usize, usize>, usize) {
-    let s = read_to_string("res/input15.txt").expect("Couldn't read day 15 input");
-    let nums = str_to_map(&s);
-    let n = *nums.iter().max_by_key(|(_, lp)| *lp).unwrap().0;
-    (nums, n)
+/// The turn each starting number was spoken on, and the last of them.
+fn load_data(s: &str) -> aoc::Result<(HashMap<usize, usize>, usize)> {
+    let nums = str_to_map(s)?;
+    let n = *nums
+        .iter()
+        .max_by_key(|(_, lp)| *lp)
+        .ok_or_else(|| Error::invalid_state("no starting numbers"))?
+        .0;
+    Ok((nums, n))
 }
 
 fn play_game(nums: &mut HashMap<usize, usize>, last_num: usize, turns: usize) -> usize {
@@ -28,13 +42,11 @@ fn play_game(nums: &mut HashMap<usize, usize>, last_num: usize, turns: usize) ->
     n
 }
 
-pub fn part1() -> usize {
-    let (mut nums, n) = load_data();
+fn part1(mut nums: HashMap<usize, usize>, n: usize) -> usize {
     play_game(&mut nums, n, 2020)
 }
 
-pub fn part2() -> usize {
-    let (mut nums, n) = load_data();
+fn part2(mut nums: HashMap<usize, usize>, n: usize) -> usize {
     play_game(&mut nums, n, 30_000_000)
 }
 
@@ -45,14 +57,15 @@ fn string_map_conversion_test() {
     m.insert(0, 0);
     m.insert(3, 1);
     m.insert(6, 2);
-    assert_eq!(str_to_map(s), m);
+    assert_eq!(str_to_map(s), Ok(m));
+    assert_eq!(str_to_map("0,,6").unwrap_err().column, 3);
+    assert!(load_data("").is_err());
 }
 
 #[test]
 fn play_test1() {
     let s = "0,3,6";
-    let mut nums = str_to_map(s);
-    let last_num = *nums.iter().max_by_key(|(_, lp)| *lp).unwrap().0;
+    let (mut nums, last_num) = load_data(s).unwrap();
     assert_eq!(play_game(&mut nums, last_num, 2020), 436);
 }
 
@@ -1,13 +1,18 @@
 use std::collections::HashSet;
-use std::fmt::Debug;
-use std::fs::File;
-use std::{
-    io::{BufRead, BufReader},
-    ops::RangeInclusive,
-    str::FromStr,
-};
+use std::fmt::Display;
+use std::fs;
+use std::{ops::RangeInclusive, str::FromStr};
 
-fn main() {}
+use aoc::Error;
+use parse::{ErrorKind, ParseError, Span};
+
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/input16.txt")?;
+    let (rules, my_ticket, nearby_tickets) = load_data(&input)?;
+    println!("Part 1: {}", part1(&rules, &nearby_tickets));
+    println!("Part 2: {}", part2(&rules, &my_ticket, &nearby_tickets)?);
+    Ok(())
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Rule<T: PartialOrd> {
@@ -15,25 +20,22 @@ struct Rule<T: PartialOrd> {
     ranges: Vec<RangeInclusive<T>>,
 }
 
-impl<T: FromStr + PartialOrd> From<&str> for Rule<T>
+impl<T: FromStr + PartialOrd> Rule<T>
 where
-    T::Err: Debug,
+    T::Err: Display,
 {
-    fn from(s: &str) -> Self {
-        let mut name_range_split = s.split(": ");
-        let name = name_range_split.next().expect("Rule string not okay");
-        let ranges_str = name_range_split.next().expect("Rule string not okay");
+    /// A rule like `class: 1-3 or 5-7`.
+    fn parse(s: Span) -> Result<Self, ParseError> {
+        let (name, ranges_str) = s.split_once(": ")?;
         let mut ranges: Vec<RangeInclusive<T>> = Vec::new();
         for range in ranges_str.split(" or ") {
-            let mut bounds = range.split('-');
-            let start: T = bounds.next().unwrap().parse().unwrap();
-            let end: T = bounds.next().unwrap().parse().unwrap();
-            ranges.push(start..=end);
+            let (start, end) = range.split_once("-")?;
+            ranges.push(start.parse()?..=end.parse()?);
         }
-        Rule {
-            name: name.to_string(),
+        Ok(Rule {
+            name: name.as_str().to_string(),
             ranges,
-        }
+        })
     }
 }
 
@@ -48,47 +50,47 @@ impl<T: PartialOrd> Rule<T> {
     }
 }
 
-fn load_data() -> (Vec<Rule<usize>>, Vec<u64>, Vec<Vec<u64>>) {
-    let f = File::open("res/input16.txt").expect("Couldn't open day 16 input");
-    let reader = BufReader::new(f);
-    let mut line_iter = reader.lines();
+type Ticket = Vec<u64>;
+type Notes = (Vec<Rule<usize>>, Ticket, Vec<Ticket>);
 
-    let mut rules: Vec<Rule<usize>> = Vec::new();
-    for line in line_iter.by_ref() {
-        let s = line.expect("Couldn't read line from day 16 input");
-        if s.is_empty() {
-            break;
-        }
-        rules.push(Rule::from(s.as_str()));
-    }
-    let mut line_iter = line_iter.skip(1);
-    let my_ticket: Vec<u64> = line_iter
-        .next()
-        .unwrap()
-        .unwrap()
-        .split(',')
-        .map(|s| s.parse().unwrap())
-        .collect();
-    let line_iter = line_iter.skip(2);
-    let nearby_tickets: Vec<Vec<u64>> = line_iter
+/// The rules, my ticket and the nearby tickets, in three blocks separated
+/// by blank lines.
+fn load_data(input: &str) -> Result<Notes, ParseError> {
+    let input = Span::new(input);
+    let mut blocks = input.blocks();
+    let mut block = || blocks.next().ok_or_else(|| input.expected("three blocks"));
+    let rules = block()?
+        .lines()
+        .map(|line| Rule::parse(line.trim()))
+        .collect::<Result<_, _>>()?;
+    let my_ticket_block = block()?;
+    let (_, my_ticket) = my_ticket_block.split_once("your ticket:")?;
+    let my_ticket: Ticket = my_ticket.trim().parse_list(",")?;
+    let (_, nearby) = block()?.split_once("nearby tickets:")?;
+    let nearby_tickets = nearby
+        .trim()
+        .lines()
         .map(|line| {
-            line.expect("Couldn't read line from day 16 input")
-                .split(',')
-                .map(|s| s.parse().unwrap())
-                .collect()
+            let ticket: Ticket = line.parse_list(",")?;
+            if ticket.len() != my_ticket.len() {
+                return Err(line.error(ErrorKind::WrongCount {
+                    expected: my_ticket.len(),
+                    found: ticket.len(),
+                }));
+            }
+            Ok(ticket)
         })
-        .collect();
-    (rules, my_ticket, nearby_tickets)
+        .collect::<Result<_, _>>()?;
+    Ok((rules, my_ticket, nearby_tickets))
 }
 
-pub fn part1() -> u64 {
-    let (rules, _, nearby_tickets) = load_data();
+fn part1(rules: &[Rule<usize>], nearby_tickets: &[Ticket]) -> u64 {
     let mut invalid_tickets: Vec<Vec<u64>> = Vec::new();
     for ticket in nearby_tickets {
         let mut invalid_values: Vec<u64> = Vec::new();
-        for val in ticket {
+        for &val in ticket {
             let mut follows = false;
-            for rule in &rules {
+            for rule in rules {
                 if rule.follows(val as usize) {
                     follows = true;
                     break;
@@ -107,8 +109,7 @@ pub fn part1() -> u64 {
         .fold(0, |acc, x| acc + x.iter().sum::<u64>())
 }
 
-pub fn part2() -> u64 {
-    let (rules, my_ticket, nearby_tickets) = load_data();
+fn part2(rules: &[Rule<usize>], my_ticket: &[u64], nearby_tickets: &[Ticket]) -> aoc::Result<u64> {
     let mut place_rules: Vec<HashSet<Rule<usize>>> =
         vec![rules.iter().cloned().collect(); my_ticket.len()];
     let mut valid_tickets: Vec<usize> = Vec::new();
@@ -117,7 +118,7 @@ pub fn part2() -> u64 {
         let mut invalid = false;
         for val in ticket {
             let mut follows = false;
-            for rule in &rules {
+            for rule in rules {
                 if rule.follows(*val as usize) {
                     follows = true;
                     break;
@@ -135,7 +136,7 @@ pub fn part2() -> u64 {
         let mut this_ticket_rules: Vec<HashSet<Rule<usize>>> =
             vec![HashSet::new(); my_ticket.len()];
         for (i, val) in nearby_tickets[idx].iter().enumerate() {
-            for rule in &rules {
+            for rule in rules {
                 if rule.follows(*val as usize) {
                     this_ticket_rules[i].insert(rule.clone());
                 }
@@ -149,14 +150,19 @@ pub fn part2() -> u64 {
         }
     }
     let mut final_rules: Vec<(usize, HashSet<Rule<usize>>)> = Vec::new();
-    while place_rules.iter().any(|set| !set.is_empty()) {
-        let single_rule = place_rules
-            .iter()
-            .cloned()
-            .enumerate()
-            .filter(|(_, set)| !set.is_empty())
-            .min_by_key(|(_, set)| set.len())
-            .unwrap();
+    while let Some(single_rule) = place_rules
+        .iter()
+        .cloned()
+        .enumerate()
+        .filter(|(_, set)| !set.is_empty())
+        .min_by_key(|(_, set)| set.len())
+    {
+        if single_rule.1.len() > 1 {
+            return Err(Error::no_solution(format!(
+                "field {} matches more than one rule",
+                single_rule.0
+            )));
+        }
         final_rules.push(single_rule.clone());
         for set in place_rules.iter_mut() {
             *set = set.difference(&single_rule.1).cloned().collect();
@@ -164,10 +170,47 @@ pub fn part2() -> u64 {
     }
     let mut prod: u64 = 1;
     for (idx, ruleset) in final_rules {
-        let rule = ruleset.iter().next().unwrap();
-        if rule.name.starts_with("departure") {
+        if ruleset
+            .iter()
+            .any(|rule| rule.name.starts_with("departure"))
+        {
             prod *= my_ticket[idx];
         }
     }
-    prod
+    Ok(prod)
+}
+
+#[test]
+fn example_tickets() {
+    let (rules, my_ticket, nearby_tickets) = load_data(
+        "class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12
+",
+    )
+    .unwrap();
+    assert_eq!(rules[1].ranges, [6..=11, 33..=44]);
+    assert_eq!(my_ticket, [7, 1, 14]);
+    assert_eq!(part1(&rules, &nearby_tickets), 71);
+    assert_eq!(part2(&rules, &my_ticket, &nearby_tickets).unwrap(), 1);
+    let error = load_data("class: 1-3\n\nyour ticket:\n7,1\n\nnearby tickets:\n7\n").unwrap_err();
+    assert_eq!(
+        (error.line, error.kind),
+        (
+            7,
+            ErrorKind::WrongCount {
+                expected: 2,
+                found: 1
+            }
+        )
+    );
 }
@@ -1,79 +1,47 @@
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
 
-fn main() {}
+use geometry::{Adjacent, Automaton, SparseGrid};
+use parse::{ParseError, Span};
 
-fn load_data() -> Vec<Vec<u8>> {
-    let f = File::open("res/input17.txt").expect("Couldn't open day 17 input");
-    let reader = BufReader::new(f);
-
-    reader
-        .lines()
-        .map(|line| {
-            line.expect("Couldn't read line from day 17 input")
-                .chars()
-                .map(|ch| {
-                    if ch == '#' {
-                        1
-                    } else if ch == '.' {
-                        0
-                    } else {
-                        unreachable!()
-                    }
-                })
-                .collect()
-        })
-        .collect()
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/input17.txt")?;
+    println!("Part 1: {}", part1(&input)?);
+    println!("Part 2: {}", part2(&input)?);
+    Ok(())
 }
 
-fn sparse_cube_from_grid(grid: &[Vec<u8>]) -> HashMap<(isize, isize, isize), u8> {
-    let mut sprs_cube: HashMap<(isize, isize, isize), u8> = HashMap::new();
-    for i in 0..grid.len() {
-        for j in 0..grid.len() {
-            if grid[i][j] == 1 {
-                sprs_cube.insert((i as isize, j as isize, 0), 1);
-            }
-        }
+/// Active cubes of the starting slice, every coordinate past the first two
+/// being zero.
+fn sparse_cube_from_str<const D: usize>(s: &str) -> Result<SparseGrid<bool, D>, ParseError> {
+    let grid = Span::new(s).grid(|ch| match ch {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    let mut sprs_cube = SparseGrid::new(false);
+    for (cell, _) in grid.indexed().filter(|&(_, &active)| active) {
+        let mut position = [0; D];
+        position[0] = cell.y;
+        position[1] = cell.x;
+        sprs_cube.insert(position, true);
     }
-    sprs_cube
+    Ok(sprs_cube)
+}
+
+/// Conway cubes: an active cube stays active with 2 or 3 active neighbours,
+/// an inactive one becomes active with exactly 3.
+fn conway_rule(&active: &bool, neighbors: &[&bool]) -> bool {
+    let active_neighbors = neighbors.iter().filter(|&&&neighbor| neighbor).count();
+    active_neighbors == 3 || (active && active_neighbors == 2)
 }
 
-fn sparse_cube_simulate_cycles(
+fn sparse_cube_simulate_cycles<const D: usize>(
     cycles: usize,
-    sprs_cube: &HashMap<(isize, isize, isize), u8>,
-    gridlen: usize,
-) -> HashMap<(isize, isize, isize), u8> {
-    let mut sprs_cube = sprs_cube.clone();
-    for c in 1..=(cycles as isize) {
-        let mut next_cube: HashMap<(isize, isize, isize), u8> = HashMap::new();
-        for k in -c..=c {
-            for i in -c..(gridlen as isize + c) {
-                for j in -c..(gridlen as isize + c) {
-                    let mut neighbors = 0u8;
-                    for kk in (k - 1)..=(k + 1) {
-                        for ii in (i - 1)..=(i + 1) {
-                            for jj in (j - 1)..=(j + 1) {
-                                if (ii == i) && (jj == j) && (kk == k) {
-                                    continue;
-                                }
-                                neighbors += sprs_cube.get(&(ii, jj, kk)).unwrap_or(&0);
-                            }
-                        }
-                    }
-                    if sprs_cube.get(&(i, j, k)).is_some() {
-                        if neighbors == 2 || neighbors == 3 {
-                            next_cube.insert((i, j, k), 1);
-                        }
-                    } else if neighbors == 3 {
-                        next_cube.insert((i, j, k), 1);
-                    }
-                }
-            }
-        }
-        sprs_cube = next_cube.clone();
-    }
-    sprs_cube
+    sprs_cube: &SparseGrid<bool, D>,
+) -> SparseGrid<bool, D> {
+    let mut automaton = Automaton::new(sprs_cube.clone(), Adjacent, conway_rule);
+    automaton.run(cycles);
+    automaton.into_lattice()
 }
 
 // fn cube_from_grid(cycles: usize, grid: &[Vec<u8>]) -> Vec<Vec<Vec<u8>>> {
@@ -129,76 +97,20 @@ fn sparse_cube_simulate_cycles(
 //     cube
 // }
 
-pub fn part1() -> usize {
-    let grid = load_data();
+fn part1(input: &str) -> Result<usize, ParseError> {
     // let cube = cube_simulate_cycles(6, &grid);
     // cube.iter().fold(0, |acc, x| {
     //     acc + x.iter().fold(0, |accx, y| accx + y.iter().sum::<u8>())
     // })
-    let sprs_cube = sparse_cube_from_grid(&grid);
-    let sprs_cube = sparse_cube_simulate_cycles(6, &sprs_cube, grid.len());
-    sprs_cube.len()
+    let sprs_cube = sparse_cube_from_str::<3>(input)?;
+    let sprs_cube = sparse_cube_simulate_cycles(6, &sprs_cube);
+    Ok(sprs_cube.len())
 }
 
-fn sparse_hypercube_from_grid(grid: &[Vec<u8>]) -> HashMap<(isize, isize, isize, isize), u8> {
-    let mut sprs_cube: HashMap<(isize, isize, isize, isize), u8> = HashMap::new();
-    for i in 0..grid.len() {
-        for j in 0..grid.len() {
-            if grid[i][j] == 1 {
-                sprs_cube.insert((i as isize, j as isize, 0, 0), 1);
-            }
-        }
-    }
-    sprs_cube
-}
-
-fn sparse_hypercube_simulate_cycles(
-    cycles: usize,
-    sprs_hcube: &HashMap<(isize, isize, isize, isize), u8>,
-    gridlen: usize,
-) -> HashMap<(isize, isize, isize, isize), u8> {
-    let mut sprs_hcube = sprs_hcube.clone();
-    for c in 1..=(cycles as isize) {
-        let mut next_hcube: HashMap<(isize, isize, isize, isize), u8> = HashMap::new();
-        for l in -c..=c {
-            for k in -c..=c {
-                for i in -c..(gridlen as isize + c) {
-                    for j in -c..(gridlen as isize + c) {
-                        let mut neighbors = 0u8;
-                        for ll in (l - 1)..=(l + 1) {
-                            for kk in (k - 1)..=(k + 1) {
-                                for ii in (i - 1)..=(i + 1) {
-                                    for jj in (j - 1)..=(j + 1) {
-                                        if (ii == i) && (jj == j) && (kk == k) && (ll == l) {
-                                            continue;
-                                        }
-                                        neighbors +=
-                                            sprs_hcube.get(&(ii, jj, kk, ll)).unwrap_or(&0);
-                                    }
-                                }
-                            }
-                        }
-                        if sprs_hcube.get(&(i, j, k, l)).is_none() {
-                            if neighbors == 2 || neighbors == 3 {
-                                next_hcube.insert((i, j, k, l), 1);
-                            }
-                        } else if neighbors == 3 {
-                            next_hcube.insert((i, j, k, l), 1);
-                        }
-                    }
-                }
-            }
-        }
-        sprs_hcube = next_hcube.clone();
-    }
-    sprs_hcube
-}
-
-pub fn part2() -> usize {
-    let grid = load_data();
-    let sprs_hcube = sparse_hypercube_from_grid(&grid);
-    let sprs_hcube = sparse_hypercube_simulate_cycles(6, &sprs_hcube, grid.len());
-    sprs_hcube.len()
+fn part2(input: &str) -> Result<usize, ParseError> {
+    let sprs_hcube = sparse_cube_from_str::<4>(input)?;
+    let sprs_hcube = sparse_cube_simulate_cycles(6, &sprs_hcube);
+    Ok(sprs_hcube.len())
 }
 
 #[cfg(test)]
@@ -243,24 +155,18 @@ mod test {
     #[test]
     fn post_sparse_sim_count() {
         let s = String::from(".#.\n..#\n###");
-        let grid: Vec<Vec<u8>> = s
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|ch| {
-                        if ch == '#' {
-                            1
-                        } else if ch == '.' {
-                            0
-                        } else {
-                            unreachable!()
-                        }
-                    })
-                    .collect()
-            })
-            .collect();
-        let sprs_cube = sparse_cube_from_grid(&grid);
-        let sprs_cube = sparse_cube_simulate_cycles(1, &sprs_cube, grid.len());
+        let sprs_cube = sparse_cube_from_str::<3>(&s).unwrap();
+        let sprs_cube = sparse_cube_simulate_cycles(1, &sprs_cube);
         assert_eq!(sprs_cube.len(), 11);
+        assert_eq!(part1(&s), Ok(112));
+        assert_eq!(part1(".#.\n.x#").unwrap_err().column, 2);
+    }
+
+    #[test]
+    fn post_sparse_hypercube_sim_count() {
+        let s = String::from(".#.\n..#\n###");
+        let sprs_hcube = sparse_cube_from_str::<4>(&s).unwrap();
+        assert_eq!(sparse_cube_simulate_cycles(1, &sprs_hcube).len(), 29);
+        assert_eq!(sparse_cube_simulate_cycles(6, &sprs_hcube).len(), 848);
     }
 }
@@ -1,193 +1,162 @@
-use parsing::{
-    self, char_parse_builder, choose, combination, left, map, right, whitespace_wrap, whole_number,
-    zero_or_more, ParseResult, Parser,
-};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs::read_to_string;
+use std::num::ParseIntError;
 
-fn main() {}
+use parse::{ErrorKind, ParseError, Span};
 
-fn load_expressions() -> Vec<String> {
-    let f = File::open("res/input18.txt").expect("Couldn't open day 18 input");
-    let reader = BufReader::new(f);
-
-    reader
-        .lines()
-        .map(|line| line.expect("Couldn't read line from day 18 input"))
-        .collect()
-}
-
-fn open_bracket(s: &str) -> ParseResult<char> {
-    whitespace_wrap(char_parse_builder('(')).parse(s)
-}
-
-fn close_bracket(s: &str) -> ParseResult<char> {
-    whitespace_wrap(char_parse_builder(')')).parse(s)
+fn main() -> aoc::Result<()> {
+    let input = read_to_string("res/input18.txt")?;
+    println!("Part 1: {}", sum_of_lines(&input, same_precedence)?);
+    println!("Part 2: {}", sum_of_lines(&input, addition_first)?);
+    Ok(())
 }
 
-fn plus_sign(s: &str) -> ParseResult<char> {
-    whitespace_wrap(char_parse_builder('+')).parse(s)
+/// Evaluates one line of homework left to right, except that operators
+/// with a higher `precedence` bind tighter.
+struct Evaluator<'a> {
+    rest: Span<'a>,
+    precedence: fn(char) -> u8,
 }
 
-fn times_sign(s: &str) -> ParseResult<char> {
-    whitespace_wrap(char_parse_builder('*')).parse(s)
-}
-
-fn bracketed_expr_p1(s: &str) -> ParseResult<u64> {
-    right(open_bracket, left(expression_p1, close_bracket)).parse(s)
-}
-
-fn binary_operator(s: &str) -> ParseResult<char> {
-    choose(plus_sign, times_sign).parse(s)
-}
+impl Evaluator<'_> {
+    /// The next character that isn't a space, without consuming it.
+    fn peek(&mut self) -> Option<char> {
+        while let Some((' ', after)) = self.rest.split_first() {
+            self.rest = after;
+        }
+        self.rest.split_first().map(|(ch, _)| ch)
+    }
 
-fn p_term_p1(s: &str) -> ParseResult<u64> {
-    choose(map(whole_number, |n| Some(n as u64)), bracketed_expr_p1).parse(s)
-}
+    fn advance(&mut self) {
+        if let Some((_, after)) = self.rest.split_first() {
+            self.rest = after;
+        }
+    }
 
-fn expression_p1(s: &str) -> ParseResult<u64> {
-    map(
-        combination(
-            p_term_p1,
-            zero_or_more(combination(binary_operator, p_term_p1)),
-        ),
-        |(a, b)| {
-            let mut acc = a as u64;
-            for (op, n) in b {
-                if op == '+' {
-                    acc += n as u64;
-                } else if op == '*' {
-                    acc *= n as u64;
+    fn operand(&mut self) -> Result<u64, ParseError> {
+        match self.peek() {
+            Some('(') => {
+                self.advance();
+                let value = self.expression(0)?;
+                if self.peek() != Some(')') {
+                    return Err(self.rest.expected("')'"));
                 }
+                self.advance();
+                Ok(value)
             }
-            Some(acc)
-        },
-    )
-    .parse(s)
-}
-
-pub fn part1() -> u64 {
-    let exprs = load_expressions();
-    exprs
-        .iter()
-        .map(|exp| expression_p1(exp).unwrap().0 as u64)
-        .sum()
-}
+            Some('0'..='9') => {
+                let start = self.rest;
+                let mut digits = String::new();
+                while let Some((digit @ '0'..='9', after)) = self.rest.split_first() {
+                    digits.push(digit);
+                    self.rest = after;
+                }
+                digits.parse().map_err(|err: ParseIntError| {
+                    start.error(ErrorKind::InvalidValue {
+                        found: digits.clone(),
+                        reason: err.to_string(),
+                    })
+                })
+            }
+            _ => Err(self.rest.expected("a number or '('")),
+        }
+    }
 
-fn bracketed_expr_p2(s: &str) -> ParseResult<u64> {
-    right(open_bracket, left(expression_p2, close_bracket)).parse(s)
+    fn expression(&mut self, min_precedence: u8) -> Result<u64, ParseError> {
+        let mut value = self.operand()?;
+        while let Some(operator @ ('+' | '*')) = self.peek() {
+            let precedence = (self.precedence)(operator);
+            if precedence < min_precedence {
+                break;
+            }
+            let at = self.rest;
+            self.advance();
+            let rhs = self.expression(precedence + 1)?;
+            let result = match operator {
+                '+' => value.checked_add(rhs),
+                _ => value.checked_mul(rhs),
+            };
+            value = result.ok_or_else(|| {
+                at.error(ErrorKind::Custom("result does not fit in 64 bits".into()))
+            })?;
+        }
+        Ok(value)
+    }
 }
 
-fn p_term_p2(s: &str) -> ParseResult<u64> {
-    choose(map(whole_number, |n| Some(n as u64)), bracketed_expr_p2).parse(s)
+fn evaluate(line: Span, precedence: fn(char) -> u8) -> Result<u64, ParseError> {
+    let mut evaluator = Evaluator {
+        rest: line,
+        precedence,
+    };
+    let value = evaluator.expression(0)?;
+    if evaluator.peek().is_some() {
+        return Err(evaluator.rest.expected("'+', '*' or the end of the line"));
+    }
+    Ok(value)
 }
 
-fn precedence(op1: char, _op2: char) -> bool {
-    op1 == '+'
+fn same_precedence(_operator: char) -> u8 {
+    0
 }
 
-fn binop(op: char) -> impl Fn(u64, u64) -> u64 {
-    if op == '+' {
-        |a, b| a + b
-    } else if op == '*' {
-        |a, b| a * b
-    } else {
-        unreachable!()
+fn addition_first(operator: char) -> u8 {
+    match operator {
+        '+' => 1,
+        _ => 0,
     }
 }
 
-fn expression_p2(s: &str) -> ParseResult<u64> {
-    map(
-        combination(
-            p_term_p2,
-            zero_or_more(combination(binary_operator, p_term_p2)),
-        ),
-        |(a, b)| {
-            let mut oprt_stack: Vec<char> = Vec::new();
-            let mut oprn_stack: Vec<u64> = Vec::new();
-            oprn_stack.push(a);
-            for (op, n) in b {
-                let mut pushed = false;
-                while let Some(top_op) = oprt_stack.pop() {
-                    if precedence(op, top_op) {
-                        oprt_stack.push(top_op);
-                        oprt_stack.push(op);
-                        oprn_stack.push(n);
-                        pushed = true;
-                        break;
-                    } else {
-                        let oprn2 = oprn_stack.pop().unwrap();
-                        let oprn1 = oprn_stack.pop().unwrap();
-                        let res = binop(top_op)(oprn1, oprn2);
-                        oprn_stack.push(res);
-                    }
-                }
-                if !pushed {
-                    oprt_stack.push(op);
-                    oprn_stack.push(n);
-                }
-            }
-            while let Some(op) = oprt_stack.pop() {
-                let oprn2 = oprn_stack.pop().unwrap();
-                let oprn1 = oprn_stack.pop().unwrap();
-                let res = binop(op)(oprn1, oprn2);
-                oprn_stack.push(res);
-            }
-            Some(oprn_stack.pop().unwrap())
-        },
-    )
-    .parse(s)
-}
-
-pub fn part2() -> u64 {
-    let exprs = load_expressions();
-    exprs
-        .iter()
-        .map(|exp| expression_p2(exp).unwrap().0 as u64)
+fn sum_of_lines(input: &str, precedence: fn(char) -> u8) -> Result<u64, ParseError> {
+    Span::new(input)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| evaluate(line, precedence))
         .sum()
 }
 
 #[test]
 fn expression_evaluates_p1() {
-    assert_eq!(Ok((71, "")), expression_p1.parse("1 + 2 * 3 + 4 * 5 + 6"));
+    let evaluate = |line| evaluate(Span::new(line), same_precedence);
+    assert_eq!(evaluate("1 + 2 * 3 + 4 * 5 + 6"), Ok(71));
+    assert_eq!(evaluate("1 + (2 * 3) + (4 * (5 + 6))"), Ok(51));
+    assert_eq!(evaluate("2 * 3 + (4 * 5)"), Ok(26));
+    assert_eq!(evaluate("5 + (8 * 3 + 9 + 3 * 4 * 3)"), Ok(437));
     assert_eq!(
-        Ok((51, "")),
-        expression_p1.parse("1 + (2 * 3) + (4 * (5 + 6))")
+        evaluate("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))"),
+        Ok(12240)
     );
-    assert_eq!(Ok((26, "")), expression_p1.parse("2 * 3 + (4 * 5)"));
     assert_eq!(
-        Ok((437, "")),
-        expression_p1.parse("5 + (8 * 3 + 9 + 3 * 4 * 3)")
-    );
-    assert_eq!(
-        Ok((12240, "")),
-        expression_p1.parse("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))")
-    );
-    assert_eq!(
-        Ok((13632, "")),
-        expression_p1.parse("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2")
+        evaluate("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"),
+        Ok(13632)
     );
 }
 
 #[test]
 fn expression_evaluates_p2() {
-    assert_eq!(Ok((12, "")), expression_p2("3 * 2 + 2"));
-    assert_eq!(Ok((231, "")), expression_p2.parse("1 + 2 * 3 + 4 * 5 + 6"));
-    assert_eq!(
-        Ok((51, "")),
-        expression_p2.parse("1 + (2 * 3) + (4 * (5 + 6))")
-    );
-    assert_eq!(Ok((46, "")), expression_p2.parse("2 * 3 + (4 * 5)"));
+    let evaluate = |line| evaluate(Span::new(line), addition_first);
+    assert_eq!(evaluate("3 * 2 + 2"), Ok(12));
+    assert_eq!(evaluate("1 + 2 * 3 + 4 * 5 + 6"), Ok(231));
+    assert_eq!(evaluate("1 + (2 * 3) + (4 * (5 + 6))"), Ok(51));
+    assert_eq!(evaluate("2 * 3 + (4 * 5)"), Ok(46));
+    assert_eq!(evaluate("5 + (8 * 3 + 9 + 3 * 4 * 3)"), Ok(1445));
     assert_eq!(
-        Ok((1445, "")),
-        expression_p2.parse("5 + (8 * 3 + 9 + 3 * 4 * 3)")
+        evaluate("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))"),
+        Ok(669060)
     );
     assert_eq!(
-        Ok((669060, "")),
-        expression_p2.parse("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))")
-    );
-    assert_eq!(
-        Ok((23340, "")),
-        expression_p2.parse("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2")
+        evaluate("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"),
+        Ok(23340)
     );
 }
+
+#[test]
+fn malformed_expression() {
+    let error = |line| evaluate(Span::new(line), same_precedence).unwrap_err();
+    assert_eq!(error("1 + (2 * 3").column, 11);
+    assert_eq!(error("1 + * 2").column, 5);
+    assert_eq!(error("1 2").column, 3);
+    assert!(matches!(
+        error("99999999999 * 99999999999").kind,
+        ErrorKind::Custom(_)
+    ));
+}
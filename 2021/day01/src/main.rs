@@ -1,25 +1,13 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    time::Instant,
-};
+use std::time::Instant;
 
-fn main() -> Result<(), String> {
-    let mut args = std::env::args();
-    let f;
-    if let Some(fpath) = args.nth(1) {
-        let base_path = std::env::current_dir()
-            .map_err(|_| "could not resolve current directory".to_string())?;
-        f = File::open(base_path.join(&fpath))
-            .map_err(|_| format!(r#"could not open file "{fpath}""#))?;
-    } else {
-        return Err("incorrect number of arguments".into());
-    }
-    let reader = BufReader::new(f);
-    let depths: Vec<i64> = reader
+use parse::Span;
+
+fn main() -> aoc::Result<()> {
+    let input = aoc::read_input_arg()?;
+    let depths: Vec<i64> = Span::new(&input)
         .lines()
-        .map(|line| line.unwrap().parse().unwrap())
-        .collect();
+        .map(|line| line.parse())
+        .collect::<Result<_, _>>()?;
     println!("Day 01");
 
     let start_time = Instant::now();
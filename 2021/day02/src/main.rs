@@ -1,41 +1,29 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    time::Instant,
-};
+use std::time::Instant;
+
+use parse::{ParseError, Span};
 
 enum Direction {
     Horizontal(i64),
     Vertical(i64),
 }
 
-fn main() -> Result<(), String> {
-    let mut args = std::env::args();
-    let f;
-    if let Some(fpath) = args.nth(1) {
-        let base_path = std::env::current_dir()
-            .map_err(|_| "could not resolve current directory".to_string())?;
-        f = File::open(base_path.join(&fpath))
-            .map_err(|_| format!(r#"could not open file "{fpath}""#))?;
-    } else {
-        return Err("incorrect number of arguments".into());
+fn parse_direction(line: Span) -> Result<Direction, ParseError> {
+    let (dir, amount) = line.split_once(" ")?;
+    let amount: i64 = amount.parse()?;
+    match dir.as_str() {
+        "forward" => Ok(Direction::Horizontal(amount)),
+        "up" => Ok(Direction::Vertical(amount)),
+        "down" => Ok(Direction::Vertical(-amount)),
+        _ => Err(dir.expected("forward, up or down")),
     }
-    let reader = BufReader::new(f);
-    let instructions: Vec<Direction> = reader
+}
+
+fn main() -> aoc::Result<()> {
+    let input = aoc::read_input_arg()?;
+    let instructions: Vec<Direction> = Span::new(&input)
         .lines()
-        .map(|line| {
-            let line = line.unwrap();
-            let mut parts = line.split(' ');
-            let dir = parts.next().unwrap();
-            if dir == "forward" {
-                Direction::Horizontal(parts.next().unwrap().parse().unwrap())
-            } else if dir == "up" {
-                Direction::Vertical(parts.next().unwrap().parse().unwrap())
-            } else {
-                Direction::Vertical(-parts.next().unwrap().parse::<i64>().unwrap())
-            }
-        })
-        .collect();
+        .map(parse_direction)
+        .collect::<Result<_, _>>()?;
     println!("Day 02");
 
     let start_time = Instant::now();
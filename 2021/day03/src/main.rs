@@ -1,25 +1,20 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    time::Instant,
-};
+use std::time::Instant;
 
-fn main() -> Result<(), String> {
-    let mut args = std::env::args();
-    let f;
-    if let Some(fpath) = args.nth(1) {
-        let base_path = std::env::current_dir()
-            .map_err(|_| "could not resolve current directory".to_string())?;
-        f = File::open(base_path.join(&fpath))
-            .map_err(|_| format!(r#"could not open file "{fpath}""#))?;
-    } else {
-        return Err("incorrect number of arguments".into());
-    }
-    let reader = BufReader::new(f);
-    let numbers: Vec<_> = reader
+use parse::{ErrorKind, Span};
+
+fn main() -> aoc::Result<()> {
+    let input = aoc::read_input_arg()?;
+    let numbers: Vec<_> = Span::new(&input)
         .lines()
-        .map(|line| u64::from_str_radix(&line.unwrap(), 2).unwrap())
-        .collect();
+        .map(|line| {
+            u64::from_str_radix(line.as_str(), 2).map_err(|err| {
+                line.error(ErrorKind::InvalidValue {
+                    found: line.to_string(),
+                    reason: err.to_string(),
+                })
+            })
+        })
+        .collect::<Result<_, _>>()?;
     println!("Day 03");
 
     let start_time = Instant::now();
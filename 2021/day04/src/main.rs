@@ -1,37 +1,25 @@
-use std::{fs::File, io::Read, time::Instant};
+use std::time::Instant;
 
-type Board = Vec<Vec<u16>>;
+use parse::{ParseError, Span};
 
-fn main() -> Result<(), String> {
-    let mut args = std::env::args();
-    let mut f;
-    if let Some(fpath) = args.nth(1) {
-        let base_path = std::env::current_dir()
-            .map_err(|_| "could not resolve current directory".to_string())?;
-        f = File::open(base_path.join(&fpath))
-            .map_err(|_| format!(r#"could not open file "{fpath}""#))?;
-    } else {
-        return Err("incorrect number of arguments".into());
-    }
-    let mut contents = String::new();
-    f.read_to_string(&mut contents).unwrap();
+type Board = Vec<Vec<u16>>;
 
-    let (draw_seq_s, boards_s) = contents.trim().split_once("\n\n").unwrap();
-    let draw_seq: Vec<u16> = draw_seq_s.split(',').map(|s| s.parse().unwrap()).collect();
+fn main() -> aoc::Result<()> {
+    let input = aoc::read_input_arg()?;
+    let mut blocks = Span::new(&input).blocks();
+    let draw_seq: Vec<u16> = match blocks.next() {
+        Some(draw_seq_s) => draw_seq_s.parse_list(",")?,
+        None => return Err(Span::new(&input).expected("the drawn numbers").into()),
+    };
 
-    let boards: Vec<Board> = boards_s
-        .split("\n\n")
+    let boards: Vec<Board> = blocks
         .map(|board_s| {
             board_s
-                .split('\n')
-                .map(|row| {
-                    row.split_ascii_whitespace()
-                        .map(|num| num.parse().unwrap())
-                        .collect()
-                })
-                .collect()
+                .lines()
+                .map(|row| row.words().map(|num| num.parse()).collect())
+                .collect::<Result<_, ParseError>>()
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
     println!("Day 04");
 
@@ -1,51 +1,30 @@
-use std::{
-    cmp::Ordering,
-    collections::HashMap,
-    fs::File,
-    io::{BufRead, BufReader},
-    time::Instant,
-};
+use std::{collections::HashMap, time::Instant};
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
-struct Point {
-    x: i32,
-    y: i32,
-}
+use geometry::Vector2;
+use parse::{ParseError, Span};
 
 struct Line {
-    p1: Point,
-    p2: Point,
+    p1: Vector2<i32>,
+    p2: Vector2<i32>,
+}
+
+fn parse_point(point: Span) -> Result<Vector2<i32>, ParseError> {
+    let (x, y) = point.split_once(",")?;
+    Ok(Vector2::new(x.parse()?, y.parse()?))
 }
 
-fn main() -> Result<(), String> {
-    let mut args = std::env::args();
-    let f;
-    if let Some(fpath) = args.nth(1) {
-        let base_path = std::env::current_dir()
-            .map_err(|_| "could not resolve current directory".to_string())?;
-        f = File::open(base_path.join(&fpath))
-            .map_err(|_| format!(r#"could not open file "{fpath}""#))?;
-    } else {
-        return Err("incorrect number of arguments".into());
-    }
-    let reader = BufReader::new(f);
-    let lines: Vec<Line> = reader
+fn main() -> aoc::Result<()> {
+    let input = aoc::read_input_arg()?;
+    let lines: Vec<Line> = Span::new(&input)
         .lines()
         .map(|line| {
-            let line = line.unwrap();
-            let mut point_iter = line.split(" -> ").map(|p_str| {
-                let mut coord_iter = p_str.split(',').map(|coord_s| coord_s.parse().unwrap());
-                Point {
-                    x: coord_iter.next().unwrap(),
-                    y: coord_iter.next().unwrap(),
-                }
-            });
-            Line {
-                p1: point_iter.next().unwrap(),
-                p2: point_iter.next().unwrap(),
-            }
+            let (p1, p2) = line.split_once(" -> ")?;
+            Ok(Line {
+                p1: parse_point(p1)?,
+                p2: parse_point(p2)?,
+            })
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
 
     println!("Day 05");
 
@@ -70,29 +49,19 @@ fn main() -> Result<(), String> {
 }
 
 fn solve(lines: &[Line], include_diagonals: bool) -> usize {
-    let mut point_counts: HashMap<Point, bool> = HashMap::new();
+    let mut point_counts: HashMap<Vector2<i32>, bool> = HashMap::new();
     lines
         .iter()
         .filter(|Line { p1, p2 }| include_diagonals || (p1.x == p2.x) || (p1.y == p2.y))
         .for_each(|Line { p1, p2 }| {
-            let x_adder = match p2.x.cmp(&p1.x) {
-                Ordering::Greater => 1,
-                Ordering::Less => -1,
-                Ordering::Equal => 0,
-            };
-            let y_adder = match p2.y.cmp(&p1.y) {
-                Ordering::Greater => 1,
-                Ordering::Less => -1,
-                Ordering::Equal => 0,
-            };
+            let step = (*p2 - *p1).signum();
             let mut cur_point = *p1;
             while cur_point != *p2 {
                 point_counts
                     .entry(cur_point)
                     .and_modify(|multiple| *multiple = true)
                     .or_insert_with(|| false);
-                cur_point.x += x_adder;
-                cur_point.y += y_adder;
+                cur_point += step;
             }
             point_counts
                 .entry(cur_point)
@@ -1,21 +1,12 @@
-use std::{collections::HashMap, fs::File, io::Read, time::Instant};
+use std::{collections::HashMap, time::Instant};
 
-fn main() -> Result<(), String> {
-    let mut args = std::env::args();
-    let mut f;
-    if let Some(fpath) = args.nth(1) {
-        let base_path = std::env::current_dir()
-            .map_err(|_| "could not resolve current directory".to_string())?;
-        f = File::open(base_path.join(&fpath))
-            .map_err(|_| format!(r#"could not open file "{fpath}""#))?;
-    } else {
-        return Err("incorrect number of arguments".into());
-    }
-    let mut ages_s = String::new();
-    f.read_to_string(&mut ages_s).unwrap();
+use parse::Span;
+
+fn main() -> aoc::Result<()> {
+    let input = aoc::read_input_arg()?;
+    let ages: Vec<u8> = Span::new(&input).trim().parse_list(",")?;
     let mut age_map: HashMap<u8, usize> = HashMap::new();
-    ages_s.trim().split(',').for_each(|num| {
-        let age = num.parse().unwrap();
+    ages.into_iter().for_each(|age| {
         age_map
             .entry(age)
             .and_modify(|cnt| *cnt += 1)
@@ -1,20 +1,10 @@
-use std::{fs::File, io::Read, time::Instant};
+use std::time::Instant;
 
-fn main() -> Result<(), String> {
-    let mut args = std::env::args();
-    let mut f;
-    if let Some(fpath) = args.nth(1) {
-        let base_path = std::env::current_dir()
-            .map_err(|_| "could not resolve current directory".to_string())?;
-        f = File::open(base_path.join(&fpath))
-            .map_err(|_| format!(r#"could not open file "{fpath}""#))?;
-    } else {
-        return Err("incorrect number of arguments".into());
-    }
-    let mut buf = String::new();
-    f.read_to_string(&mut buf).unwrap();
+use parse::Span;
 
-    let positions: Vec<i64> = buf.trim().split(',').map(|n| n.parse().unwrap()).collect();
+fn main() -> aoc::Result<()> {
+    let input = aoc::read_input_arg()?;
+    let positions: Vec<i64> = Span::new(&input).trim().parse_list(",")?;
     println!("Day 07");
 
     let start_time = Instant::now();
@@ -1,33 +1,21 @@
 use std::{
     collections::{HashMap, HashSet},
-    fs::File,
-    io::{BufRead, BufReader},
     time::Instant,
 };
 
-fn main() -> Result<(), String> {
-    let mut args = std::env::args();
-    let f;
-    if let Some(fpath) = args.nth(1) {
-        let base_path = std::env::current_dir()
-            .map_err(|_| "could not resolve current directory".to_string())?;
-        f = File::open(base_path.join(&fpath))
-            .map_err(|_| format!(r#"could not open file "{fpath}""#))?;
-    } else {
-        return Err("incorrect number of arguments".into());
-    }
-    let reader = BufReader::new(f);
-    let outputs: Vec<(Vec<String>, Vec<String>)> = reader
+use parse::{ParseError, Span};
+
+fn main() -> aoc::Result<()> {
+    let input = aoc::read_input_arg()?;
+    let outputs: Vec<(Vec<String>, Vec<String>)> = Span::new(&input)
         .lines()
         .map(|line| {
-            let line = line.unwrap();
-            let mut parts = line.split(" | ");
-            let patterns: Vec<String> =
-                parts.next().unwrap().split(' ').map(|s| s.into()).collect();
-            let digits: Vec<String> = parts.next().unwrap().split(' ').map(|s| s.into()).collect();
-            (patterns, digits)
+            let (patterns, digits) = line.split_once(" | ")?;
+            let patterns: Vec<String> = patterns.words().map(|s| s.to_string()).collect();
+            let digits: Vec<String> = digits.words().map(|s| s.to_string()).collect();
+            Ok((patterns, digits))
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
 
     println!("Day 08");
 
@@ -1,25 +1,20 @@
-use std::{fs::File, io::Read, time::Instant};
+use std::time::Instant;
+
+use aoc::Error;
+use parse::Span;
 
 const WIDTH: usize = 100;
 
-fn main() -> Result<(), String> {
-    let mut args = std::env::args();
-    let mut f;
-    if let Some(fpath) = args.nth(1) {
-        let base_path = std::env::current_dir()
-            .map_err(|_| "could not resolve current directory".to_string())?;
-        f = File::open(base_path.join(&fpath))
-            .map_err(|_| format!(r#"could not open file "{fpath}""#))?;
-    } else {
-        return Err("incorrect number of arguments".into());
+fn main() -> aoc::Result<()> {
+    let input = aoc::read_input_arg()?;
+    let heightmap = Span::new(&input).grid(|ch| ch.to_digit(10))?;
+    if heightmap.width() != WIDTH {
+        return Err(Error::invalid_state(format!(
+            "the heightmap is {} wide instead of {WIDTH}",
+            heightmap.width()
+        )));
     }
-    let mut buf = String::new();
-    f.read_to_string(&mut buf).unwrap();
-    let heightmap: Vec<u32> = buf
-        .trim()
-        .chars()
-        .filter_map(|ch| ch.to_digit(10))
-        .collect();
+    let heightmap: Vec<u32> = heightmap.iter().copied().collect();
     println!("Day 09");
     let start_time = Instant::now();
     let (p1, low_points) = part1(&heightmap);
@@ -1,23 +1,8 @@
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufRead, BufReader},
-    time::Instant,
-};
+use std::{collections::HashMap, time::Instant};
 
-fn main() -> Result<(), String> {
-    let mut args = std::env::args();
-    let f;
-    if let Some(fpath) = args.nth(1) {
-        let base_path = std::env::current_dir()
-            .map_err(|_| "could not resolve current directory".to_string())?;
-        f = File::open(base_path.join(&fpath))
-            .map_err(|_| format!(r#"could not open file "{fpath}""#))?;
-    } else {
-        return Err("incorrect number of arguments".into());
-    }
-    let reader = BufReader::new(f);
-    let nav_subsystem: Vec<String> = reader.lines().collect::<Result<_, _>>().unwrap();
+fn main() -> aoc::Result<()> {
+    let input = aoc::read_input_arg()?;
+    let nav_subsystem: Vec<String> = input.lines().map(|line| line.to_string()).collect();
 
     println!("Day 10");
 
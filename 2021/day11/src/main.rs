@@ -1,8 +1,7 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    time::Instant,
-};
+use std::time::Instant;
+
+use geometry::{Adjacent, Automaton, Grid};
+use parse::Span;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 struct Octopus {
@@ -33,43 +32,14 @@ enum Light {
     Dim,
 }
 
-fn main() -> Result<(), String> {
-    let mut args = std::env::args();
-    let f;
-    if let Some(fpath) = args.nth(1) {
-        let base_path = std::env::current_dir()
-            .map_err(|_| "could not resolve current directory".to_string())?;
-        f = File::open(base_path.join(&fpath))
-            .map_err(|_| format!(r#"could not open file "{fpath}""#))?;
-    } else {
-        return Err("incorrect number of arguments".into());
-    }
-    let reader = BufReader::new(f);
-    let mut octopuses = Vec::new();
-    let mut lines = reader.lines().peekable();
-    let width = lines
-        .peek()
-        .ok_or_else(|| "no line in file".to_string())?
-        .as_ref()
-        .map_err(|_| "could not read line from file".to_string())?
-        .len();
-
-    for line in lines {
-        for ch in line
-            .map_err(|_| "could not read line from file".to_string())?
-            .chars()
-        {
-            let energy = ch
-                .to_digit(10)
-                .ok_or_else(|| "character in file not a digit".to_string())?;
-            octopuses.push(Octopus::new(energy));
-        }
-    }
+fn main() -> aoc::Result<()> {
+    let input = aoc::read_input_arg()?;
+    let octopuses = Span::new(&input).grid(|ch| ch.to_digit(10).map(Octopus::new))?;
 
     println!("Day 11");
 
     let start_time = Instant::now();
-    let total_flashes = part1(octopuses.clone(), width);
+    let total_flashes = part1(octopuses.clone());
     let duration = start_time.elapsed();
 
     println!(
@@ -79,7 +49,7 @@ fn main() -> Result<(), String> {
     );
 
     let start_time = Instant::now();
-    let sync_step = part2(octopuses.clone(), width);
+    let sync_step = part2(octopuses);
     let duration = start_time.elapsed();
 
     println!(
@@ -90,79 +60,50 @@ fn main() -> Result<(), String> {
     Ok(())
 }
 
-fn part1(mut octopuses: Vec<Octopus>, width: usize) -> usize {
+fn part1(mut octopuses: Grid<Octopus>) -> usize {
     let mut total_flashes = 0;
     for _ in 0..100 {
-        total_flashes += octopuses_life_step(&mut octopuses, width);
+        total_flashes += octopuses_life_step(&mut octopuses);
     }
     total_flashes
 }
 
-fn part2(mut octopuses: Vec<Octopus>, width: usize) -> usize {
+fn part2(mut octopuses: Grid<Octopus>) -> usize {
     let mut step = 0;
     loop {
         step += 1;
-        octopuses_life_step(&mut octopuses, width);
+        octopuses_life_step(&mut octopuses);
         if octopuses.iter().all(|oct| oct.energy == 0) {
             break step;
         }
     }
 }
 
-fn octopuses_life_step(octopuses: &mut Vec<Octopus>, width: usize) -> usize {
+/// One round of the flash cascade: octopuses ready to flash do so, and every
+/// octopus gains one energy per neighbour flashing this round.
+fn flash_rule(octopus: &Octopus, neighbors: &[&Octopus]) -> Octopus {
+    let flashing_neighbors = neighbors.iter().filter(|oct| oct.ready_to_flash()).count();
+    Octopus {
+        energy: octopus.energy + flashing_neighbors as u32,
+        brightness: if octopus.ready_to_flash() {
+            Light::Flash
+        } else {
+            octopus.brightness
+        },
+    }
+}
+
+fn octopuses_life_step(octopuses: &mut Grid<Octopus>) -> usize {
     octopuses.iter_mut().for_each(|oct| oct.energy += 1);
+    let mut cascade = Automaton::new(octopuses.clone(), Adjacent, flash_rule);
+    cascade.run_to_fixpoint();
+    *octopuses = cascade.into_lattice();
     let mut total_flashes = 0;
-    loop {
-        let mut octopus_flashed = false;
-        for i in 0..octopuses.len() {
-            if octopuses[i].ready_to_flash() {
-                octopuses[i].brightness = Light::Flash;
-                octopus_flashed = true;
-                total_flashes += 1;
-                let column = i % width;
-                let row = i / width;
-                if row != 0 {
-                    // top
-                    octopuses[i - width].energy += 1;
-                    if column != 0 {
-                        // top left
-                        octopuses[i - width - 1].energy += 1;
-                    }
-                    if column + 1 != width {
-                        // top right
-                        octopuses[i + 1 - width].energy += 1;
-                    }
-                }
-                if column != 0 {
-                    // left
-                    octopuses[i - 1].energy += 1;
-                    if row != octopuses.len() / width - 1 {
-                        // bottom left
-                        octopuses[i + width - 1].energy += 1;
-                    }
-                }
-                if column + 1 != width {
-                    // right
-                    octopuses[i + 1].energy += 1;
-                    if row != octopuses.len() / width - 1 {
-                        // bottom right
-                        octopuses[i + width + 1].energy += 1;
-                    }
-                }
-                if row != octopuses.len() / width - 1 {
-                    // bottom
-                    octopuses[i + width].energy += 1;
-                }
-            }
-        }
-        if !octopus_flashed {
-            break;
-        }
-    }
     for oct in octopuses.iter_mut() {
         if oct.flashed() {
             oct.brightness = Light::Dim;
             oct.energy = 0;
+            total_flashes += 1;
         }
     }
     total_flashes
@@ -173,7 +114,7 @@ fn life_step_test() {
     let energies = vec![
         1u32, 1, 1, 1, 1, 1, 9, 9, 9, 1, 1, 9, 1, 9, 1, 1, 9, 9, 9, 1, 1, 1, 1, 1, 1,
     ];
-    let mut octopuses: Vec<Octopus> = energies.iter().map(|en| Octopus::new(*en)).collect();
+    let octopuses: Vec<Octopus> = energies.iter().map(|en| Octopus::new(*en)).collect();
 
     let first_step_energies = vec![
         3u32, 4, 5, 4, 3, 4, 0, 0, 0, 4, 5, 0, 0, 0, 5, 4, 0, 0, 0, 4, 3, 4, 5, 4, 3,
@@ -182,14 +123,14 @@ fn life_step_test() {
     let second_step_energies = vec![
         4u32, 5, 6, 5, 4, 5, 1, 1, 1, 5, 6, 1, 1, 1, 6, 5, 1, 1, 1, 5, 4, 5, 6, 5, 4,
     ];
-    let width = 5;
-    octopuses_life_step(&mut octopuses, width);
-    for i in 0..energies.len() {
-        assert_eq!(first_step_energies[i], octopuses[i].energy);
+    let mut octopuses = Grid::new(5, 5, octopuses).unwrap();
+    octopuses_life_step(&mut octopuses);
+    for (oct, energy) in octopuses.iter().zip(first_step_energies) {
+        assert_eq!(energy, oct.energy);
     }
-    octopuses_life_step(&mut octopuses, width);
-    for i in 0..energies.len() {
-        assert_eq!(second_step_energies[i], octopuses[i].energy);
+    octopuses_life_step(&mut octopuses);
+    for (oct, energy) in octopuses.iter().zip(second_step_energies) {
+        assert_eq!(energy, oct.energy);
     }
 }
 
@@ -201,7 +142,7 @@ fn life_step_test2() {
         5, 2, 1, 7, 6, 8, 4, 1, 7, 2, 1, 6, 8, 8, 2, 8, 8, 1, 1, 3, 4, 4, 8, 4, 6, 8, 4, 8, 5, 5,
         4, 5, 2, 8, 3, 7, 5, 1, 5, 2, 6,
     ];
-    let mut octopuses: Vec<Octopus> = energies.iter().map(|en| Octopus::new(*en)).collect();
+    let octopuses: Vec<Octopus> = energies.iter().map(|en| Octopus::new(*en)).collect();
 
     let first_step_energies = vec![
         6u32, 5, 9, 4, 2, 5, 4, 3, 3, 4, 3, 8, 5, 6, 9, 6, 5, 8, 2, 2, 6, 3, 7, 5, 6, 6, 7, 2, 8,
@@ -216,13 +157,13 @@ fn life_step_test2() {
         9, 6, 8, 0, 0, 0, 0, 5, 9, 4, 3, 0, 0, 0, 0, 0, 0, 7, 4, 5, 6, 9, 0, 0, 0, 0, 0, 0, 8, 7,
         6, 8, 7, 0, 0, 0, 0, 6, 8, 4, 8,
     ];
-    let width = 10;
-    octopuses_life_step(&mut octopuses, width);
-    for i in 0..energies.len() {
-        assert_eq!(first_step_energies[i], octopuses[i].energy);
+    let mut octopuses = Grid::new(10, 10, octopuses).unwrap();
+    octopuses_life_step(&mut octopuses);
+    for (oct, energy) in octopuses.iter().zip(first_step_energies) {
+        assert_eq!(energy, oct.energy);
     }
-    octopuses_life_step(&mut octopuses, width);
-    for i in 0..energies.len() {
-        assert_eq!(second_step_energies[i], octopuses[i].energy);
+    octopuses_life_step(&mut octopuses);
+    for (oct, energy) in octopuses.iter().zip(second_step_energies) {
+        assert_eq!(energy, oct.energy);
     }
 }
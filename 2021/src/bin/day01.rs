@@ -1,22 +1,17 @@
-use std::{fs::File, io::BufReader};
+use std::fs;
 
-fn main() -> std::io::Result<()> {
-    let file = File::open("res/input01")?;
-    let depths = parse(BufReader::new(file))?;
+use parse::{ParseError, Span};
+
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/input01")?;
+    let depths = parse(Span::new(&input))?;
 
     println!("Part 1: {}", count_depth_increases(&depths));
     Ok(())
 }
 
-fn parse<T: std::io::BufRead>(input: T) -> std::io::Result<Vec<u64>> {
-    let mut depths = Vec::new();
-    for maybe_line in input.lines() {
-        let depth = maybe_line?.parse().map_err(|_| {
-            std::io::Error::new(std::io::ErrorKind::InvalidData, "unable to parse as u64")
-        })?;
-        depths.push(depth);
-    }
-    Ok(depths)
+fn parse(input: Span) -> Result<Vec<u64>, ParseError> {
+    input.lines().map(|line| line.parse()).collect()
 }
 
 fn count_depth_increases(depths: &[u64]) -> usize {
@@ -31,6 +26,8 @@ fn count_depth_increases(depths: &[u64]) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use parse::Span;
+
     use crate::{count_depth_increases, parse};
 
     const TEST_INPUT_1: &str = "\
@@ -48,7 +45,7 @@ mod tests {
 
     #[test]
     fn test_depth_increase_count() {
-        let depths = parse(TEST_INPUT_1.as_bytes()).expect("test input 1 did not parse correctly");
+        let depths = parse(Span::new(TEST_INPUT_1)).expect("test input 1 did not parse correctly");
         assert_eq!(count_depth_increases(&depths), 7);
     }
 }
@@ -0,0 +1,86 @@
+//! Times the sweeps of `Forest` against the scans day 8 used to do, on
+//! generated forests of growing size. Run with
+//! `cargo bench --features bench --bench forest`.
+//!
+//! Each is timed over several runs, reporting the best and the median so
+//! that a cold first run or a busy moment doesn't skew the comparison.
+
+use std::time::{Duration, Instant};
+
+use adventofcode_2022::forest::Forest;
+use geometry::Grid;
+
+/// A square forest of random heights, the same one for the same `seed`.
+fn generate(size: usize, seed: u64) -> Forest {
+    // xorshift64, good enough for tree heights.
+    let mut state = seed.max(1);
+    let trees = Grid::from_fn(size, size, |_| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % 10) as u8
+    });
+    Forest::new(trees)
+}
+
+const RUNS: usize = 9;
+
+/// The best and median time over `RUNS` runs.
+struct Timing {
+    best: Duration,
+    median: Duration,
+}
+
+impl Timing {
+    fn print(&self, name: &str) {
+        println!(
+            "  {name:7} best: {:.3e} s, median: {:.3e} s",
+            self.best.as_secs_f32(),
+            self.median.as_secs_f32()
+        );
+    }
+}
+
+fn time<T>(f: impl Fn() -> T) -> (T, Timing) {
+    let mut durations = Vec::with_capacity(RUNS);
+    let mut result = None;
+    for _ in 0..RUNS {
+        let start_time = Instant::now();
+        result = Some(f());
+        durations.push(start_time.elapsed());
+    }
+    durations.sort_unstable();
+    let timing = Timing {
+        best: durations[0],
+        median: durations[RUNS / 2],
+    };
+    (result.expect("RUNS is not zero"), timing)
+}
+
+fn main() {
+    for size in [100, 300, 1000] {
+        let forest = generate(size, size as u64);
+        println!("{size}x{size} trees");
+
+        let ((visible, best), timing) = time(|| {
+            let visible = forest.visibility().iter().filter(|&&v| v).count();
+            let best = forest.scenic_scores().iter().copied().max();
+            (visible, best)
+        });
+        timing.print("sweeps:");
+
+        let ((scanned_visible, scanned_best), timing) = time(|| {
+            let positions = || forest.trees().positions();
+            let visible = positions()
+                .filter(|&position| forest.is_visible_by_scan(position))
+                .count();
+            let best = positions()
+                .map(|position| forest.scenic_score_by_scan(position))
+                .max();
+            (visible, best)
+        });
+        timing.print("scans:");
+
+        assert_eq!((visible, best), (scanned_visible, scanned_best));
+    }
+}
@@ -1,63 +1,63 @@
-use std::{fs::File, io::Read, num::ParseIntError};
+use std::fs;
 
-fn main() -> Result<(), anyhow::Error> {
-    let mut file = File::open("resources/input01")?;
-    let mut input = String::new();
-    file.read_to_string(&mut input)?;
-    let elves = parse_input(&input)?;
+use aoc::{Error, Solution};
+use parse::{ParseError, Span};
+
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("resources/input01")?;
     println!("Day 1");
-    println!("Part 1: {:?}", part1(&elves));
-    println!("Part 2: {}", part2(&elves));
-    Ok(())
+    aoc::solve::<Day01>(&input)
 }
 
-fn parse_input(input: &str) -> Result<Vec<Vec<u64>>, ParseIntError> {
-    let mut elves = Vec::new();
-    let mut elf = Vec::new();
-    for line in input.lines() {
-        if line.is_empty() {
-            elves.push(elf);
-            elf = Vec::new();
-            continue;
-        }
-        elf.push(line.parse::<u64>()?);
-    }
-    elves.push(elf);
-    Ok(elves)
+fn parse_input(input: &str) -> Result<Vec<Vec<u64>>, ParseError> {
+    Span::new(input)
+        .blocks()
+        .map(|elf| elf.lines().map(|line| line.parse()).collect())
+        .collect()
 }
 
 fn part1(elves: &[Vec<u64>]) -> Option<u64> {
     elves.iter().map(|elf| elf.iter().sum()).max()
 }
 
-fn part2(elves: &[Vec<u64>]) -> u64 {
-    assert!(elves.len() >= 3);
+fn part2(elves: &[Vec<u64>]) -> aoc::Result<u64> {
+    if elves.len() < 3 {
+        return Err(Error::no_solution(format!(
+            "only {} elves, the top three are needed",
+            elves.len()
+        )));
+    }
     let mut calories: Vec<u64> = elves.iter().map(|elf| elf.iter().sum()).collect();
     calories.sort_unstable_by(|a, b| b.cmp(a));
-    calories[..3].iter().sum()
+    Ok(calories[..3].iter().sum())
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{parse_input, part1, part2};
+struct Day01;
 
-    fn test_input_1() -> String {
-        "1000
-2000
-3000
+impl Solution for Day01 {
+    type Input = Vec<Vec<u64>>;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-4000
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
 
-5000
-6000
+    fn part1(elves: &Self::Input) -> aoc::Result<u64> {
+        part1(elves).ok_or_else(|| Error::no_solution("there are no elves"))
+    }
+
+    fn part2(elves: &Self::Input) -> aoc::Result<u64> {
+        part2(elves)
+    }
+}
 
-7000
-8000
-9000
+#[cfg(test)]
+mod tests {
+    use crate::{parse_input, part2};
 
-10000
-"
-        .to_string()
+    fn test_input_1() -> String {
+        aoc::example_input(2022, 1, 1)
     }
 
     #[test]
@@ -77,16 +77,10 @@ mod tests {
     }
 
     #[test]
-    fn part1_test_1() {
-        let input = test_input_1();
-        let elves = parse_input(&input).unwrap();
-        assert_eq!(part1(&elves), Some(24000));
-    }
-
-    #[test]
-    fn part2_test_1() {
-        let input = test_input_1();
-        let elves = parse_input(&input).unwrap();
-        assert_eq!(part2(&elves), 45000);
+    fn too_few_elves_test() {
+        let elves = parse_input("1000\n\n2000\n").unwrap();
+        assert!(part2(&elves).is_err());
     }
 }
+
+aoc::example_tests!(Day01, 2022, 1);
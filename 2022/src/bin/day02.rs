@@ -1,13 +1,12 @@
-use std::{fs::File, io::Read};
+use std::fs;
 
-fn main() -> Result<(), anyhow::Error> {
-    let mut fl = File::open("resources/input02")?;
-    let mut input = String::new();
-    fl.read_to_string(&mut input)?;
+use aoc::Solution;
+use parse::{ParseError, Span};
+
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("resources/input02")?;
     println!("Day 2");
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
+    aoc::solve::<Day02>(&input)
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -24,24 +23,28 @@ enum Outcome {
     Win = 6,
 }
 
-fn parse_input_1(input: &str) -> Vec<(Shape, Shape)> {
-    input
+/// The opponent's shape and the second column, which is `X`, `Y` or `Z`
+/// and means a shape or an outcome depending on the part.
+fn parse_input(input: &str) -> Result<Vec<(Shape, char)>, ParseError> {
+    Span::new(input)
         .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
         .map(|line| {
-            let mut chars = line.chars();
-            let s1 = match chars.next().unwrap() {
-                'A' => Shape::Rock,
-                'B' => Shape::Paper,
-                'C' => Shape::Scissor,
-                _ => panic!("unknown shape"),
+            let (s1, s2) = line.split_once(" ")?;
+            let s1 = match s1.as_str() {
+                "A" => Shape::Rock,
+                "B" => Shape::Paper,
+                "C" => Shape::Scissor,
+                _ => return Err(s1.expected("A, B or C")),
             };
-            let s2 = match chars.nth(1).unwrap() {
-                'X' => Shape::Rock,
-                'Y' => Shape::Paper,
-                'Z' => Shape::Scissor,
-                _ => panic!("unknown shape"),
+            let s2 = match s2.as_str() {
+                "X" => 'X',
+                "Y" => 'Y',
+                "Z" => 'Z',
+                _ => return Err(s2.expected("X, Y or Z")),
             };
-            (s1, s2)
+            Ok((s1, s2))
         })
         .collect()
 }
@@ -66,72 +69,84 @@ fn score(rounds: &[(Shape, Shape)]) -> u64 {
         .fold(0, |acc, el| acc + is_win(el) as u64 + el.1 as u64)
 }
 
-fn part1(input: &str) -> u64 {
-    let rounds = parse_input_1(input);
+fn part1(rounds: &[(Shape, char)]) -> u64 {
+    let rounds: Vec<(Shape, Shape)> = rounds
+        .iter()
+        .map(|&(s1, s2)| {
+            let s2 = match s2 {
+                'X' => Shape::Rock,
+                'Y' => Shape::Paper,
+                _ => Shape::Scissor,
+            };
+            (s1, s2)
+        })
+        .collect();
     score(&rounds)
 }
 
-fn part2(input: &str) -> u64 {
-    let mut rounds = Vec::new();
-    for line in input.lines() {
-        let mut chars = line.chars();
-        let s1 = match chars.next().unwrap() {
-            'A' => Shape::Rock,
-            'B' => Shape::Paper,
-            'C' => Shape::Scissor,
-            _ => panic!("unknown shape"),
-        };
-        match (s1, chars.nth(1).unwrap()) {
-            (Shape::Rock, 'X') => rounds.push((s1, Shape::Scissor)),
-            (Shape::Rock, 'Y') => rounds.push((s1, Shape::Rock)),
-            (Shape::Rock, 'Z') => rounds.push((s1, Shape::Paper)),
-            (Shape::Paper, 'X') => rounds.push((s1, Shape::Rock)),
-            (Shape::Paper, 'Y') => rounds.push((s1, Shape::Paper)),
-            (Shape::Paper, 'Z') => rounds.push((s1, Shape::Scissor)),
-            (Shape::Scissor, 'X') => rounds.push((s1, Shape::Paper)),
-            (Shape::Scissor, 'Y') => rounds.push((s1, Shape::Scissor)),
-            (Shape::Scissor, 'Z') => rounds.push((s1, Shape::Rock)),
-            (_, _) => panic!("unknown shape"),
-        }
-    }
+fn part2(rounds: &[(Shape, char)]) -> u64 {
+    let rounds: Vec<(Shape, Shape)> = rounds
+        .iter()
+        .map(|&(s1, outcome)| {
+            let s2 = match (s1, outcome) {
+                (Shape::Rock, 'X') => Shape::Scissor,
+                (Shape::Rock, 'Y') => Shape::Rock,
+                (Shape::Rock, _) => Shape::Paper,
+                (Shape::Paper, 'X') => Shape::Rock,
+                (Shape::Paper, 'Y') => Shape::Paper,
+                (Shape::Paper, _) => Shape::Scissor,
+                (Shape::Scissor, 'X') => Shape::Paper,
+                (Shape::Scissor, 'Y') => Shape::Scissor,
+                (Shape::Scissor, _) => Shape::Rock,
+            };
+            (s1, s2)
+        })
+        .collect();
     score(&rounds)
 }
 
+struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<(Shape, char)>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> aoc::Result<u64> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> aoc::Result<u64> {
+        Ok(part2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{parse_input_1, part2, score, Shape};
+    use crate::{parse_input, Shape};
 
     fn test_input_1() -> String {
-        "A Y
-B X
-C Z
-"
-        .to_string()
+        aoc::example_input(2022, 2, 1)
     }
 
     #[test]
     fn parse_test() {
         let input = test_input_1();
         let expected = vec![
-            (Shape::Rock, Shape::Paper),
-            (Shape::Paper, Shape::Rock),
-            (Shape::Scissor, Shape::Scissor),
+            (Shape::Rock, 'Y'),
+            (Shape::Paper, 'X'),
+            (Shape::Scissor, 'Z'),
         ];
-        assert_eq!(parse_input_1(&input), expected);
-    }
-
-    #[test]
-    fn part1_test_1() {
-        let input = test_input_1();
-        let rounds = parse_input_1(&input);
-        let expected = 15;
-        assert_eq!(score(&rounds), expected);
-    }
-
-    #[test]
-    fn part2_test_1() {
-        let input = test_input_1();
-        let expected = 12;
-        assert_eq!(part2(&input), expected);
+        assert_eq!(parse_input(&input), Ok(expected));
+        let error = parse_input("A Y\nD X\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(parse_input("A W").unwrap_err().column, 3);
+        assert!(parse_input("AY").is_err());
     }
 }
+
+aoc::example_tests!(Day02, 2022, 2);
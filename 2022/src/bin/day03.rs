@@ -1,49 +1,59 @@
 use std::{
     collections::{HashMap, HashSet},
-    fs::File,
-    io::Read,
+    fs,
 };
 
-fn main() -> Result<(), anyhow::Error> {
-    let mut fl = File::open("resources/input03")?;
-    let mut input = String::new();
-    fl.read_to_string(&mut input)?;
-    let rucksacks = parse_input(&input);
+use aoc::{Error, Solution};
+use parse::{ErrorKind, ParseError, Span};
+
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("resources/input03")?;
     println!("Day 3");
-    println!("Part 1: {}", part1(&rucksacks));
-    println!("Part 2: {}", part2(&rucksacks));
-    Ok(())
+    aoc::solve::<Day03>(&input)
 }
 
 type Compartment = HashMap<char, usize>;
 type Rucksack = (Compartment, Compartment);
 
-fn parse_input(input: &str) -> Vec<Rucksack> {
-    input
+fn compartment(items: &str) -> Compartment {
+    let mut compartment = HashMap::new();
+    for ch in items.chars() {
+        compartment
+            .entry(ch)
+            .and_modify(|quant| *quant += 1)
+            .or_insert_with(|| 1);
+    }
+    compartment
+}
+
+fn parse_input(input: &str) -> Result<Vec<Rucksack>, ParseError> {
+    Span::new(input)
         .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
         .map(|line| {
-            let len = line.len();
-            let mut compartment_1 = HashMap::new();
-            line[..len / 2].chars().for_each(|ch| {
-                compartment_1
-                    .entry(ch)
-                    .and_modify(|quant| *quant += 1)
-                    .or_insert_with(|| 1);
-            });
-            let mut compartment_2 = HashMap::new();
-            line[len / 2..].chars().for_each(|ch| {
-                compartment_2
-                    .entry(ch)
-                    .and_modify(|quant| *quant += 1)
-                    .or_insert_with(|| 1);
-            });
-            (compartment_1, compartment_2)
+            let mut rest = line;
+            while let Some((ch, after)) = rest.split_first() {
+                if !ch.is_ascii_alphabetic() {
+                    return Err(rest.error(ErrorKind::UnexpectedChar(ch)));
+                }
+                rest = after;
+            }
+            let len = line.as_str().len();
+            if len % 2 != 0 {
+                return Err(line.error(ErrorKind::InvalidValue {
+                    found: line.as_str().to_string(),
+                    reason: "the two compartments must hold as many items".into(),
+                }));
+            }
+            let (first, second) = line.as_str().split_at(len / 2);
+            Ok((compartment(first), compartment(second)))
         })
         .collect()
 }
 
 fn priority(ch: char) -> u64 {
-    if ('a'..='z').contains(&ch) {
+    if ch.is_ascii_lowercase() {
         u64::from(ch) - u64::from('a') + 1
     } else {
         u64::from(ch) - u64::from('A') + 27
@@ -62,8 +72,10 @@ fn part1(rucksacks: &[Rucksack]) -> u64 {
     prio
 }
 
-fn find_badge(rucksacks: &[Rucksack]) -> char {
-    assert_eq!(rucksacks.len(), 3, "an elf group must have 3 rucksacks");
+fn find_badge(rucksacks: &[Rucksack]) -> aoc::Result<char> {
+    if rucksacks.len() != 3 {
+        return Err(Error::invalid_state("an elf group must have 3 rucksacks"));
+    }
     let item_types_1: HashSet<&char> =
         HashSet::from_iter(rucksacks[0].0.keys().chain(rucksacks[0].1.keys()));
     let item_types_2: HashSet<&char> =
@@ -71,37 +83,56 @@ fn find_badge(rucksacks: &[Rucksack]) -> char {
     let item_types_3: HashSet<&char> =
         HashSet::from_iter(rucksacks[2].0.keys().chain(rucksacks[2].1.keys()));
     let interm: HashSet<&char> = item_types_1.intersection(&item_types_2).copied().collect();
-    **interm
+    interm
         .intersection(&item_types_3)
         .next()
-        .expect("each group expected to have a common item")
+        .map(|&&badge| badge)
+        .ok_or_else(|| Error::no_solution("a group of elves has no item in common"))
 }
 
-fn part2(rucksacks: &[Rucksack]) -> u64 {
-    rucksacks.chunks(3).map(find_badge).map(priority).sum()
+fn part2(rucksacks: &[Rucksack]) -> aoc::Result<u64> {
+    rucksacks
+        .chunks(3)
+        .map(|group| find_badge(group).map(priority))
+        .sum()
+}
+
+struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Rucksack>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(rucksacks: &Self::Input) -> aoc::Result<u64> {
+        Ok(part1(rucksacks))
+    }
+
+    fn part2(rucksacks: &Self::Input) -> aoc::Result<u64> {
+        part2(rucksacks)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
-    use crate::{find_badge, parse_input, part1, part2};
+    use parse::ErrorKind;
+
+    use crate::{find_badge, parse_input};
 
     fn test_input_1() -> String {
-        "vJrwpWtwJgWrhcsFMMfFFhFp
-jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-PmmdzqPrVvPwwTWBwg
-wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw
-"
-        .to_string()
+        aoc::example_input(2022, 3, 1)
     }
 
     #[test]
     fn parse_test() {
         let input = test_input_1();
-        let expected = vec![
+        let expected = [
             (
                 HashMap::from([
                     ('v', 1),
@@ -217,34 +248,33 @@ CrZsJsPPZsGzwwsLwLmpwMDw
                 ]),
             ),
         ];
-        for (i, (rucksack, exp)) in parse_input(&input).iter().zip(expected.iter()).enumerate() {
+        let rucksacks = parse_input(&input).unwrap();
+        for (i, (rucksack, exp)) in rucksacks.iter().zip(expected.iter()).enumerate() {
             assert_eq!(rucksack, exp, "mismatch in {i}th element");
         }
     }
 
-    #[test]
-    fn part1_test_1() {
-        let input = test_input_1();
-        let rucksacks = parse_input(&input);
-        let expected = 157;
-        assert_eq!(part1(&rucksacks), expected);
-    }
-
     #[test]
     fn find_badge_test_1() {
         let input = test_input_1();
-        let rucksacks = parse_input(&input);
+        let rucksacks = parse_input(&input).unwrap();
         let expected = ['r', 'Z'];
         for (group, exp) in rucksacks.chunks(3).zip(expected) {
-            assert_eq!(find_badge(group), exp);
+            assert_eq!(find_badge(group).unwrap(), exp);
         }
+        assert!(find_badge(&rucksacks[..2]).is_err());
     }
 
     #[test]
-    fn part2_test_1() {
-        let input = test_input_1();
-        let rucksacks = parse_input(&input);
-        let expected = 70;
-        assert_eq!(part2(&rucksacks), expected);
+    fn parse_error_test() {
+        let error = parse_input("abcd\nabc\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        let error = parse_input("ab1d").unwrap_err();
+        assert_eq!(
+            (error.column, error.kind),
+            (3, ErrorKind::UnexpectedChar('1'))
+        );
     }
 }
+
+aoc::example_tests!(Day03, 2022, 3);
@@ -1,40 +1,35 @@
-use std::{fs::File, io::Read, ops::RangeInclusive};
+use std::{fs, ops::RangeInclusive};
 
-use regex::Regex;
+use aoc::Solution;
+use parse::{ErrorKind, ParseError, Span};
 
-fn main() -> Result<(), anyhow::Error> {
-    let mut fl = File::open("resources/input04")?;
-    let mut input = String::new();
-    fl.read_to_string(&mut input)?;
-    let elf_pairs = parse_input(&input);
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("resources/input04")?;
     println!("Day 4");
-    println!("Part 1: {}", part1(&elf_pairs));
-    println!("Part 2: {}", part2(&elf_pairs));
-    Ok(())
+    aoc::solve::<Day04>(&input)
 }
 
 type SectionRange = RangeInclusive<usize>;
 
-fn parse_input(input: &str) -> Vec<(SectionRange, SectionRange)> {
-    let mut elf_pairs = Vec::new();
-    let section_pattern = Regex::new(r#"^(\d+)-(\d+),(\d+)-(\d+)$"#).expect("pattern is valid");
-    for line in input.lines() {
-        match section_pattern.captures(line) {
-            Some(captures) => {
-                let elf1 = captures.get(1).unwrap().as_str().parse::<usize>().unwrap()
-                    ..=captures.get(2).unwrap().as_str().parse::<usize>().unwrap();
-                let elf2 = captures.get(3).unwrap().as_str().parse::<usize>().unwrap()
-                    ..=captures.get(4).unwrap().as_str().parse::<usize>().unwrap();
-                assert!(
-                    (elf1.start() <= elf1.end()) && (elf2.start() <= elf2.end()),
-                    "invalid range on line {line}"
-                );
-                elf_pairs.push((elf1, elf2));
-            }
-            None => panic!("each line must have 2 elves"),
-        }
+fn parse_input(input: &str) -> Result<Vec<(SectionRange, SectionRange)>, ParseError> {
+    Span::new(input)
+        .lines()
+        .map(|line| {
+            let (elf1, elf2) = line.split_once(",")?;
+            Ok((parse_range(elf1)?, parse_range(elf2)?))
+        })
+        .collect()
+}
+
+fn parse_range(span: Span) -> Result<SectionRange, ParseError> {
+    let (start, end) = span.split_once("-")?;
+    let (start, end) = (start.parse()?, end.parse()?);
+    if start > end {
+        return Err(span.error(ErrorKind::Custom(format!(
+            "section range {span} ends before it starts"
+        ))));
     }
-    elf_pairs
+    Ok(start..=end)
 }
 
 /// Do ranges intersect
@@ -63,18 +58,32 @@ fn part2(elf_pairs: &[(SectionRange, SectionRange)]) -> usize {
         .count()
 }
 
+struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<(SectionRange, SectionRange)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(elf_pairs: &Self::Input) -> aoc::Result<usize> {
+        Ok(part1(elf_pairs))
+    }
+
+    fn part2(elf_pairs: &Self::Input) -> aoc::Result<usize> {
+        Ok(part2(elf_pairs))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{is_intersection, is_subset, parse_input, part1, part2};
+    use crate::{is_intersection, is_subset, parse_input};
 
     fn test_input_1() -> String {
-        "2-4,6-8
-2-3,4-5
-5-7,7-9
-2-8,3-7
-6-6,4-6
-2-6,4-8"
-            .to_string()
+        aoc::example_input(2022, 4, 1)
     }
 
     #[test]
@@ -88,7 +97,20 @@ mod tests {
             (6..=6, 4..=6),
             (2..=6, 4..=8),
         ];
-        assert_eq!(parse_input(&input), expected);
+        assert_eq!(parse_input(&input), Ok(expected));
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let error = parse_input("2-4,6-8\n2-3;4-5").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        let error = parse_input("2-4,6-8\n2-3,5-x").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 7));
+        let error = parse_input("2-4,8-6").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 5: section range 8-6 ends before it starts"
+        );
     }
 
     #[test]
@@ -121,18 +143,6 @@ mod tests {
             );
         }
     }
-
-    #[test]
-    fn part1_test_1() {
-        let input = test_input_1();
-        let elf_pairs = parse_input(&input);
-        assert_eq!(part1(&elf_pairs), 2);
-    }
-
-    #[test]
-    fn part2_test_1() {
-        let input = test_input_1();
-        let elf_pairs = parse_input(&input);
-        assert_eq!(part2(&elf_pairs), 4);
-    }
 }
+
+aoc::example_tests!(Day04, 2022, 4);
@@ -1,136 +1,98 @@
-use std::{fs::File, io::Read};
+use std::fs;
 
-use regex::Regex;
+use adventofcode_2022::crane::{Crane, CrateMover9000, CrateMover9001, Move, Replay, Stacks};
+use aoc::{Error, Solution};
+use parse::{ErrorKind, ParseError, Span};
 
-fn main() -> Result<(), anyhow::Error> {
-    let mut fl = File::open("resources/input05")?;
-    let mut input = String::new();
-    fl.read_to_string(&mut input)?;
-    let (stacks, moves) = parse_input(&input);
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("resources/input05")?;
     println!("Day 5");
-    println!("Part 1: {}", part1(stacks.clone(), &moves));
-    println!("Part 2: {}", part2(stacks, &moves));
-    Ok(())
+    aoc::solve::<Day05>(&input)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Move {
-    source: usize,
-    destination: usize,
-    quantity: usize,
-}
-
-impl Move {
-    fn new(source: usize, destination: usize, quantity: usize) -> Self {
-        Self {
-            source,
-            destination,
-            quantity,
+fn parse_input(input: &str) -> Result<(Stacks, Vec<Move>), ParseError> {
+    let input = Span::new(input);
+    let mut parts = input.blocks();
+    let drawing = parts
+        .next()
+        .ok_or_else(|| input.expected("a drawing of the stacks"))?;
+    let moves_str = parts
+        .next()
+        .ok_or_else(|| input.expected("a list of moves after the stacks"))?;
+
+    let stacks = Stacks::parse(drawing)?;
+    let mut moves = Vec::new();
+    for line in moves_str.lines() {
+        let [quantity, from, to] = line.integer_array()?;
+        for stack in [from, to] {
+            if stacks.stack(stack).is_none() {
+                return Err(line.error(ErrorKind::Custom(format!("there is no stack {stack}"))));
+            }
         }
+        moves.push(Move::new(quantity, from, to));
     }
+    Ok((stacks, moves))
 }
 
-type Stack = Vec<char>;
+fn top_crates(stacks: &Stacks, crane: impl Crane, moves: &[Move]) -> aoc::Result<String> {
+    let stacks = Replay::new(stacks.clone(), crane, moves)
+        .finish()
+        .map_err(Error::invalid_state)?;
+    Ok(stacks.tops())
+}
 
-fn parse_input(input: &str) -> (Vec<Stack>, Vec<Move>) {
-    let mut parts = input.split("\n\n");
-    let stack_str = parts.next().unwrap();
-    let moves_str = parts.next().unwrap();
+struct Day05;
 
-    let (stack_str, stack_nums) = stack_str.rsplit_once('\n').unwrap();
-    let num_stacks = stack_nums.split_whitespace().count();
-    let mut stacks = vec![vec![]; num_stacks + 1];
-    for line in stack_str.lines().rev() {
-        for (ch, stack) in line.chars().skip(1).step_by(4).zip(&mut stacks[1..]) {
-            if ch != ' ' {
-                stack.push(ch);
-            }
-        }
-    }
+impl Solution for Day05 {
+    type Input = (Stacks, Vec<Move>);
+    type Answer1 = String;
+    type Answer2 = String;
 
-    let move_pattern = Regex::new(r#"\d+"#).expect("pattern is valid");
-    let mut moves = Vec::new();
-    for line in moves_str.lines() {
-        let mut matches = move_pattern.find_iter(line);
-        let quantity = matches.next().unwrap().as_str().parse().unwrap();
-        let source = matches.next().unwrap().as_str().parse().unwrap();
-        let destination = matches.next().unwrap().as_str().parse().unwrap();
-        moves.push(Move::new(source, destination, quantity));
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(parse_input(input)?)
     }
-    (stacks, moves)
-}
 
-fn part1(mut stacks: Vec<Stack>, moves: &[Move]) -> String {
-    for m in moves {
-        for _ in 0..m.quantity {
-            let source = stacks[m.source]
-                .pop()
-                .expect("expecting stack to have at least one item");
-            stacks[m.destination].push(source);
-        }
+    fn part1((stacks, moves): &Self::Input) -> aoc::Result<String> {
+        top_crates(stacks, CrateMover9000, moves)
     }
-    stacks[1..]
-        .iter()
-        .map(|stack| stack.last().unwrap_or(&' '))
-        .collect()
-}
 
-fn part2(mut stacks: Vec<Stack>, moves: &[Move]) -> String {
-    for m in moves {
-        let source_len = stacks[m.source].len();
-        let source = stacks[m.source].split_off(source_len - m.quantity);
-        stacks[m.destination].extend_from_slice(&source);
+    fn part2((stacks, moves): &Self::Input) -> aoc::Result<String> {
+        top_crates(stacks, CrateMover9001, moves)
     }
-    stacks[1..]
-        .iter()
-        .map(|stack| stack.last().unwrap_or(&' '))
-        .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{parse_input, part1, part2, Move};
+    use adventofcode_2022::crane::{Move, Stacks};
+
+    use crate::parse_input;
 
     fn test_input_1() -> String {
-        "    [D]    
-[N] [C]    
-[Z] [M] [P]
- 1   2   3 
-
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2
-"
-        .to_string()
+        aoc::example_input(2022, 5, 1)
     }
 
     #[test]
     fn parse_test() {
         let input = test_input_1();
-        let exp_stacks = vec![vec![], vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']];
+        let exp_stacks = Stacks::new(vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]);
         let exp_moves = vec![
-            Move::new(2, 1, 1),
-            Move::new(1, 3, 3),
-            Move::new(2, 1, 2),
             Move::new(1, 2, 1),
+            Move::new(3, 1, 3),
+            Move::new(2, 2, 1),
+            Move::new(1, 1, 2),
         ];
-        assert_eq!(parse_input(&input), (exp_stacks, exp_moves));
-    }
-
-    #[test]
-    fn part1_test_1() {
-        let input = test_input_1();
-        let (stacks, moves) = parse_input(&input);
-        let expected = "CMZ";
-        assert_eq!(part1(stacks, &moves), expected);
+        assert_eq!(parse_input(&input), Ok((exp_stacks, exp_moves)));
     }
 
     #[test]
-    fn part2_test_1() {
-        let input = test_input_1();
-        let (stacks, moves) = parse_input(&input);
-        let expected = "MCD";
-        assert_eq!(part2(stacks, &moves), expected);
+    fn parse_error_test() {
+        let input = test_input_1().replace("from 1 to 2", "from 1 to 4");
+        let error = parse_input(&input).unwrap_err();
+        assert_eq!(error.to_string(), "line 9, column 1: there is no stack 4");
+        let input = test_input_1().replace("from 1 to 3", "from 1");
+        let error = parse_input(&input).unwrap_err();
+        assert_eq!((error.line, error.column), (7, 1));
     }
 }
+
+aoc::example_tests!(Day05, 2022, 5);
@@ -1,64 +1,67 @@
-use std::{collections::HashSet, fs::File, io::Read};
+use std::{collections::HashSet, fs};
 
-fn main() -> Result<(), anyhow::Error> {
-    let mut fl = File::open("resources/input06")?;
-    let mut input = String::new();
-    fl.read_to_string(&mut input)?;
+use aoc::{Error, Solution};
+
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("resources/input06")?;
     println!("Day 6");
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
+    aoc::solve::<Day06>(&input)
 }
 
-fn marker_position(buf: &str, size: usize) -> usize {
+fn marker_position(buf: &str, size: usize) -> aoc::Result<usize> {
     match buf
         .as_bytes()
         .windows(size)
         .enumerate()
         .find(|(_, window)| HashSet::<_>::from_iter(window.iter()).len() == size)
     {
-        Some((idx, _)) => idx + size,
-        None => panic!("buffer did not have a non-repeating {size} byte sequence"),
+        Some((idx, _)) => Ok(idx + size),
+        None => Err(Error::no_solution(format!(
+            "buffer did not have a non-repeating {size} byte sequence"
+        ))),
     }
 }
 
-fn part1(buf: &str) -> usize {
+fn part1(buf: &str) -> aoc::Result<usize> {
     marker_position(buf, 4)
 }
 
-fn part2(buf: &str) -> usize {
+fn part2(buf: &str) -> aoc::Result<usize> {
     marker_position(buf, 14)
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{part1, part2};
+struct Day06;
 
-    fn test_inputs() -> Vec<String> {
-        vec![
-            "mjqjpqmgbljsphdztnvjfqwrcgsmlb".to_string(),
-            "bvwbjplbgvbhsrlpgdmjqwftvncz".to_string(),
-            "nppdvjthqldpwncqszvftbrmjlhg".to_string(),
-            "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg".to_string(),
-            "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw".to_string(),
-        ]
+impl Solution for Day06 {
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(input.to_string())
     }
 
-    #[test]
-    fn part1_test() {
-        let input = test_inputs();
-        let expected = vec![7, 5, 6, 10, 11];
-        for (inp, exp) in input.iter().zip(expected) {
-            assert_eq!(part1(inp), exp, "failure for {inp}");
-        }
+    fn part1(buf: &Self::Input) -> aoc::Result<usize> {
+        part1(buf)
+    }
+
+    fn part2(buf: &Self::Input) -> aoc::Result<usize> {
+        part2(buf)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::part1;
 
     #[test]
-    fn part2_test() {
-        let input = test_inputs();
-        let expected = vec![19, 23, 23, 29, 26];
-        for (inp, exp) in input.iter().zip(expected) {
-            assert_eq!(part2(inp), exp, "failure for {inp}");
-        }
+    fn no_marker_test() {
+        let error = part1("abcabcabc").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "no solution found: buffer did not have a non-repeating 4 byte sequence"
+        );
     }
 }
+
+aoc::example_tests!(Day06, 2022, 6);
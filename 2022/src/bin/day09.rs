@@ -1,230 +1,179 @@
-use std::{collections::HashSet, fs::File, io::Read};
+use std::{collections::HashSet, fs};
 
-fn main() -> Result<(), anyhow::Error> {
-    let mut fl = File::open("resources/input09")?;
-    let mut input = String::new();
-    fl.read_to_string(&mut input)?;
-    let moves = parse_input(&input);
-    println!("Day 9");
-    println!("Part 1: {}", part1(&moves));
-    println!("Part 2: {}", part2(&moves));
-    Ok(())
-}
-
-type Point = (i64, i64);
-
-fn add(p1: Point, p2: Point) -> Point {
-    (p1.0 + p2.0, p1.1 + p2.1)
-}
+use adventofcode_2022::rope::{Rope, Steps};
+use aoc::Solution;
+use geometry::{Direction, Vector2};
+use parse::{ParseError, Span};
 
-fn sub(p1: Point, p2: Point) -> Point {
-    (p1.0 - p2.0, p1.1 - p2.1)
-}
-
-fn distance(p1: Point, p2: Point) -> i64 {
-    (p2.0 - p1.0).abs() + (p2.1 - p1.1).abs()
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("resources/input09")?;
+    println!("Day 9");
+    aoc::solve::<Day09>(&input)
 }
 
-fn parse_input(input: &str) -> Vec<Point> {
-    input
+fn parse_input(input: &str) -> Result<Vec<Vector2<i64>>, ParseError> {
+    Span::new(input)
         .lines()
         .map(|line| {
-            let (direction, magnitude) = line.split_once(' ').unwrap();
-            let magnitude: i64 = magnitude.parse().unwrap();
-            match direction {
-                "R" => (magnitude, 0),
-                "L" => (-magnitude, 0),
-                "U" => (0, magnitude),
-                "D" => (0, -magnitude),
-                _ => panic!("unexpected direction"),
+            let (direction, magnitude) = line.split_once(" ")?;
+            let magnitude: i64 = magnitude.parse()?;
+            let direction = match direction.split_first() {
+                Some((ch, rest)) if rest.is_empty() => Direction::from_char(ch),
+                _ => None,
             }
+            .ok_or_else(|| direction.expected("one of U, D, L or R"))?;
+            Ok(direction.to_vector() * magnitude)
         })
         .collect()
 }
 
-fn execute_moves(moves: &[Point], knots: usize) -> HashSet<Point> {
-    let mut knot_positions = vec![(0, 0); knots];
-    let mut t_positions = HashSet::new();
-    for m in moves {
-        match m {
-            (x, y) if x == &0 || y == &0 => {
-                for _ in 0..x.abs().max(y.abs()) {
-                    execute_move(&mut knot_positions, (x.signum(), y.signum()));
-                    t_positions.insert(knot_positions[knots - 1]);
-                }
-            }
-            _ => panic!("move should not be in any other pattern"),
-        }
-    }
-    t_positions
+fn tail_positions(moves: &[Vector2<i64>], knots: usize) -> HashSet<Vector2<i64>> {
+    let rope = Steps::new(Rope::new(knots), moves).finish();
+    rope.visited(knots - 1).cloned().unwrap_or_default()
 }
 
-fn execute_move(knots: &mut [Point], m: Point) {
-    knots[0] = add(knots[0], m);
-    for i in 1..knots.len() {
-        knots[i] = adjust_knot_pair(knots[i - 1], knots[i]);
-    }
+fn part1(moves: &[Vector2<i64>]) -> usize {
+    tail_positions(moves, 2).len()
 }
 
-fn adjust_knot_pair(head_position: Point, tail_position: Point) -> Point {
-    let mut new_tail = tail_position;
-    if distance(head_position, tail_position) >= 2 {
-        let diff = sub(head_position, tail_position);
-        match diff {
-            (x, 0) => new_tail = add(tail_position, ((x.abs() - 1) * x.signum(), 0)),
-            (0, y) => new_tail = add(tail_position, (0, (y.abs() - 1) * y.signum())),
-            (x, y) if x.abs() != y.abs() => {
-                if x.abs() > y.abs() {
-                    new_tail = add(tail_position, ((x.abs() - 1) * x.signum(), y))
-                } else {
-                    new_tail = add(tail_position, (x, (y.abs() - 1) * y.signum()))
-                }
-            }
-            (x, y) => {
-                new_tail = add(
-                    tail_position,
-                    ((x.abs() - 1) * x.signum(), (y.abs() - 1) * y.signum()),
-                )
-            }
-        }
-    }
-    new_tail
+fn part2(moves: &[Vector2<i64>]) -> usize {
+    tail_positions(moves, 10).len()
 }
 
-fn part1(moves: &[Point]) -> usize {
-    execute_moves(moves, 2).len()
-}
+struct Day09;
 
-fn part2(moves: &[Point]) -> usize {
-    execute_moves(moves, 10).len()
+impl Solution for Day09 {
+    type Input = Vec<Vector2<i64>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(moves: &Self::Input) -> aoc::Result<usize> {
+        Ok(part1(moves))
+    }
+
+    fn part2(moves: &Self::Input) -> aoc::Result<usize> {
+        Ok(part2(moves))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
 
-    use crate::{execute_moves, parse_input, part1, part2};
+    use adventofcode_2022::rope::{Rope, Steps};
+    use geometry::{Bounds, Vector2};
+
+    use crate::{parse_input, tail_positions};
 
     fn test_input_1() -> String {
-        "R 4
-U 4
-L 3
-D 1
-R 4
-D 1
-L 5
-R 2
-"
-        .to_string()
+        aoc::example_input(2022, 9, 1)
     }
 
     fn test_input_2() -> String {
-        "R 5
-U 8
-L 8
-D 3
-R 17
-D 10
-L 25
-U 20
-"
-        .to_string()
+        aoc::example_input(2022, 9, 2)
     }
 
     #[test]
     fn parse_test() {
         let input = test_input_1();
         let expected = vec![
-            (4, 0),
-            (0, 4),
-            (-3, 0),
-            (0, -1),
-            (4, 0),
-            (0, -1),
-            (-5, 0),
-            (2, 0),
+            Vector2::new(4, 0),
+            Vector2::new(0, 4),
+            Vector2::new(-3, 0),
+            Vector2::new(0, -1),
+            Vector2::new(4, 0),
+            Vector2::new(0, -1),
+            Vector2::new(-5, 0),
+            Vector2::new(2, 0),
         ];
-        assert_eq!(parse_input(&input), expected);
+        assert_eq!(parse_input(&input), Ok(expected));
     }
 
     #[test]
     fn tail_positions_test_1() {
         let input = test_input_1();
-        let moves = parse_input(&input);
-        let expected = HashSet::from([
-            (0, 0),
-            (1, 0),
-            (2, 0),
-            (3, 0),
-            (4, 1),
-            (1, 2),
-            (2, 2),
-            (3, 2),
-            (4, 2),
-            (3, 3),
-            (4, 3),
-            (2, 4),
-            (3, 4),
-        ]);
-        assert_eq!(execute_moves(&moves, 2), expected);
-    }
-
-    #[test]
-    fn tail_positions_test_2() {
-        let input = test_input_2();
-        let moves = parse_input(&input);
+        let moves = parse_input(&input).unwrap();
         let expected = HashSet::from([
-            (0, 0),
-            (1, 1),
-            (2, 2),
-            (1, 3),
-            (2, 4),
-            (3, 5),
-            (4, 5),
-            (5, 5),
-            (6, 4),
-            (7, 3),
-            (8, 2),
-            (9, 1),
-            (10, 0),
-            (9, -1),
-            (8, -2),
-            (7, -3),
-            (6, -4),
-            (5, -5),
-            (4, -5),
-            (3, -5),
-            (2, -5),
-            (1, -5),
-            (0, -5),
-            (-1, -5),
-            (-2, -5),
-            (-3, -4),
-            (-4, -3),
-            (-5, -2),
-            (-6, -1),
-            (-7, 0),
-            (-8, 1),
-            (-9, 2),
-            (-10, 3),
-            (-11, 4),
-            (-11, 5),
-            (-11, 6),
+            Vector2::new(0, 0),
+            Vector2::new(1, 0),
+            Vector2::new(2, 0),
+            Vector2::new(3, 0),
+            Vector2::new(4, 1),
+            Vector2::new(1, 2),
+            Vector2::new(2, 2),
+            Vector2::new(3, 2),
+            Vector2::new(4, 2),
+            Vector2::new(3, 3),
+            Vector2::new(4, 3),
+            Vector2::new(2, 4),
+            Vector2::new(3, 4),
         ]);
-        assert_eq!(execute_moves(&moves, 10), expected);
+        assert_eq!(tail_positions(&moves, 2), expected);
     }
 
     #[test]
-    fn part1_test_1() {
+    fn visited_frame_test() {
         let input = test_input_1();
-        let moves = parse_input(&input);
-        assert_eq!(part1(&moves), 13);
+        let moves = parse_input(&input).unwrap();
+        let rope = Steps::new(Rope::new(2), &moves).finish();
+        assert_eq!(
+            rope.visited_frame(1, Bounds::new([0, 0], [5, 4])),
+            "..##..\n...##.\n.####.\n....#.\ns###.."
+        );
+        assert_eq!(
+            rope.frame(Bounds::new([0, 0], [5, 4])),
+            "......\n......\n.TH...\n......\ns....."
+        );
     }
 
     #[test]
-    fn part2_test_1() {
+    fn tail_positions_test_2() {
         let input = test_input_2();
-        let moves = parse_input(&input);
-        assert_eq!(part2(&moves), 36);
+        let moves = parse_input(&input).unwrap();
+        let expected = HashSet::from([
+            Vector2::new(0, 0),
+            Vector2::new(1, 1),
+            Vector2::new(2, 2),
+            Vector2::new(1, 3),
+            Vector2::new(2, 4),
+            Vector2::new(3, 5),
+            Vector2::new(4, 5),
+            Vector2::new(5, 5),
+            Vector2::new(6, 4),
+            Vector2::new(7, 3),
+            Vector2::new(8, 2),
+            Vector2::new(9, 1),
+            Vector2::new(10, 0),
+            Vector2::new(9, -1),
+            Vector2::new(8, -2),
+            Vector2::new(7, -3),
+            Vector2::new(6, -4),
+            Vector2::new(5, -5),
+            Vector2::new(4, -5),
+            Vector2::new(3, -5),
+            Vector2::new(2, -5),
+            Vector2::new(1, -5),
+            Vector2::new(0, -5),
+            Vector2::new(-1, -5),
+            Vector2::new(-2, -5),
+            Vector2::new(-3, -4),
+            Vector2::new(-4, -3),
+            Vector2::new(-5, -2),
+            Vector2::new(-6, -1),
+            Vector2::new(-7, 0),
+            Vector2::new(-8, 1),
+            Vector2::new(-9, 2),
+            Vector2::new(-10, 3),
+            Vector2::new(-11, 4),
+            Vector2::new(-11, 5),
+            Vector2::new(-11, 6),
+        ]);
+        assert_eq!(tail_positions(&moves, 10), expected);
     }
 }
+
+aoc::example_tests!(Day09, 2022, 9);
@@ -1,18 +1,12 @@
-use std::{collections::VecDeque, fs::File, io::Read};
+use std::{collections::VecDeque, fs};
 
-fn main() -> Result<(), anyhow::Error> {
-    let mut fl = File::open("resources/input10")?;
-    let mut input = String::new();
-    fl.read_to_string(&mut input)?;
-    let instructions = parse_input(&input);
-    let mut cpu = Cpu::new_with_instructions(instructions.clone());
+use aoc::Solution;
+use parse::{ParseError, Span};
+
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("resources/input10")?;
     println!("Day 10");
-    println!("Part 1: {}", part1(&mut cpu));
-    cpu.reset();
-    cpu.set_instructions(instructions);
-    println!("Part 2:");
-    part2(&mut cpu);
-    Ok(())
+    aoc::solve::<Day10>(&input)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -63,14 +57,14 @@ impl Cpu {
         }
     }
 
-    fn draw(&self) {
+    fn draw(&self, screen: &mut String) {
         if ((self.x - 1)..=(self.x + 1)).contains(&(self.cycle as i64 % 40)) {
-            print!("#");
+            screen.push('#');
         } else {
-            print!(" ");
+            screen.push('.');
         }
         if (self.cycle + 1) % 40 == 0 {
-            println!();
+            screen.push('\n');
         }
     }
 
@@ -79,8 +73,8 @@ impl Cpu {
         self.cycle += 1;
     }
 
-    fn tick_with_draw(&mut self) {
-        self.draw();
+    fn tick_with_draw(&mut self, screen: &mut String) {
+        self.draw(screen);
         self.compute();
         self.cycle += 1;
     }
@@ -92,33 +86,18 @@ impl Cpu {
     fn is_completed(&self) -> bool {
         self.instructions.is_empty()
     }
-
-    fn reset(&mut self) {
-        self.cycle = 0;
-        self.x = 1;
-        self.instructions = VecDeque::new();
-    }
-
-    fn set_instructions(&mut self, instructions: VecDeque<(Instruction, usize)>) {
-        self.instructions = instructions;
-    }
 }
 
-fn parse_input(input: &str) -> VecDeque<(Instruction, usize)> {
-    input
+fn parse_input(input: &str) -> Result<VecDeque<(Instruction, usize)>, ParseError> {
+    Span::new(input)
         .lines()
         .map(|line| {
-            if line.starts_with("noop") {
-                (NoOp, 0)
-            } else if line.starts_with("addx") {
-                (
-                    AddX {
-                        v: line[5..].parse().unwrap(),
-                    },
-                    0,
-                )
+            if line.as_str() == "noop" {
+                Ok((NoOp, 0))
+            } else if let Ok(v) = line.strip_prefix("addx ") {
+                Ok((AddX { v: v.parse()? }, 0))
             } else {
-                panic!("unknown instruction")
+                Err(line.expected("noop or addx"))
             }
         })
         .collect()
@@ -142,9 +121,33 @@ fn part1(cpu: &mut Cpu) -> i64 {
     total_signal_strength
 }
 
-fn part2(cpu: &mut Cpu) {
+/// The screen drawn while running, lit pixels are `#` and dark ones `.`.
+fn part2(cpu: &mut Cpu) -> String {
+    let mut screen = String::new();
     while !cpu.is_completed() {
-        cpu.tick_with_draw();
+        cpu.tick_with_draw(&mut screen);
+    }
+    screen.truncate(screen.trim_end_matches('\n').len());
+    screen
+}
+
+struct Day10;
+
+impl Solution for Day10 {
+    type Input = VecDeque<(Instruction, usize)>;
+    type Answer1 = i64;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(instructions: &Self::Input) -> aoc::Result<i64> {
+        Ok(part1(&mut Cpu::new_with_instructions(instructions.clone())))
+    }
+
+    fn part2(instructions: &Self::Input) -> aoc::Result<String> {
+        Ok(part2(&mut Cpu::new_with_instructions(instructions.clone())))
     }
 }
 
@@ -153,180 +156,29 @@ mod tests {
     use std::collections::VecDeque;
 
     use crate::{
-        parse_input, part1, Cpu,
+        parse_input, Cpu,
         Instruction::{AddX, NoOp},
     };
 
     fn test_input_1() -> String {
-        "noop
-addx 3
-addx -5
-"
-        .to_string()
+        aoc::example_input(2022, 10, 1)
     }
 
     fn test_input_2() -> String {
-        "addx 15
-addx -11
-addx 6
-addx -3
-addx 5
-addx -1
-addx -8
-addx 13
-addx 4
-noop
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx -35
-addx 1
-addx 24
-addx -19
-addx 1
-addx 16
-addx -11
-noop
-noop
-addx 21
-addx -15
-noop
-noop
-addx -3
-addx 9
-addx 1
-addx -3
-addx 8
-addx 1
-addx 5
-noop
-noop
-noop
-noop
-noop
-addx -36
-noop
-addx 1
-addx 7
-noop
-noop
-noop
-addx 2
-addx 6
-noop
-noop
-noop
-noop
-noop
-addx 1
-noop
-noop
-addx 7
-addx 1
-noop
-addx -13
-addx 13
-addx 7
-noop
-addx 1
-addx -33
-noop
-noop
-noop
-addx 2
-noop
-noop
-noop
-addx 8
-noop
-addx -1
-addx 2
-addx 1
-noop
-addx 17
-addx -9
-addx 1
-addx 1
-addx -3
-addx 11
-noop
-noop
-addx 1
-noop
-addx 1
-noop
-noop
-addx -13
-addx -19
-addx 1
-addx 3
-addx 26
-addx -30
-addx 12
-addx -1
-addx 3
-addx 1
-noop
-noop
-noop
-addx -9
-addx 18
-addx 1
-addx 2
-noop
-noop
-addx 9
-noop
-noop
-noop
-addx -1
-addx 2
-addx -37
-addx 1
-addx 3
-noop
-addx 15
-addx -21
-addx 22
-addx -6
-addx 1
-noop
-addx 2
-addx 1
-noop
-addx -10
-noop
-noop
-addx 20
-addx 1
-addx 2
-addx 2
-addx -6
-addx -11
-noop
-noop
-noop
-"
-        .to_string()
+        aoc::example_input(2022, 10, 2)
     }
 
     #[test]
     fn parse_test() {
         let input = test_input_1();
         let expected = VecDeque::from([(NoOp, 0), (AddX { v: 3 }, 0), (AddX { v: -5 }, 0)]);
-        assert_eq!(parse_input(&input), expected);
+        assert_eq!(parse_input(&input), Ok(expected));
     }
 
     #[test]
     fn signal_strength_test_1() {
         let input = test_input_1();
-        let instructions = parse_input(&input);
+        let instructions = parse_input(&input).unwrap();
         let mut cpu = Cpu::new_with_instructions(instructions);
         while !cpu.is_completed() {
             cpu.tick();
@@ -337,7 +189,7 @@ noop
     #[test]
     fn signal_strength_test_2() {
         let input = test_input_2();
-        let instructions = parse_input(&input);
+        let instructions = parse_input(&input).unwrap();
         let mut cpu = Cpu::new_with_instructions(instructions);
         // expected values are in reverse order for easy popping
         let mut expected = vec![3960, 2880, 2940, 1800, 1140, 420];
@@ -355,12 +207,6 @@ noop
             }
         }
     }
-
-    #[test]
-    fn part1_test_1() {
-        let input = test_input_2();
-        let instructions = parse_input(&input);
-        let mut cpu = Cpu::new_with_instructions(instructions);
-        assert_eq!(part1(&mut cpu), 13140);
-    }
 }
+
+aoc::example_tests!(Day10, 2022, 10);
@@ -1,198 +1,172 @@
-use std::{fs::File, io::Read};
+use std::fs;
 
-use serde::Deserialize;
+use adventofcode_2022::packet::{divider_positions, Packet, PacketError};
+use aoc::{Error, Solution};
+use parse::{ErrorKind, ParseError, Span};
 
-fn main() -> Result<(), anyhow::Error> {
-    let mut fl = File::open("resources/input13")?;
-    let mut input = String::new();
-    fl.read_to_string(&mut input)?;
-    let packets = parse_input(&input)?;
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("resources/input13")?;
     println!("Day 13");
-    println!("Part 1: {}", part1(&packets));
-    println!("Part 2: {}", part2(packets));
-    Ok(())
+    aoc::solve::<Day13>(&input)
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
-#[serde(untagged)]
-enum Data {
-    Int(i64),
-    List(Vec<Data>),
-}
-
-impl Ord for Data {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        match (self, other) {
-            (Data::Int(i1), Data::Int(i2)) => i1.cmp(i2),
-            (Data::List(v1), Data::List(v2)) => {
-                match v1
-                    .iter()
-                    .zip(v2)
-                    .map(|(d1, d2)| d1.cmp(d2))
-                    .fold(std::cmp::Ordering::Equal, |acc, ord| acc.then(ord))
-                {
-                    std::cmp::Ordering::Equal => {
-                        v1.len().cmp(&v2.len())
-                    }
-                    ord => ord,
-                }
-            }
-            (Data::Int(i1), l2) => Data::List(vec![Data::Int(*i1)]).cmp(l2),
-            (l1, Data::Int(i2)) => l1.cmp(&Data::List(vec![Data::Int(*i2)])),
-        }
-    }
-}
-
-impl PartialOrd for Data {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-fn parse_input(input: &str) -> Result<Vec<Data>, serde_json::Error> {
+fn parse_input(input: &str) -> Result<Vec<Packet>, ParseError> {
     let mut packets = Vec::new();
-    for line in input.trim().lines() {
+    for line in Span::new(input).trim().lines() {
         if line.is_empty() {
             continue;
         }
-        packets.push(serde_json::from_str(line)?);
+        let packet = line
+            .as_str()
+            .parse()
+            .map_err(|err: PacketError| ParseError {
+                line: line.line(),
+                // Everything before the error is ASCII, bytes and characters agree.
+                column: line.column() + err.offset,
+                kind: ErrorKind::Custom(err.kind.to_string()),
+            })?;
+        packets.push(packet);
     }
     Ok(packets)
 }
 
-fn is_correct_order(left: &Data, right: &Data) -> bool {
-    left <= right
+fn is_correct_order(left: &Packet, right: &Packet) -> bool {
+    left.signal_cmp(right).is_le()
 }
 
-fn part1(packets: &[Data]) -> usize {
-    assert_eq!(packets.len() % 2, 0, "must have even number of packets");
-    packets
-        .chunks(2)
-        .map(|pair| is_correct_order(&pair[0], &pair[1]))
+fn part1(packets: &[Packet]) -> aoc::Result<usize> {
+    let pairs = packets.chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return Err(Error::invalid_state(format!(
+            "{} packets can't be split into pairs",
+            packets.len()
+        )));
+    }
+    Ok(pairs
         .enumerate()
-        .filter(|(_, correct_ord)| *correct_ord)
+        .filter(|(_, pair)| is_correct_order(&pair[0], &pair[1]))
         .map(|(idx, _)| idx + 1)
-        .sum()
+        .sum())
 }
 
-fn part2(mut packets: Vec<Data>) -> usize {
-    let decoder_packet_1 = Data::List(vec![Data::List(vec![Data::Int(2)])]);
-    let decoder_packet_2 = Data::List(vec![Data::List(vec![Data::Int(6)])]);
-    packets.push(decoder_packet_1.clone());
-    packets.push(decoder_packet_2.clone());
-    packets.sort_unstable();
-    let idx1 = packets.iter().position(|p| p == &decoder_packet_1).unwrap() + 1;
-    let idx2 = packets.iter().position(|p| p == &decoder_packet_2).unwrap() + 1;
-    idx1 * idx2
+fn part2(packets: &[Packet]) -> usize {
+    let dividers = [
+        Packet::List(vec![Packet::List(vec![Packet::Int(2)])]),
+        Packet::List(vec![Packet::List(vec![Packet::Int(6)])]),
+    ];
+    divider_positions(packets, &dividers).into_iter().product()
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{is_correct_order, parse_input, part1, part2, Data};
+struct Day13;
 
-    fn test_input_1() -> String {
-        "[1,1,3,1,1]
-[1,1,5,1,1]
-
-[[1],[2,3,4]]
-[[1],4]
+impl Solution for Day13 {
+    type Input = Vec<Packet>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-[9]
-[[8,7,6]]
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
 
-[[4,4],4,4]
-[[4,4],4,4,4]
+    fn part1(packets: &Self::Input) -> aoc::Result<usize> {
+        part1(packets)
+    }
 
-[7,7,7,7]
-[7,7,7]
+    fn part2(packets: &Self::Input) -> aoc::Result<usize> {
+        Ok(part2(packets))
+    }
+}
 
-[]
-[3]
+#[cfg(test)]
+mod tests {
+    use adventofcode_2022::packet::Packet;
 
-[[[]]]
-[[]]
+    use crate::{is_correct_order, parse_input};
 
-[1,[2,[3,[4,[5,6,7]]]],8,9]
-[1,[2,[3,[4,[5,6,0]]]],8,9]
-"
-        .to_string()
+    fn test_input_1() -> String {
+        aoc::example_input(2022, 13, 1)
     }
 
     #[test]
     fn parse_test() {
         let input = test_input_1();
         let expected = vec![
-            Data::List(vec![
-                Data::Int(1),
-                Data::Int(1),
-                Data::Int(3),
-                Data::Int(1),
-                Data::Int(1),
+            Packet::List(vec![
+                Packet::Int(1),
+                Packet::Int(1),
+                Packet::Int(3),
+                Packet::Int(1),
+                Packet::Int(1),
             ]),
-            Data::List(vec![
-                Data::Int(1),
-                Data::Int(1),
-                Data::Int(5),
-                Data::Int(1),
-                Data::Int(1),
+            Packet::List(vec![
+                Packet::Int(1),
+                Packet::Int(1),
+                Packet::Int(5),
+                Packet::Int(1),
+                Packet::Int(1),
             ]),
-            Data::List(vec![
-                Data::List(vec![Data::Int(1)]),
-                Data::List(vec![Data::Int(2), Data::Int(3), Data::Int(4)]),
+            Packet::List(vec![
+                Packet::List(vec![Packet::Int(1)]),
+                Packet::List(vec![Packet::Int(2), Packet::Int(3), Packet::Int(4)]),
             ]),
-            Data::List(vec![Data::List(vec![Data::Int(1)]), Data::Int(4)]),
-            Data::List(vec![Data::Int(9)]),
-            Data::List(vec![Data::List(vec![
-                Data::Int(8),
-                Data::Int(7),
-                Data::Int(6),
+            Packet::List(vec![Packet::List(vec![Packet::Int(1)]), Packet::Int(4)]),
+            Packet::List(vec![Packet::Int(9)]),
+            Packet::List(vec![Packet::List(vec![
+                Packet::Int(8),
+                Packet::Int(7),
+                Packet::Int(6),
             ])]),
-            Data::List(vec![
-                Data::List(vec![Data::Int(4), Data::Int(4)]),
-                Data::Int(4),
-                Data::Int(4),
+            Packet::List(vec![
+                Packet::List(vec![Packet::Int(4), Packet::Int(4)]),
+                Packet::Int(4),
+                Packet::Int(4),
             ]),
-            Data::List(vec![
-                Data::List(vec![Data::Int(4), Data::Int(4)]),
-                Data::Int(4),
-                Data::Int(4),
-                Data::Int(4),
+            Packet::List(vec![
+                Packet::List(vec![Packet::Int(4), Packet::Int(4)]),
+                Packet::Int(4),
+                Packet::Int(4),
+                Packet::Int(4),
             ]),
-            Data::List(vec![Data::Int(7), Data::Int(7), Data::Int(7), Data::Int(7)]),
-            Data::List(vec![Data::Int(7), Data::Int(7), Data::Int(7)]),
-            Data::List(vec![]),
-            Data::List(vec![Data::Int(3)]),
-            Data::List(vec![Data::List(vec![Data::List(vec![])])]),
-            Data::List(vec![Data::List(vec![])]),
-            Data::List(vec![
-                Data::Int(1),
-                Data::List(vec![
-                    Data::Int(2),
-                    Data::List(vec![
-                        Data::Int(3),
-                        Data::List(vec![
-                            Data::Int(4),
-                            Data::List(vec![Data::Int(5), Data::Int(6), Data::Int(7)]),
+            Packet::List(vec![
+                Packet::Int(7),
+                Packet::Int(7),
+                Packet::Int(7),
+                Packet::Int(7),
+            ]),
+            Packet::List(vec![Packet::Int(7), Packet::Int(7), Packet::Int(7)]),
+            Packet::List(vec![]),
+            Packet::List(vec![Packet::Int(3)]),
+            Packet::List(vec![Packet::List(vec![Packet::List(vec![])])]),
+            Packet::List(vec![Packet::List(vec![])]),
+            Packet::List(vec![
+                Packet::Int(1),
+                Packet::List(vec![
+                    Packet::Int(2),
+                    Packet::List(vec![
+                        Packet::Int(3),
+                        Packet::List(vec![
+                            Packet::Int(4),
+                            Packet::List(vec![Packet::Int(5), Packet::Int(6), Packet::Int(7)]),
                         ]),
                     ]),
                 ]),
-                Data::Int(8),
-                Data::Int(9),
+                Packet::Int(8),
+                Packet::Int(9),
             ]),
-            Data::List(vec![
-                Data::Int(1),
-                Data::List(vec![
-                    Data::Int(2),
-                    Data::List(vec![
-                        Data::Int(3),
-                        Data::List(vec![
-                            Data::Int(4),
-                            Data::List(vec![Data::Int(5), Data::Int(6), Data::Int(0)]),
+            Packet::List(vec![
+                Packet::Int(1),
+                Packet::List(vec![
+                    Packet::Int(2),
+                    Packet::List(vec![
+                        Packet::Int(3),
+                        Packet::List(vec![
+                            Packet::Int(4),
+                            Packet::List(vec![Packet::Int(5), Packet::Int(6), Packet::Int(0)]),
                         ]),
                     ]),
                 ]),
-                Data::Int(8),
-                Data::Int(9),
+                Packet::Int(8),
+                Packet::Int(9),
             ]),
         ];
         match parse_input(&input) {
@@ -216,16 +190,10 @@ mod tests {
     }
 
     #[test]
-    fn part1_test_1() {
-        let input = test_input_1();
-        let packets = parse_input(&input).unwrap();
-        assert_eq!(part1(&packets), 13);
-    }
-
-    #[test]
-    fn part2_test_1() {
-        let input = test_input_1();
-        let packets = parse_input(&input).unwrap();
-        assert_eq!(part2(packets), 140);
+    fn parse_error_test() {
+        let error = parse_input("[1,2]\n[3,[4,]]\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 7));
     }
 }
+
+aoc::example_tests!(Day13, 2022, 13);
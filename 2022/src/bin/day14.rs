@@ -1,130 +1,119 @@
-use std::{collections::HashMap, fs::File, io::Read};
+use std::fs;
 
-use adventofcode_2022::Position;
+use aoc::Solution;
+use geometry::{SparseGrid, Vector2};
+use parse::{ParseError, Span};
 
-fn main() -> Result<(), anyhow::Error> {
-    let mut fl = File::open("resources/input14")?;
-    let mut input = String::new();
-    fl.read_to_string(&mut input)?;
-    let mut cave = parse_input(&input);
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("resources/input14")?;
     println!("Day 14");
-    println!("Part 1: {}", part1(&mut cave.clone()));
-    println!("Part 2: {}", part2(&mut cave));
-    Ok(())
+    aoc::solve::<Day14>(&input)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Cave {
-    tiles: HashMap<Position, Tile>,
-    bounds: Bounds,
+    tiles: SparseGrid<Tile, 2>,
+    bottom: i64,
     floor: Option<i64>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 enum Tile {
+    #[default]
+    Air,
     Rock,
     Sand,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Bounds {
-    bottom: i64,
-    left: i64,
-    right: i64,
-}
-
 impl Cave {
-    fn drop_sand(&mut self, from: Position) {
+    fn drop_sand(&mut self, from: Vector2<i64>) {
         self.tiles.insert(from, Tile::Sand);
     }
 
-    fn move_sand(&mut self, from: &Position) -> Position {
-        let down = Position::new(from.row + 1, from.col);
-        let down_left = Position::new(from.row + 1, from.col - 1);
-        let down_right = Position::new(from.row + 1, from.col + 1);
-        if self.get(&down).is_none() {
+    fn move_sand(&mut self, from: &Vector2<i64>) -> Vector2<i64> {
+        let down = Vector2::new(from.x, from.y + 1);
+        let down_left = Vector2::new(from.x - 1, from.y + 1);
+        let down_right = Vector2::new(from.x + 1, from.y + 1);
+        if self.get(&down) == Tile::Air {
             self.tiles.insert(down, Tile::Sand);
-            self.tiles.remove(from);
+            self.tiles.remove(*from);
             down
-        } else if self.get(&down_left).is_none() {
+        } else if self.get(&down_left) == Tile::Air {
             self.tiles.insert(down_left, Tile::Sand);
-            self.tiles.remove(from);
+            self.tiles.remove(*from);
             down_left
-        } else if self.get(&down_right).is_none() {
+        } else if self.get(&down_right) == Tile::Air {
             self.tiles.insert(down_right, Tile::Sand);
-            self.tiles.remove(from);
+            self.tiles.remove(*from);
             down_right
         } else {
             *from
         }
     }
 
-    fn is_out_of_bounds(&self, position: &Position) -> bool {
-        self.bounds.bottom < position.row
+    fn is_out_of_bounds(&self, position: &Vector2<i64>) -> bool {
+        self.bottom < position.y
     }
 
     fn add_floor(&mut self, at_row: i64) {
         self.floor = Some(at_row);
     }
 
-    fn get(&mut self, position: &Position) -> Option<&Tile> {
-        if let Some(floor) = self.floor {
-            if position.row == floor {
-                self.tiles.entry(*position).or_insert(Tile::Rock);
-            }
+    fn get(&self, position: &Vector2<i64>) -> Tile {
+        match self.floor {
+            Some(floor) if position.y == floor => Tile::Rock,
+            _ => *self.tiles.get(*position),
         }
-        self.tiles.get(position)
     }
 }
 
-fn parse_input(input: &str) -> Cave {
-    let mut tiles = HashMap::new();
-    let mut bottom = i64::MIN;
-    let mut left = i64::MAX;
-    let mut right = i64::MIN;
-    for line in input.lines() {
+fn parse_input(input: &str) -> Result<Cave, ParseError> {
+    let mut tiles = SparseGrid::new(Tile::Air);
+    for line in Span::new(input).lines().map(|line| line.trim()) {
+        if line.is_empty() {
+            continue;
+        }
         let mut points = line.split(" -> ");
-        let mut p1 = parse_position(points.next().unwrap());
+        let mut p1 = match points.next() {
+            Some(point) => parse_position(point)?,
+            None => return Err(line.expected("a path of points")),
+        };
         for point in points {
-            let p2 = parse_position(point);
-            bottom = bottom.max(p1.row).max(p2.row);
-            left = left.min(p1.col).min(p2.col);
-            right = right.max(p1.col).max(p2.col);
-            if p1.row == p2.row {
+            let p2 = parse_position(point)?;
+            if p1.y == p2.y {
                 tiles.extend(
-                    (p1.col.min(p2.col)..=p1.col.max(p2.col))
-                        .map(|col| (Position::new(p1.row, col), Tile::Rock)),
+                    (p1.x.min(p2.x)..=p1.x.max(p2.x))
+                        .map(|col| (Vector2::new(col, p1.y), Tile::Rock)),
                 );
-            } else {
+            } else if p1.x == p2.x {
                 tiles.extend(
-                    (p1.row.min(p2.row)..=p1.row.max(p2.row))
-                        .map(|row| (Position::new(row, p1.col), Tile::Rock)),
+                    (p1.y.min(p2.y)..=p1.y.max(p2.y))
+                        .map(|row| (Vector2::new(p1.x, row), Tile::Rock)),
                 );
+            } else {
+                return Err(point.expected("a point in line with the one before"));
             }
             p1 = p2;
         }
     }
-    Cave {
+    let bottom = tiles.bounds().map_or(0, |bounds| bounds.max[1]);
+    Ok(Cave {
         tiles,
-        bounds: Bounds {
-            bottom,
-            left,
-            right,
-        },
+        bottom,
         floor: None,
-    }
+    })
 }
 
-fn parse_position(s: &str) -> Position {
-    let (col_s, row_s) = s.split_once(',').unwrap();
-    Position::new(row_s.parse().unwrap(), col_s.parse().unwrap())
+fn parse_position(s: Span) -> Result<Vector2<i64>, ParseError> {
+    let (col_s, row_s) = s.trim().split_once(",")?;
+    Ok(Vector2::new(col_s.parse()?, row_s.parse()?))
 }
 
 fn part1(cave: &mut Cave) -> usize {
     let mut count = 0;
-    let mut from_pos = Position::new(0, 500);
+    let mut from_pos = Vector2::new(500, 0);
     while !cave.is_out_of_bounds(&from_pos) {
-        let start_position = Position::new(0, 500);
+        let start_position = Vector2::new(500, 0);
         from_pos = start_position;
         cave.drop_sand(start_position);
         count += 1;
@@ -141,11 +130,11 @@ fn part1(cave: &mut Cave) -> usize {
 }
 
 fn part2(cave: &mut Cave) -> usize {
-    cave.add_floor(cave.bounds.bottom + 2);
+    cave.add_floor(cave.bottom + 2);
     let mut count = 0;
-    let mut from_pos = Position::new(1, 500);
-    while from_pos != Position::new(0, 500) {
-        let start_position = Position::new(0, 500);
+    let mut from_pos = Vector2::new(500, 1);
+    while from_pos != Vector2::new(500, 0) {
+        let start_position = Vector2::new(500, 0);
         from_pos = start_position;
         cave.drop_sand(start_position);
         count += 1;
@@ -161,68 +150,75 @@ fn part2(cave: &mut Cave) -> usize {
     count
 }
 
+struct Day14;
+
+impl Solution for Day14 {
+    type Input = Cave;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(cave: &Self::Input) -> aoc::Result<usize> {
+        Ok(part1(&mut cave.clone()))
+    }
+
+    fn part2(cave: &Self::Input) -> aoc::Result<usize> {
+        Ok(part2(&mut cave.clone()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
-
-    use adventofcode_2022::Position;
+    use geometry::{SparseGrid, Vector2};
 
-    use crate::{parse_input, part1, part2, Bounds, Cave, Tile};
+    use crate::{parse_input, Cave, Tile};
 
     fn test_input_1() -> String {
-        "498,4 -> 498,6 -> 496,6
-503,4 -> 502,4 -> 502,9 -> 494,9
-"
-        .to_string()
+        aoc::example_input(2022, 14, 1)
     }
 
     #[test]
     fn parse_test() {
         let input = test_input_1();
         let expected = Cave {
-            tiles: HashMap::from([
-                (Position::new(4, 498), Tile::Rock),
-                (Position::new(5, 498), Tile::Rock),
-                (Position::new(6, 498), Tile::Rock),
-                (Position::new(6, 497), Tile::Rock),
-                (Position::new(6, 496), Tile::Rock),
-                (Position::new(4, 503), Tile::Rock),
-                (Position::new(4, 502), Tile::Rock),
-                (Position::new(5, 502), Tile::Rock),
-                (Position::new(6, 502), Tile::Rock),
-                (Position::new(7, 502), Tile::Rock),
-                (Position::new(8, 502), Tile::Rock),
-                (Position::new(9, 502), Tile::Rock),
-                (Position::new(9, 501), Tile::Rock),
-                (Position::new(9, 500), Tile::Rock),
-                (Position::new(9, 499), Tile::Rock),
-                (Position::new(9, 498), Tile::Rock),
-                (Position::new(9, 497), Tile::Rock),
-                (Position::new(9, 496), Tile::Rock),
-                (Position::new(9, 495), Tile::Rock),
-                (Position::new(9, 494), Tile::Rock),
+            tiles: SparseGrid::from_iter([
+                (Vector2::new(498, 4), Tile::Rock),
+                (Vector2::new(498, 5), Tile::Rock),
+                (Vector2::new(498, 6), Tile::Rock),
+                (Vector2::new(497, 6), Tile::Rock),
+                (Vector2::new(496, 6), Tile::Rock),
+                (Vector2::new(503, 4), Tile::Rock),
+                (Vector2::new(502, 4), Tile::Rock),
+                (Vector2::new(502, 5), Tile::Rock),
+                (Vector2::new(502, 6), Tile::Rock),
+                (Vector2::new(502, 7), Tile::Rock),
+                (Vector2::new(502, 8), Tile::Rock),
+                (Vector2::new(502, 9), Tile::Rock),
+                (Vector2::new(501, 9), Tile::Rock),
+                (Vector2::new(500, 9), Tile::Rock),
+                (Vector2::new(499, 9), Tile::Rock),
+                (Vector2::new(498, 9), Tile::Rock),
+                (Vector2::new(497, 9), Tile::Rock),
+                (Vector2::new(496, 9), Tile::Rock),
+                (Vector2::new(495, 9), Tile::Rock),
+                (Vector2::new(494, 9), Tile::Rock),
             ]),
-            bounds: Bounds {
-                bottom: 9,
-                left: 494,
-                right: 503,
-            },
+            bottom: 9,
             floor: None,
         };
-        assert_eq!(parse_input(&input), expected);
+        assert_eq!(parse_input(&input), Ok(expected));
     }
 
     #[test]
-    fn part1_test_1() {
-        let input = test_input_1();
-        let mut cave = parse_input(&input);
-        assert_eq!(part1(&mut cave), 24);
-    }
-
-    #[test]
-    fn part2_test_1() {
-        let input = test_input_1();
-        let mut cave = parse_input(&input);
-        assert_eq!(part2(&mut cave), 93);
+    fn parse_error_test() {
+        let error = parse_input("498,4 -> 498,6\n503,4 -> 502;4").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 10));
+        let error = parse_input("498,4 -> 500,6").unwrap_err();
+        assert_eq!(error.column, 10);
     }
 }
+
+aoc::example_tests!(Day14, 2022, 14);
@@ -0,0 +1,312 @@
+//! Stacks of crates rearranged by a crane, as in 2022 day 5. Stacks are
+//! numbered from 1, like in the puzzle's drawings.
+
+use std::fmt::{self, Display};
+
+use parse::{ErrorKind, ParseError, Span};
+use thiserror::Error;
+
+/// Move the top `quantity` crates of stack `from` onto stack `to`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub quantity: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl Move {
+    pub fn new(quantity: usize, from: usize, to: usize) -> Self {
+        Self { quantity, from, to }
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.quantity, self.from, self.to
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CraneError {
+    #[error("there is no stack {0}")]
+    NoSuchStack(usize),
+    #[error("stack {stack} has {found} crates, {needed} are to be moved")]
+    NotEnoughCrates {
+        stack: usize,
+        needed: usize,
+        found: usize,
+    },
+}
+
+/// How a crane carries crates from one stack to another.
+pub trait Crane {
+    /// Move the top `quantity` crates of `from` onto `to`. `from` holds at
+    /// least that many crates.
+    fn transfer(&self, from: &mut Vec<char>, to: &mut Vec<char>, quantity: usize);
+}
+
+/// Moves crates one at a time, so the moved ones end up in reverse order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn transfer(&self, from: &mut Vec<char>, to: &mut Vec<char>, quantity: usize) {
+        to.extend(from.drain(from.len() - quantity..).rev());
+    }
+}
+
+/// Moves all the crates at once, keeping their order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn transfer(&self, from: &mut Vec<char>, to: &mut Vec<char>, quantity: usize) {
+        to.extend(from.drain(from.len() - quantity..));
+    }
+}
+
+/// The stacks, each listed from the bottom crate up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stacks {
+    stacks: Vec<Vec<char>>,
+}
+
+impl Stacks {
+    pub fn new(stacks: Vec<Vec<char>>) -> Self {
+        Self { stacks }
+    }
+
+    /// Parse a drawing of the stacks with their numbers below:
+    ///
+    /// ```text
+    ///     [D]
+    /// [N] [C]
+    /// [Z] [M] [P]
+    ///  1   2   3
+    /// ```
+    pub fn parse(drawing: Span) -> Result<Self, ParseError> {
+        let (crates, numbers) = drawing
+            .as_str()
+            .rsplit_once('\n')
+            .ok_or_else(|| drawing.expected("crates above the stack numbers"))?;
+        let mut stacks = vec![Vec::new(); numbers.split_whitespace().count()];
+        for line in crates.lines().rev() {
+            for (ch, stack) in line.chars().skip(1).step_by(4).zip(&mut stacks) {
+                if ch != ' ' {
+                    stack.push(ch);
+                }
+            }
+        }
+        if stacks.is_empty() {
+            return Err(drawing.error(ErrorKind::Custom("there are no stacks".into())));
+        }
+        Ok(Self { stacks })
+    }
+
+    pub fn len(&self) -> usize {
+        self.stacks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stacks.is_empty()
+    }
+
+    /// Stack number `stack`, bottom crate first.
+    pub fn stack(&self, stack: usize) -> Option<&[char]> {
+        let index = stack.checked_sub(1)?;
+        self.stacks.get(index).map(Vec::as_slice)
+    }
+
+    /// The crate on top of each stack, a space for empty ones.
+    pub fn tops(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|stack| stack.last().copied().unwrap_or(' '))
+            .collect()
+    }
+
+    /// Carry out `step` with `crane`. Nothing is moved if the step can't be
+    /// carried out in full.
+    pub fn apply(&mut self, crane: &impl Crane, step: Move) -> Result<(), CraneError> {
+        for stack in [step.from, step.to] {
+            if !(1..=self.stacks.len()).contains(&stack) {
+                return Err(CraneError::NoSuchStack(stack));
+            }
+        }
+        let found = self.stacks[step.from - 1].len();
+        if found < step.quantity {
+            return Err(CraneError::NotEnoughCrates {
+                stack: step.from,
+                needed: step.quantity,
+                found,
+            });
+        }
+        // Lifting crates and putting them back down on the same stack leaves
+        // it as it was.
+        if step.from != step.to {
+            let mut from = std::mem::take(&mut self.stacks[step.from - 1]);
+            crane.transfer(&mut from, &mut self.stacks[step.to - 1], step.quantity);
+            self.stacks[step.from - 1] = from;
+        }
+        Ok(())
+    }
+}
+
+/// Draws the stacks the way [`Stacks::parse`] reads them, every line padded
+/// to the full width.
+impl Display for Stacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        for level in (0..height).rev() {
+            let cells: Vec<String> = self
+                .stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(ch) => format!("[{ch}]"),
+                    None => "   ".to_string(),
+                })
+                .collect();
+            writeln!(f, "{}", cells.join(" "))?;
+        }
+        let numbers: Vec<String> = (1..=self.stacks.len())
+            .map(|number| format!(" {number} "))
+            .collect();
+        write!(f, "{}", numbers.join(" "))
+    }
+}
+
+/// Carries out moves one at a time, so the stacks can be looked at between
+/// them. Each item is the move just made, or why it couldn't be.
+#[derive(Debug, Clone)]
+pub struct Replay<'a, C> {
+    stacks: Stacks,
+    crane: C,
+    moves: std::slice::Iter<'a, Move>,
+}
+
+impl<'a, C: Crane> Replay<'a, C> {
+    pub fn new(stacks: Stacks, crane: C, moves: &'a [Move]) -> Self {
+        Self {
+            stacks,
+            crane,
+            moves: moves.iter(),
+        }
+    }
+
+    /// The stacks after the moves made so far.
+    pub fn stacks(&self) -> &Stacks {
+        &self.stacks
+    }
+
+    /// Make the remaining moves and return the final stacks.
+    pub fn finish(mut self) -> Result<Stacks, CraneError> {
+        for step in self.by_ref() {
+            step?;
+        }
+        Ok(self.stacks)
+    }
+}
+
+impl<C: Crane> Iterator for Replay<'_, C> {
+    type Item = Result<Move, CraneError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let step = *self.moves.next()?;
+        Some(self.stacks.apply(&self.crane, step).map(|()| step))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parse::Span;
+
+    use super::{CraneError, CrateMover9000, CrateMover9001, Move, Replay, Stacks};
+
+    const DRAWING: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ";
+
+    fn stacks() -> Stacks {
+        Stacks::parse(Span::new(DRAWING)).unwrap()
+    }
+
+    #[test]
+    fn drawing_test() {
+        let stacks = stacks();
+        assert_eq!(stacks.stack(1), Some(&['Z', 'N'][..]));
+        assert_eq!(stacks.stack(2), Some(&['M', 'C', 'D'][..]));
+        assert_eq!(stacks.stack(4), None);
+        assert_eq!(stacks.to_string(), DRAWING);
+        assert_eq!(Stacks::new(vec![vec![], vec![]]).to_string(), " 1   2 ");
+    }
+
+    #[test]
+    fn display_move_test() {
+        assert_eq!(Move::new(3, 1, 2).to_string(), "move 3 from 1 to 2");
+    }
+
+    #[test]
+    fn crane_test() {
+        let mut one_at_a_time = stacks();
+        one_at_a_time
+            .apply(&CrateMover9000, Move::new(2, 2, 3))
+            .unwrap();
+        assert_eq!(one_at_a_time.stack(3), Some(&['P', 'D', 'C'][..]));
+        let mut all_at_once = stacks();
+        all_at_once
+            .apply(&CrateMover9001, Move::new(2, 2, 3))
+            .unwrap();
+        assert_eq!(all_at_once.stack(3), Some(&['P', 'C', 'D'][..]));
+
+        let mut same_stack = stacks();
+        same_stack
+            .apply(&CrateMover9000, Move::new(2, 2, 2))
+            .unwrap();
+        assert_eq!(same_stack, stacks());
+    }
+
+    #[test]
+    fn invalid_move_test() {
+        let mut stacks = stacks();
+        assert_eq!(
+            stacks.apply(&CrateMover9000, Move::new(2, 3, 1)),
+            Err(CraneError::NotEnoughCrates {
+                stack: 3,
+                needed: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            stacks.apply(&CrateMover9000, Move::new(1, 0, 1)),
+            Err(CraneError::NoSuchStack(0))
+        );
+        assert_eq!(
+            stacks.apply(&CrateMover9001, Move::new(1, 1, 4)),
+            Err(CraneError::NoSuchStack(4))
+        );
+        assert_eq!(stacks.to_string(), DRAWING);
+    }
+
+    #[test]
+    fn replay_test() {
+        let moves = [Move::new(1, 2, 1), Move::new(3, 1, 3), Move::new(4, 1, 2)];
+        let mut replay = Replay::new(stacks(), CrateMover9000, &moves);
+        assert_eq!(replay.next(), Some(Ok(moves[0])));
+        assert_eq!(
+            replay.stacks().to_string(),
+            "[D]        \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 "
+        );
+        assert_eq!(replay.next(), Some(Ok(moves[1])));
+        assert_eq!(replay.stacks().tops(), " CZ");
+        assert_eq!(
+            replay.finish(),
+            Err(CraneError::NotEnoughCrates {
+                stack: 1,
+                needed: 4,
+                found: 0
+            })
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! A grid of tree heights and what each tree can see, as in 2022 day 8.
+//!
+//! Every direction is handled by one sweep per row or column with a stack
+//! of the trees that could still block the view of the ones coming after
+//! them, so every map costs time linear in the number of trees.
+
+use geometry::{Grid, Vector2};
+use parse::{ParseError, Span};
+
+/// The directions to look in, as steps between positions: left, right, up
+/// and down.
+pub const LOOKS: [Vector2<i64>; 4] = [
+    Vector2::new(-1, 0),
+    Vector2::new(1, 0),
+    Vector2::new(0, -1),
+    Vector2::new(0, 1),
+];
+
+/// What a tree sees looking in one direction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct View {
+    /// How many trees it sees, up to and including the first one at least
+    /// as tall as itself.
+    pub distance: usize,
+    /// Whether every tree that way is shorter, so the tree is visible from
+    /// that edge.
+    pub to_edge: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Forest {
+    trees: Grid<u8>,
+}
+
+impl Forest {
+    pub fn new(trees: Grid<u8>) -> Self {
+        Self { trees }
+    }
+
+    /// Parse a map of digit heights, one row per line.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let trees = Span::new(input).grid(|ch| ch.to_digit(10).map(|height| height as u8))?;
+        Ok(Self { trees })
+    }
+
+    pub fn trees(&self) -> &Grid<u8> {
+        &self.trees
+    }
+
+    /// What every tree sees looking along `look`, one of [`LOOKS`].
+    pub fn views(&self, look: Vector2<i64>) -> Grid<View> {
+        let mut views = Grid::from_fn(self.trees.width(), self.trees.height(), |_| View::default());
+        // Each line starts at the edge `look` points to and walks away from
+        // it, so the trees a tree looks at come before it.
+        let starts = self
+            .trees
+            .positions()
+            .filter(|&position| !self.trees.contains(position + look));
+        let mut blockers: Vec<(usize, u8)> = Vec::new();
+        for start in starts {
+            blockers.clear();
+            let line = std::iter::successors(Some(start), |&position| Some(position - look))
+                .take_while(|&position| self.trees.contains(position));
+            for (i, position) in line.enumerate() {
+                let height = self.trees[position];
+                // A tree hides every shorter one behind it from the trees
+                // after it, those are never needed again.
+                while blockers
+                    .last()
+                    .is_some_and(|&(_, blocker)| blocker < height)
+                {
+                    blockers.pop();
+                }
+                views[position] = match blockers.last() {
+                    Some(&(blocker, _)) => View {
+                        distance: i - blocker,
+                        to_edge: false,
+                    },
+                    None => View {
+                        distance: i,
+                        to_edge: true,
+                    },
+                };
+                blockers.push((i, height));
+            }
+        }
+        views
+    }
+
+    /// Whether each tree can be seen from outside the forest.
+    pub fn visibility(&self) -> Grid<bool> {
+        let mut visible = Grid::from_fn(self.trees.width(), self.trees.height(), |_| false);
+        for look in LOOKS {
+            let views = self.views(look);
+            for (is_visible, view) in visible.iter_mut().zip(views.iter()) {
+                *is_visible |= view.to_edge;
+            }
+        }
+        visible
+    }
+
+    /// The scenic score of each tree, the product of its viewing distances.
+    pub fn scenic_scores(&self) -> Grid<usize> {
+        let mut scores = Grid::from_fn(self.trees.width(), self.trees.height(), |_| 1);
+        for look in LOOKS {
+            let views = self.views(look);
+            for (score, view) in scores.iter_mut().zip(views.iter()) {
+                *score *= view.distance;
+            }
+        }
+        scores
+    }
+
+    /// Whether the tree at `position` is visible, by scanning from each edge
+    /// in towards it the way day 8 used to. Quadratic where
+    /// [`Forest::visibility`] is linear, it is kept to check and measure
+    /// that against, so it is only built for tests and the `bench` feature.
+    #[cfg(any(test, feature = "bench"))]
+    pub fn is_visible_by_scan(&self, position: Vector2<i64>) -> bool {
+        let (column, row) = (position.x as usize, position.y as usize);
+        let height = self.trees[position];
+        let shorter = |&other: &u8| other < height;
+        let row_trees = self.trees.row(row);
+        row_trees[..column].iter().all(shorter)
+            || row_trees[column + 1..].iter().all(shorter)
+            || self.trees.column(column).take(row).all(shorter)
+            || self.trees.column(column).skip(row + 1).all(shorter)
+    }
+
+    /// The scenic score of the tree at `position`, looking left and up by
+    /// folding over everything from the edge and right and down by stopping
+    /// at the first blocking tree, the way day 8 used to.
+    #[cfg(any(test, feature = "bench"))]
+    pub fn scenic_score_by_scan(&self, position: Vector2<i64>) -> usize {
+        let (column, row) = (position.x as usize, position.y as usize);
+        let height = self.trees[position];
+        let from_edge = |count, &other: &u8| if other < height { count + 1 } else { 1 };
+        let row_trees = self.trees.row(row);
+        let left = row_trees[..column].iter().fold(0, from_edge);
+        let right = viewing_distance(row_trees[column + 1..].iter(), height);
+        let up = self.trees.column(column).take(row).fold(0, from_edge);
+        let down = viewing_distance(self.trees.column(column).skip(row + 1), height);
+        left * right * up * down
+    }
+}
+
+/// How many of `trees` a tree of `height` sees, nearest first.
+#[cfg(any(test, feature = "bench"))]
+fn viewing_distance<'a>(trees: impl Iterator<Item = &'a u8>, height: u8) -> usize {
+    let mut count = 0;
+    for &other in trees {
+        count += 1;
+        if other >= height {
+            break;
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use geometry::{Grid, Vector2};
+
+    use super::{Forest, View, LOOKS};
+
+    fn forest() -> Forest {
+        Forest::parse("30373\n25512\n65332\n33549\n35390\n").unwrap()
+    }
+
+    #[test]
+    fn views_test() {
+        let forest = forest();
+        let tree = Vector2::new(2, 3);
+        let views: Vec<View> = LOOKS.iter().map(|&look| forest.views(look)[tree]).collect();
+        assert_eq!(
+            views,
+            [
+                View {
+                    distance: 2,
+                    to_edge: true
+                },
+                View {
+                    distance: 2,
+                    to_edge: false
+                },
+                View {
+                    distance: 2,
+                    to_edge: false
+                },
+                View {
+                    distance: 1,
+                    to_edge: true
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_test() {
+        let forest = forest();
+        let tree = Vector2::new(2, 3);
+        assert!(forest.is_visible_by_scan(tree));
+        assert!(!forest.is_visible_by_scan(Vector2::new(3, 1)));
+        assert_eq!(forest.scenic_score_by_scan(tree), 8);
+        assert_eq!(forest.scenic_score_by_scan(Vector2::new(0, 2)), 0);
+    }
+
+    #[test]
+    fn empty_test() {
+        let forest = Forest::new(Grid::new(0, 0, vec![]).unwrap());
+        assert_eq!(forest.visibility().iter().count(), 0);
+        assert_eq!(forest.scenic_scores().iter().count(), 0);
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    //! Checks the sweeps against scanning every line of sight.
+
+    use geometry::Grid;
+    use proptest::prelude::*;
+
+    use super::Forest;
+
+    fn forest() -> impl Strategy<Value = Forest> {
+        (1..8usize, 1..8usize).prop_flat_map(|(width, height)| {
+            prop::collection::vec(0..10u8, width * height)
+                .prop_map(move |trees| Forest::new(Grid::new(width, height, trees).unwrap()))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn visibility_matches_scan(forest in forest()) {
+            let visibility = forest.visibility();
+            for (position, &visible) in visibility.indexed() {
+                prop_assert_eq!(visible, forest.is_visible_by_scan(position));
+            }
+        }
+
+        #[test]
+        fn scenic_scores_match_scan(forest in forest()) {
+            let scores = forest.scenic_scores();
+            for (position, &score) in scores.indexed() {
+                prop_assert_eq!(score, forest.scenic_score_by_scan(position));
+            }
+        }
+    }
+}
@@ -0,0 +1,563 @@
+//! Monkeys throwing items around based on how worried you are about them,
+//! as in 2022 day 11.
+//!
+//! The rules come from [`parse_monkeys`], [`Rounds`] plays them out round
+//! by round and [`inspections_by_item`] follows each item on its own, which
+//! stays fast for any number of rounds. How worry levels are kept is up to a
+//! [`WorryPolicy`].
+
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+use num_bigint::BigUint;
+use parse::{ErrorKind, ParseError, Span};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Old,
+    Number(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Mul,
+}
+
+/// How a monkey changes the worry level of an item it inspects, like
+/// `new = old * 19`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Operation {
+    pub left: Operand,
+    pub operator: Operator,
+    pub right: Operand,
+}
+
+impl Operation {
+    /// The operands with the old worry level filled in, `number` turning
+    /// constants into worry levels.
+    pub fn operands<T: Clone>(&self, old: &T, number: impl Fn(u64) -> T) -> (T, T) {
+        let operand = |operand| match operand {
+            Operand::Old => old.clone(),
+            Operand::Number(n) => number(n),
+        };
+        (operand(self.left), operand(self.right))
+    }
+}
+
+/// Where a monkey throws an item, depending on whether the worry level is
+/// divisible by `divisor`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Test {
+    pub divisor: u64,
+    pub if_true: usize,
+    pub if_false: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monkey {
+    /// The worry levels of the items the monkey starts with.
+    pub items: Vec<u64>,
+    pub operation: Operation,
+    pub test: Test,
+}
+
+/// The rest of the next line of `block`, which must start with `prefix`
+/// once indentation is removed.
+fn field<'a>(
+    block: Span<'a>,
+    lines: &mut impl Iterator<Item = Span<'a>>,
+    prefix: &str,
+) -> Result<Span<'a>, ParseError> {
+    let line = lines
+        .next()
+        .ok_or_else(|| block.expected(format!("a line starting with {prefix:?}")))?;
+    line.trim().strip_prefix(prefix)
+}
+
+fn parse_operand(operand: Span) -> Result<Operand, ParseError> {
+    match operand.as_str() {
+        "old" => Ok(Operand::Old),
+        _ => Ok(Operand::Number(operand.parse()?)),
+    }
+}
+
+fn parse_operation(operation: Span) -> Result<Operation, ParseError> {
+    let expression = operation.strip_prefix("new = ")?;
+    let words: Vec<Span> = expression.words().collect();
+    let [left, operator, right] = words[..] else {
+        return Err(expression.expected("an expression like \"old * 19\""));
+    };
+    let operator = match operator.as_str() {
+        "+" => Operator::Add,
+        "*" => Operator::Mul,
+        _ => return Err(operator.expected("+ or *")),
+    };
+    Ok(Operation {
+        left: parse_operand(left)?,
+        operator,
+        right: parse_operand(right)?,
+    })
+}
+
+/// Parse the monkeys' notes, checking that they are numbered in order and
+/// that every monkey throws to another one that exists.
+pub fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let mut monkeys = Vec::new();
+    let mut targets = Vec::new();
+    for block in Span::new(input).blocks() {
+        let mut lines = block.lines();
+        let number = field(block, &mut lines, "Monkey ")?;
+        let number_span = number.strip_suffix(":")?;
+        if number_span.parse::<usize>()? != monkeys.len() {
+            return Err(number_span.expected(format!("monkey {}", monkeys.len())));
+        }
+        let items = field(block, &mut lines, "Starting items:")?;
+        let items = if items.trim().is_empty() {
+            Vec::new()
+        } else {
+            items.parse_list(",")?
+        };
+        let operation = parse_operation(field(block, &mut lines, "Operation: ")?)?;
+        let divisor_span = field(block, &mut lines, "Test: divisible by ")?;
+        let divisor = divisor_span.parse()?;
+        if divisor == 0 {
+            return Err(divisor_span.expected("a positive divisor"));
+        }
+        let if_true_span = field(block, &mut lines, "If true: throw to monkey ")?;
+        let if_false_span = field(block, &mut lines, "If false: throw to monkey ")?;
+        if let Some(extra) = lines.next() {
+            return Err(extra.expected("the end of the monkey's notes"));
+        }
+        let test = Test {
+            divisor,
+            if_true: if_true_span.parse()?,
+            if_false: if_false_span.parse()?,
+        };
+        targets.push((monkeys.len(), if_true_span, test.if_true));
+        targets.push((monkeys.len(), if_false_span, test.if_false));
+        monkeys.push(Monkey {
+            items,
+            operation,
+            test,
+        });
+    }
+    for (monkey, span, target) in targets {
+        if target == monkey || target >= monkeys.len() {
+            return Err(span.error(ErrorKind::Custom(format!(
+                "monkey {monkey} can't throw to monkey {target}"
+            ))));
+        }
+    }
+    Ok(monkeys)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("a worry level got too large while monkey {monkey} inspected it")]
+pub struct WorryOverflow {
+    pub monkey: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("the least common multiple of the monkeys' divisors does not fit in 64 bits")]
+pub struct ModulusOverflow;
+
+/// How worry levels are represented and kept in check.
+pub trait WorryPolicy {
+    type Level: Clone;
+
+    /// The level an item starts at.
+    fn level(&self, start: u64) -> Self::Level;
+    /// The level after the inspection `operation`, `None` if it doesn't fit
+    /// in a `Level`.
+    fn inspect(&self, operation: &Operation, level: &Self::Level) -> Option<Self::Level>;
+    fn is_divisible(&self, level: &Self::Level, divisor: u64) -> bool;
+}
+
+/// The relief of an item not being damaged divides the worry level by 3
+/// after every inspection.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Relief;
+
+impl WorryPolicy for Relief {
+    type Level = u64;
+
+    fn level(&self, start: u64) -> u64 {
+        start
+    }
+
+    fn inspect(&self, operation: &Operation, level: &u64) -> Option<u64> {
+        let (left, right) = operation.operands(level, |n| n);
+        let new = match operation.operator {
+            Operator::Add => left.checked_add(right),
+            Operator::Mul => left.checked_mul(right),
+        };
+        new.map(|new| new / 3)
+    }
+
+    fn is_divisible(&self, level: &u64, divisor: u64) -> bool {
+        level.is_multiple_of(divisor)
+    }
+}
+
+/// No relief, worry levels are kept modulo a multiple of every divisor the
+/// monkeys test with, which leaves the outcome of every test as it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Modular {
+    modulus: u64,
+}
+
+impl Modular {
+    /// Levels modulo the least common multiple of the monkeys' divisors.
+    pub fn for_monkeys(monkeys: &[Monkey]) -> Result<Self, ModulusOverflow> {
+        fn gcd(a: u64, b: u64) -> u64 {
+            if b == 0 {
+                a
+            } else {
+                gcd(b, a % b)
+            }
+        }
+        let modulus = monkeys
+            .iter()
+            .map(|monkey| monkey.test.divisor)
+            .try_fold(1u64, |lcm, divisor| {
+                (lcm / gcd(lcm, divisor)).checked_mul(divisor)
+            })
+            .ok_or(ModulusOverflow)?;
+        Ok(Self { modulus })
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+}
+
+impl WorryPolicy for Modular {
+    type Level = u64;
+
+    fn level(&self, start: u64) -> u64 {
+        start % self.modulus
+    }
+
+    fn inspect(&self, operation: &Operation, level: &u64) -> Option<u64> {
+        let modulus = u128::from(self.modulus);
+        let (left, right) = operation.operands(&u128::from(*level), |n| u128::from(n) % modulus);
+        let new = match operation.operator {
+            Operator::Add => left + right,
+            Operator::Mul => left * right,
+        };
+        u64::try_from(new % modulus).ok()
+    }
+
+    fn is_divisible(&self, level: &u64, divisor: u64) -> bool {
+        level.is_multiple_of(divisor)
+    }
+}
+
+/// No relief and no shortcuts, the worry levels are exact. Only usable for
+/// a few rounds, but good for checking the other policies against.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Exact;
+
+impl WorryPolicy for Exact {
+    type Level = BigUint;
+
+    fn level(&self, start: u64) -> BigUint {
+        BigUint::from(start)
+    }
+
+    fn inspect(&self, operation: &Operation, level: &BigUint) -> Option<BigUint> {
+        let (left, right) = operation.operands(level, BigUint::from);
+        Some(match operation.operator {
+            Operator::Add => &left + &right,
+            Operator::Mul => &left * &right,
+        })
+    }
+
+    fn is_divisible(&self, level: &BigUint, divisor: u64) -> bool {
+        level % divisor == BigUint::ZERO
+    }
+}
+
+/// Where an inspected item goes and its new worry level.
+fn throw<P: WorryPolicy>(
+    monkeys: &[Monkey],
+    policy: &P,
+    monkey: usize,
+    level: &P::Level,
+) -> Result<(usize, P::Level), WorryOverflow> {
+    let Monkey {
+        operation, test, ..
+    } = &monkeys[monkey];
+    let level = policy
+        .inspect(operation, level)
+        .ok_or(WorryOverflow { monkey })?;
+    let target = if policy.is_divisible(&level, test.divisor) {
+        test.if_true
+    } else {
+        test.if_false
+    };
+    Ok((target, level))
+}
+
+/// Plays out rounds in which every monkey in turn inspects and throws all
+/// the items it holds. Each item is the number of items each monkey has
+/// inspected so far.
+#[derive(Debug, Clone)]
+pub struct Rounds<'a, P: WorryPolicy> {
+    monkeys: &'a [Monkey],
+    policy: P,
+    items: Vec<VecDeque<P::Level>>,
+    inspections: Vec<u64>,
+}
+
+impl<'a, P: WorryPolicy> Rounds<'a, P> {
+    pub fn new(monkeys: &'a [Monkey], policy: P) -> Self {
+        let items = monkeys
+            .iter()
+            .map(|monkey| {
+                monkey
+                    .items
+                    .iter()
+                    .map(|&item| policy.level(item))
+                    .collect()
+            })
+            .collect();
+        Self {
+            monkeys,
+            policy,
+            items,
+            inspections: vec![0; monkeys.len()],
+        }
+    }
+
+    /// How many items each monkey has inspected so far.
+    pub fn inspections(&self) -> &[u64] {
+        &self.inspections
+    }
+
+    /// The worry levels of the items `monkey` holds.
+    pub fn items(&self, monkey: usize) -> impl Iterator<Item = &P::Level> {
+        self.items[monkey].iter()
+    }
+
+    fn round(&mut self) -> Result<(), WorryOverflow> {
+        for monkey in 0..self.monkeys.len() {
+            while let Some(level) = self.items[monkey].pop_front() {
+                self.inspections[monkey] += 1;
+                let (target, level) = throw(self.monkeys, &self.policy, monkey, &level)?;
+                self.items[target].push_back(level);
+            }
+        }
+        Ok(())
+    }
+
+    /// Play out `rounds` more rounds.
+    pub fn run(&mut self, rounds: usize) -> Result<&[u64], WorryOverflow> {
+        for _ in 0..rounds {
+            self.round()?;
+        }
+        Ok(&self.inspections)
+    }
+}
+
+impl<P: WorryPolicy> Iterator for Rounds<'_, P> {
+    type Item = Result<Vec<u64>, WorryOverflow>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.round().map(|()| self.inspections.clone()))
+    }
+}
+
+/// How many items each monkey inspects in `rounds` rounds, found by
+/// following each item on its own: how often a monkey inspects an item
+/// doesn't depend on the other items.
+///
+/// An item is back where it was once it starts a round at the same monkey
+/// with the same worry level, from there on it repeats itself. That makes
+/// this fast for any number of rounds with [`Modular`] levels, but with
+/// [`Exact`] ones an item never repeats.
+pub fn inspections_by_item<P>(
+    monkeys: &[Monkey],
+    policy: P,
+    rounds: u64,
+) -> Result<Vec<u64>, WorryOverflow>
+where
+    P: WorryPolicy,
+    P::Level: Hash + Eq,
+{
+    let mut total = vec![0; monkeys.len()];
+    for (start, monkey) in monkeys.iter().enumerate() {
+        for &item in &monkey.items {
+            let counts = item_inspections(monkeys, &policy, start, policy.level(item), rounds)?;
+            for (total, count) in total.iter_mut().zip(counts) {
+                *total += count;
+            }
+        }
+    }
+    Ok(total)
+}
+
+/// How often each monkey inspects an item starting at `holder` in `rounds`
+/// rounds.
+fn item_inspections<P>(
+    monkeys: &[Monkey],
+    policy: &P,
+    mut holder: usize,
+    mut level: P::Level,
+    rounds: u64,
+) -> Result<Vec<u64>, WorryOverflow>
+where
+    P: WorryPolicy,
+    P::Level: Hash + Eq,
+{
+    let mut seen = HashMap::new();
+    // The counts at the start of each round so far.
+    let mut history = vec![vec![0; monkeys.len()]];
+    for round in 0..rounds {
+        let counts = &history[round as usize];
+        if let Some(&start) = seen.get(&(holder, level.clone())) {
+            let period = round - start;
+            let (cycles, rest) = ((rounds - round) / period, (rounds - round) % period);
+            let start_counts = &history[start as usize];
+            let rest_counts = &history[(start + rest) as usize];
+            return Ok((0..monkeys.len())
+                .map(|m| {
+                    let per_cycle = counts[m] - start_counts[m];
+                    counts[m] + cycles * per_cycle + (rest_counts[m] - start_counts[m])
+                })
+                .collect());
+        }
+        seen.insert((holder, level.clone()), round);
+        let mut counts = counts.clone();
+        // Monkeys take their turns in order, an item thrown ahead is
+        // inspected again in the same round.
+        loop {
+            counts[holder] += 1;
+            let (target, new_level) = throw(monkeys, policy, holder, &level)?;
+            let next_round = target < holder;
+            (holder, level) = (target, new_level);
+            if next_round {
+                break;
+            }
+        }
+        history.push(counts);
+    }
+    Ok(history.pop().unwrap_or_default())
+}
+
+/// The product of the two highest inspection counts.
+pub fn monkey_business(inspections: &[u64]) -> u64 {
+    let mut inspections = inspections.to_vec();
+    inspections.sort_unstable_by(|a, b| b.cmp(a));
+    inspections.iter().take(2).product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        inspections_by_item, monkey_business, parse_monkeys, Exact, Modular, ModulusOverflow,
+        Monkey, Operand, Operation, Operator, Relief, Rounds, Test,
+    };
+
+    fn test_input_1() -> String {
+        aoc::example_input(2022, 11, 1)
+    }
+
+    fn monkeys() -> Vec<Monkey> {
+        parse_monkeys(&test_input_1()).unwrap()
+    }
+
+    #[test]
+    fn parse_test() {
+        let monkeys = monkeys();
+        assert_eq!(monkeys.len(), 4);
+        assert_eq!(
+            monkeys[2],
+            Monkey {
+                items: vec![79, 60, 97],
+                operation: Operation {
+                    left: Operand::Old,
+                    operator: Operator::Mul,
+                    right: Operand::Old
+                },
+                test: Test {
+                    divisor: 13,
+                    if_true: 1,
+                    if_false: 3
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let error = parse_monkeys(&test_input_1().replace("old + 6", "old - 6")).unwrap_err();
+        assert_eq!((error.line, error.column), (10, 24));
+        let error =
+            parse_monkeys(&test_input_1().replace("monkey 3\n\nMonkey 1", "monkey 7\n\nMonkey 1"))
+                .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 6, column 31: monkey 0 can't throw to monkey 7"
+        );
+        let error = parse_monkeys(&test_input_1().replace("Monkey 2:", "Monkey 5:")).unwrap_err();
+        assert_eq!((error.line, error.column), (15, 8));
+        let error = parse_monkeys(&test_input_1().replace("    If false: throw to monkey 1\n", ""))
+            .unwrap_err();
+        assert_eq!(error.line, 22);
+    }
+
+    #[test]
+    fn rounds_test() {
+        let monkeys = monkeys();
+        let mut rounds = Rounds::new(&monkeys, Relief);
+        assert_eq!(rounds.next(), Some(Ok(vec![2, 4, 3, 5])));
+        assert_eq!(
+            rounds.items(0).copied().collect::<Vec<_>>(),
+            [20, 23, 27, 26]
+        );
+        assert_eq!(rounds.run(19), Ok(&[101, 95, 7, 105][..]));
+        assert_eq!(monkey_business(rounds.inspections()), 10605);
+    }
+
+    #[test]
+    fn policies_agree_test() {
+        let monkeys = monkeys();
+        let modular = Modular::for_monkeys(&monkeys).unwrap();
+        assert_eq!(modular.modulus(), 23 * 19 * 13 * 17);
+        let exact = Rounds::new(&monkeys, Exact).nth(19).unwrap();
+        assert_eq!(exact, Ok(vec![99, 97, 8, 103]));
+        assert_eq!(Rounds::new(&monkeys, modular).nth(19).unwrap(), exact);
+        assert_eq!(inspections_by_item(&monkeys, modular, 20), exact);
+    }
+
+    #[test]
+    fn by_item_test() {
+        let monkeys = monkeys();
+        let modular = Modular::for_monkeys(&monkeys).unwrap();
+        let by_round = Rounds::new(&monkeys, modular).run(1000).unwrap().to_vec();
+        assert_eq!(by_round, [5204, 4792, 199, 5192]);
+        assert_eq!(inspections_by_item(&monkeys, modular, 1000), Ok(by_round));
+        assert_eq!(
+            inspections_by_item(&monkeys, Relief, 20),
+            Ok(vec![101, 95, 7, 105])
+        );
+    }
+
+    #[test]
+    fn modulus_overflow_test() {
+        let mut monkeys = monkeys();
+        for (monkey, divisor) in monkeys.iter_mut().zip([4_294_967_291, 4_294_967_279, 2, 3]) {
+            monkey.test.divisor = divisor;
+        }
+        assert_eq!(
+            Modular::for_monkeys(&monkeys[..2]).map(|m| m.modulus()),
+            Ok(4_294_967_291 * 4_294_967_279)
+        );
+        assert_eq!(Modular::for_monkeys(&monkeys[..3]), Err(ModulusOverflow));
+    }
+}
@@ -0,0 +1,379 @@
+//! The nested lists of integers sent by the distress signal of 2022 day 13,
+//! written like `[1,[2,[]],3]`.
+
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    slice,
+    str::FromStr,
+};
+
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Packet {
+    Int(u32),
+    List(Vec<Packet>),
+}
+
+impl Packet {
+    /// The items to compare with those of a list: a list's own, or the
+    /// integer itself as the only item.
+    fn items(&self) -> &[Packet] {
+        match self {
+            Packet::Int(_) => slice::from_ref(self),
+            Packet::List(items) => items,
+        }
+    }
+
+    /// The order of the puzzle: integers compare by value, lists item by
+    /// item with the shorter list first if one runs out. An integer compared
+    /// with a list is treated as a list of just that integer, so `2` and
+    /// `[[2]]` are in the same place.
+    pub fn signal_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Int(left), Packet::Int(right)) => left.cmp(right),
+            _ => self
+                .items()
+                .iter()
+                .zip(other.items())
+                .map(|(left, right)| left.signal_cmp(right))
+                .find(|ord| ord.is_ne())
+                .unwrap_or_else(|| self.items().len().cmp(&other.items().len())),
+        }
+    }
+
+    /// Tells apart packets the puzzle order puts in the same place, an
+    /// integer before any list.
+    fn structure_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Int(left), Packet::Int(right)) => left.cmp(right),
+            (Packet::Int(_), Packet::List(_)) => Ordering::Less,
+            (Packet::List(_), Packet::Int(_)) => Ordering::Greater,
+            (Packet::List(left), Packet::List(right)) => left
+                .iter()
+                .zip(right)
+                .map(|(left, right)| left.structure_cmp(right))
+                .find(|ord| ord.is_ne())
+                .unwrap_or_else(|| left.len().cmp(&right.len())),
+        }
+    }
+}
+
+/// The puzzle order, see [`Packet::signal_cmp`], with ties between packets
+/// that aren't equal broken so that it agrees with `==`.
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.signal_cmp(other)
+            .then_with(|| self.structure_cmp(other))
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Packet::Int(value) => write!(f, "{value}"),
+            Packet::List(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_str("]")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PacketErrorKind {
+    #[error("unexpected end of packet, expected {0}")]
+    UnexpectedEnd(&'static str),
+    #[error("unexpected {found:?}, expected {expected}")]
+    Unexpected { found: char, expected: &'static str },
+    #[error("integer does not fit in 32 bits")]
+    IntTooLarge,
+    #[error("lists nested more than {MAX_DEPTH} deep")]
+    TooDeep,
+}
+
+/// A malformed packet, `offset` being the byte where parsing failed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("at offset {offset}: {kind}")]
+pub struct PacketError {
+    pub offset: usize,
+    pub kind: PacketErrorKind,
+}
+
+/// How deep lists may nest in a packet. The parser and the comparisons
+/// recurse once per level, so this keeps hostile input from overflowing the
+/// stack.
+pub const MAX_DEPTH: usize = 256;
+
+/// Reads a packet straight off the bytes of the text.
+struct Parser<'a> {
+    text: &'a str,
+    offset: usize,
+    // lists currently open
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.offset).copied()
+    }
+
+    fn error(&self, expected: &'static str) -> PacketError {
+        // Only ASCII is ever consumed, so the offset is on a char boundary.
+        let kind = match self.text[self.offset..].chars().next() {
+            Some(found) => PacketErrorKind::Unexpected { found, expected },
+            None => PacketErrorKind::UnexpectedEnd(expected),
+        };
+        PacketError {
+            offset: self.offset,
+            kind,
+        }
+    }
+
+    fn packet(&mut self) -> Result<Packet, PacketError> {
+        match self.peek() {
+            Some(b'[') => self.list(),
+            Some(b'0'..=b'9') => self.int(),
+            _ => Err(self.error("'[' or a digit")),
+        }
+    }
+
+    fn int(&mut self) -> Result<Packet, PacketError> {
+        let start = self.offset;
+        let mut value: u32 = 0;
+        while let Some(digit @ b'0'..=b'9') = self.peek() {
+            value = value
+                .checked_mul(10)
+                .and_then(|value| value.checked_add(u32::from(digit - b'0')))
+                .ok_or(PacketError {
+                    offset: start,
+                    kind: PacketErrorKind::IntTooLarge,
+                })?;
+            self.offset += 1;
+        }
+        Ok(Packet::Int(value))
+    }
+
+    fn list(&mut self) -> Result<Packet, PacketError> {
+        if self.depth == MAX_DEPTH {
+            return Err(PacketError {
+                offset: self.offset,
+                kind: PacketErrorKind::TooDeep,
+            });
+        }
+        self.depth += 1;
+        let list = self.items();
+        self.depth -= 1;
+        list
+    }
+
+    fn items(&mut self) -> Result<Packet, PacketError> {
+        self.offset += 1;
+        let mut items = Vec::new();
+        if self.peek() == Some(b']') {
+            self.offset += 1;
+            return Ok(Packet::List(items));
+        }
+        loop {
+            items.push(self.packet()?);
+            match self.peek() {
+                Some(b',') => self.offset += 1,
+                Some(b']') => {
+                    self.offset += 1;
+                    return Ok(Packet::List(items));
+                }
+                _ => return Err(self.error("',' or ']'")),
+            }
+        }
+    }
+}
+
+impl FromStr for Packet {
+    type Err = PacketError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            text: s,
+            offset: 0,
+            depth: 0,
+        };
+        let packet = parser.packet()?;
+        if parser.offset < s.len() {
+            return Err(parser.error("the end of the packet"));
+        }
+        Ok(packet)
+    }
+}
+
+/// The 1-based positions the `dividers` end up at when sorted together with
+/// `packets`, found by counting what sorts before each of them.
+pub fn divider_positions(packets: &[Packet], dividers: &[Packet]) -> Vec<usize> {
+    dividers
+        .iter()
+        .enumerate()
+        .map(|(i, divider)| {
+            let packets_before = packets
+                .iter()
+                .filter(|packet| packet.signal_cmp(divider).is_lt())
+                .count();
+            let dividers_before = dividers
+                .iter()
+                .enumerate()
+                .filter(|&(j, other)| match other.signal_cmp(divider) {
+                    Ordering::Less => true,
+                    Ordering::Equal => j < i,
+                    Ordering::Greater => false,
+                })
+                .count();
+            1 + packets_before + dividers_before
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::{divider_positions, Packet, PacketError, PacketErrorKind, MAX_DEPTH};
+
+    fn packet(s: &str) -> Packet {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(
+            packet("[1,[2,[]],10]"),
+            Packet::List(vec![
+                Packet::Int(1),
+                Packet::List(vec![Packet::Int(2), Packet::List(vec![])]),
+                Packet::Int(10),
+            ])
+        );
+        assert_eq!(packet("7"), Packet::Int(7));
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let error = |s: &str| s.parse::<Packet>().unwrap_err();
+        assert_eq!(
+            error("[1,,2]"),
+            PacketError {
+                offset: 3,
+                kind: PacketErrorKind::Unexpected {
+                    found: ',',
+                    expected: "'[' or a digit"
+                }
+            }
+        );
+        assert_eq!(
+            error("[[1]"),
+            PacketError {
+                offset: 4,
+                kind: PacketErrorKind::UnexpectedEnd("',' or ']'")
+            }
+        );
+        assert_eq!(error("[1]]").offset, 3);
+        assert_eq!(error("[1 ]").offset, 2);
+        assert_eq!(
+            error("[4294967296]"),
+            PacketError {
+                offset: 1,
+                kind: PacketErrorKind::IntTooLarge
+            }
+        );
+        let nested = |depth| "[".repeat(depth) + &"]".repeat(depth);
+        assert!(nested(MAX_DEPTH).parse::<Packet>().is_ok());
+        assert_eq!(
+            error(&nested(MAX_DEPTH + 1)),
+            PacketError {
+                offset: MAX_DEPTH,
+                kind: PacketErrorKind::TooDeep
+            }
+        );
+        assert_eq!(error(&"[".repeat(1 << 20)).kind, PacketErrorKind::TooDeep);
+    }
+
+    #[test]
+    fn order_test() {
+        assert!(packet("[1,1,3,1,1]") < packet("[1,1,5,1,1]"));
+        assert!(packet("[[1],[2,3,4]]") < packet("[[1],4]"));
+        assert!(packet("[9]") > packet("[[8,7,6]]"));
+        assert!(packet("[[4,4],4,4]") < packet("[[4,4],4,4,4]"));
+        assert!(packet("[[[]]]") > packet("[[]]"));
+        assert_eq!(packet("[[2]]").signal_cmp(&packet("2")), Ordering::Equal);
+        assert!(packet("2") < packet("[[2]]"));
+    }
+
+    #[test]
+    fn divider_positions_test() {
+        let packets = [packet("[1]"), packet("[3]"), packet("[7]")];
+        let dividers = [packet("[[6]]"), packet("[[2]]")];
+        assert_eq!(divider_positions(&packets, &dividers), [4, 2]);
+        let same = [packet("[2]"), packet("[[2]]")];
+        assert_eq!(divider_positions(&packets, &same), [2, 3]);
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    //! Round trips random packets through their text form, and throws random
+    //! text at the parser.
+
+    use proptest::prelude::*;
+
+    use super::{Packet, PacketErrorKind, MAX_DEPTH};
+
+    fn packet() -> impl Strategy<Value = Packet> {
+        let int = any::<u32>().prop_map(Packet::Int);
+        int.prop_recursive(6, 64, 8, |inner| {
+            prop::collection::vec(inner, 0..8).prop_map(Packet::List)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trips(p in packet()) {
+            prop_assert_eq!(p.to_string().parse::<Packet>(), Ok(p));
+        }
+
+        #[test]
+        fn parse_never_panics(s in r"[\[\],0-9 x]{0,400}") {
+            if let Err(err) = s.parse::<Packet>() {
+                prop_assert!(err.offset <= s.len());
+            }
+        }
+
+        #[test]
+        fn nesting_is_limited(depth in 0..MAX_DEPTH * 2, inner in r"[,0-9]{0,8}") {
+            let s = "[".repeat(depth) + &inner + &"]".repeat(depth);
+            match s.parse::<Packet>() {
+                Err(err) if err.kind == PacketErrorKind::TooDeep => {
+                    prop_assert!(depth > MAX_DEPTH)
+                }
+                Err(_) => {}
+                Ok(_) => prop_assert!(depth <= MAX_DEPTH),
+            }
+        }
+
+        #[test]
+        fn order_is_antisymmetric(a in packet(), b in packet()) {
+            prop_assert_eq!(a.signal_cmp(&b), b.signal_cmp(&a).reverse());
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            prop_assert_eq!(a.cmp(&b).is_eq(), a == b);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! A rope of knots dragged around by its head, as in 2022 day 9. `y` grows
+//! upwards, and every knot starts at the origin.
+
+use std::{collections::HashSet, slice};
+
+use geometry::{Bounds, Vector2};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rope {
+    knots: Vec<Vector2<i64>>,
+    visited: Vec<HashSet<Vector2<i64>>>,
+}
+
+impl Rope {
+    /// A rope of `knots` knots, the head included.
+    ///
+    /// # Panics
+    ///
+    /// If `knots` is 0.
+    pub fn new(knots: usize) -> Self {
+        assert!(knots > 0, "a rope needs at least a head");
+        Self {
+            knots: vec![Vector2::ZERO; knots],
+            visited: vec![HashSet::from([Vector2::ZERO]); knots],
+        }
+    }
+
+    /// The positions of the knots, head first.
+    pub fn knots(&self) -> &[Vector2<i64>] {
+        &self.knots
+    }
+
+    pub fn head(&self) -> Vector2<i64> {
+        self.knots[0]
+    }
+
+    pub fn tail(&self) -> Vector2<i64> {
+        self.knots[self.knots.len() - 1]
+    }
+
+    /// Every position knot `knot` has been at, `None` if there is no such
+    /// knot.
+    pub fn visited(&self, knot: usize) -> Option<&HashSet<Vector2<i64>>> {
+        self.visited.get(knot)
+    }
+
+    /// Move the head by `step`, at most one in each direction, and let every
+    /// other knot follow the one ahead of it.
+    pub fn step(&mut self, step: Vector2<i64>) {
+        debug_assert!(step.chebyshev_norm() <= 1, "{step:?} is more than one step");
+        self.knots[0] += step;
+        self.visited[0].insert(self.knots[0]);
+        for i in 1..self.knots.len() {
+            let (ahead, knot) = (self.knots[i - 1], self.knots[i]);
+            if ahead.chebyshev_distance(knot) < 2 {
+                // Knots only move when the one ahead does.
+                break;
+            }
+            self.knots[i] = knot + (ahead - knot).signum();
+            self.visited[i].insert(self.knots[i]);
+        }
+    }
+
+    /// What knot `knot` is drawn as: `H` for the head, then numbers counting
+    /// back from it, or `T` for the tail of a two knot rope.
+    fn label(&self, knot: usize) -> char {
+        match knot {
+            0 => 'H',
+            1 if self.knots.len() == 2 => 'T',
+            _ => char::from_digit(knot as u32, 36).unwrap_or('?'),
+        }
+    }
+
+    /// Draw the part of the plane in `bounds` the way the puzzle does, top
+    /// row first. Where knots overlap the one nearest to the head is drawn,
+    /// `s` marks the start if no knot is on it.
+    pub fn frame(&self, bounds: Bounds<2>) -> String {
+        render(bounds, |position| {
+            match self.knots.iter().position(|&knot| knot == position) {
+                Some(knot) => self.label(knot),
+                None if position == Vector2::ZERO => 's',
+                None => '.',
+            }
+        })
+    }
+
+    /// Draw the positions knot `knot` has visited in `bounds` with `#`, the
+    /// start with `s`.
+    ///
+    /// # Panics
+    ///
+    /// If there is no such knot.
+    pub fn visited_frame(&self, knot: usize, bounds: Bounds<2>) -> String {
+        let visited = &self.visited[knot];
+        render(bounds, |position| {
+            if position == Vector2::ZERO {
+                's'
+            } else if visited.contains(&position) {
+                '#'
+            } else {
+                '.'
+            }
+        })
+    }
+}
+
+fn render(bounds: Bounds<2>, cell: impl Fn(Vector2<i64>) -> char) -> String {
+    let Bounds {
+        min: [min_x, min_y],
+        max: [max_x, max_y],
+    } = bounds;
+    let rows: Vec<String> = (min_y..=max_y)
+        .rev()
+        .map(|y| (min_x..=max_x).map(|x| cell(Vector2::new(x, y))).collect())
+        .collect();
+    rows.join("\n")
+}
+
+/// Drags a rope through a list of moves one step at a time, yielding the
+/// positions of the knots after each step.
+///
+/// Every move is a straight horizontal, vertical or diagonal line.
+#[derive(Debug, Clone)]
+pub struct Steps<'a> {
+    rope: Rope,
+    moves: slice::Iter<'a, Vector2<i64>>,
+    step: Vector2<i64>,
+    remaining: i64,
+}
+
+impl<'a> Steps<'a> {
+    pub fn new(rope: Rope, moves: &'a [Vector2<i64>]) -> Self {
+        Self {
+            rope,
+            moves: moves.iter(),
+            step: Vector2::ZERO,
+            remaining: 0,
+        }
+    }
+
+    /// The rope after the steps taken so far.
+    pub fn rope(&self) -> &Rope {
+        &self.rope
+    }
+
+    /// Take the remaining steps and return the rope.
+    pub fn finish(mut self) -> Rope {
+        self.by_ref().for_each(drop);
+        self.rope
+    }
+}
+
+impl Iterator for Steps<'_> {
+    type Item = Vec<Vector2<i64>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.remaining == 0 {
+            let next = *self.moves.next()?;
+            self.step = next.signum();
+            self.remaining = next.chebyshev_norm();
+        }
+        self.remaining -= 1;
+        self.rope.step(self.step);
+        Some(self.rope.knots.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geometry::{Bounds, Vector2};
+
+    use super::{Rope, Steps};
+
+    fn small() -> Bounds<2> {
+        Bounds::new([0, 0], [5, 4])
+    }
+
+    #[test]
+    fn frame_test() {
+        let moves = [Vector2::new(4, 0), Vector2::new(0, 4)];
+        let mut steps = Steps::new(Rope::new(2), &moves);
+        let frames = [
+            "......\n......\n......\n......\nTH....",
+            "......\n......\n......\n......\nsTH...",
+            "......\n......\n......\n......\ns.TH..",
+            "......\n......\n......\n......\ns..TH.",
+            "......\n......\n......\n....H.\ns..T..",
+            "......\n......\n....H.\n....T.\ns.....",
+            "......\n....H.\n....T.\n......\ns.....",
+            "....H.\n....T.\n......\n......\ns.....",
+        ];
+        assert_eq!(
+            steps.rope().frame(small()),
+            "......\n......\n......\n......\nH....."
+        );
+        for frame in frames {
+            assert!(steps.next().is_some());
+            assert_eq!(steps.rope().frame(small()), frame);
+        }
+        assert_eq!(steps.next(), None);
+    }
+
+    #[test]
+    fn long_rope_test() {
+        let rope = Steps::new(Rope::new(10), &[Vector2::new(4, 0)]).finish();
+        assert_eq!(rope.frame(small()).lines().last(), Some("4321H."));
+        assert_eq!(rope.tail(), Vector2::ZERO);
+        assert_eq!(rope.visited(9).map(|visited| visited.len()), Some(1));
+        assert_eq!(rope.visited(4).map(|visited| visited.len()), Some(1));
+        assert_eq!(rope.visited(3).map(|visited| visited.len()), Some(2));
+        assert_eq!(rope.visited(10), None);
+    }
+
+    #[test]
+    fn diagonal_step_test() {
+        let mut rope = Rope::new(3);
+        rope.step(Vector2::new(1, 1));
+        rope.step(Vector2::new(1, 1));
+        assert_eq!(
+            rope.knots(),
+            [Vector2::new(2, 2), Vector2::new(1, 1), Vector2::ZERO]
+        );
+        assert_eq!(rope.head(), Vector2::new(2, 2));
+    }
+}
@@ -1,3 +1,9 @@
+use std::{
+    cmp::Ordering,
+    iter::Sum,
+    ops::{Mul, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive, Sub},
+};
+
 pub trait Membership<T> {
     fn contains(&self, element: &T) -> bool;
 }
@@ -24,151 +30,334 @@ pub trait Union<Rhs = Self> {
 pub enum Bound<T> {
     Open(T),
     Closed(T),
+    /// No limit: minus infinity as a lower bound, plus infinity as an upper
+    /// bound.
+    Unbounded,
 }
 
 impl<T> Bound<T> {
-    fn value(&self) -> &T {
+    /// The limiting value, `None` for [`Bound::Unbounded`].
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Bound::Open(b) => Some(b),
+            Bound::Closed(b) => Some(b),
+            Bound::Unbounded => None,
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Bound::Closed(_))
+    }
+
+    /// The bound on the other side of the same point, e.g. the upper bound of
+    /// everything below a lower bound. There is nothing beyond an unbounded
+    /// end, hence `None`.
+    fn flip(self) -> Option<Self> {
         match self {
-            Bound::Open(b) => b,
-            Bound::Closed(b) => b,
+            Bound::Open(b) => Some(Bound::Closed(b)),
+            Bound::Closed(b) => Some(Bound::Open(b)),
+            Bound::Unbounded => None,
         }
     }
 }
 
+/// Order lower bounds by the first point they admit: `[a` comes before `(a`.
+fn cmp_lower<T: PartialOrd>(lhs: &Bound<T>, rhs: &Bound<T>) -> Ordering {
+    match (lhs.value(), rhs.value()) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some(l), Some(r)) => match l.partial_cmp(r) {
+            Some(Ordering::Equal) | None => rhs.is_closed().cmp(&lhs.is_closed()),
+            Some(ordering) => ordering,
+        },
+    }
+}
+
+/// Order upper bounds by the last point they admit: `a)` comes before `a]`.
+fn cmp_upper<T: PartialOrd>(lhs: &Bound<T>, rhs: &Bound<T>) -> Ordering {
+    match (lhs.value(), rhs.value()) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(l), Some(r)) => match l.partial_cmp(r) {
+            Some(Ordering::Equal) | None => lhs.is_closed().cmp(&rhs.is_closed()),
+            Some(ordering) => ordering,
+        },
+    }
+}
+
+fn max_lower<T: PartialOrd>(lhs: Bound<T>, rhs: Bound<T>) -> Bound<T> {
+    match cmp_lower(&lhs, &rhs) {
+        Ordering::Less => rhs,
+        _ => lhs,
+    }
+}
+
+fn min_upper<T: PartialOrd>(lhs: Bound<T>, rhs: Bound<T>) -> Bound<T> {
+    match cmp_upper(&lhs, &rhs) {
+        Ordering::Greater => rhs,
+        _ => lhs,
+    }
+}
+
+fn max_upper<T: PartialOrd>(lhs: Bound<T>, rhs: Bound<T>) -> Bound<T> {
+    match cmp_upper(&lhs, &rhs) {
+        Ordering::Less => rhs,
+        _ => lhs,
+    }
+}
+
+/// Whether an interval ending at `upper` and one starting at `lower` leave no
+/// gap between them, i.e. they overlap or touch. Values are treated as
+/// continuous, so `2]` and `[3` leave a gap even for integers.
+fn connects<T: PartialOrd>(upper: &Bound<T>, lower: &Bound<T>) -> bool {
+    match (upper.value(), lower.value()) {
+        (Some(u), Some(l)) => match u.partial_cmp(l) {
+            Some(Ordering::Greater) => true,
+            Some(Ordering::Equal) => upper.is_closed() || lower.is_closed(),
+            _ => false,
+        },
+        _ => true,
+    }
+}
+
+/// Types whose values can be enumerated, which lets sets of them be counted
+/// element by element instead of measured.
+pub trait Discrete: PartialOrd + Sized {
+    /// The smallest value, where an unbounded lower end starts.
+    const MIN: Self;
+
+    /// The largest value, where an unbounded upper end stops.
+    const MAX: Self;
+
+    /// The next value up, `None` at the maximum.
+    fn successor(&self) -> Option<Self>;
+
+    /// The next value down, `None` at the minimum.
+    fn predecessor(&self) -> Option<Self>;
+
+    /// Number of values from `lower` to `upper`, both included.
+    fn closed_count(lower: &Self, upper: &Self) -> u128;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty),*) => {
+        $(
+            impl Discrete for $t {
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+
+                fn successor(&self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn predecessor(&self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn closed_count(lower: &Self, upper: &Self) -> u128 {
+                    if lower <= upper {
+                        (upper.abs_diff(*lower) as u128).saturating_add(1)
+                    } else {
+                        0
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_discrete!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct Interval<T> {
     lower: Bound<T>,
     upper: Bound<T>,
 }
 
-impl<T: PartialOrd> Membership<T> for Interval<T> {
-    fn contains(&self, element: &T) -> bool {
-        if element > self.lower.value() && element < self.upper.value() {
-            true
-        } else if element < self.lower.value() || element > self.upper.value() {
-            false
-        } else if element == self.lower.value() {
-            matches!(self.lower, Bound::Closed(_))
-        } else if element == self.upper.value() {
-            matches!(self.upper, Bound::Closed(_))
-        } else {
-            false
-        }
-    }
-}
-
-fn order_lower_bounds<T: PartialOrd>(
-    lower_bound_1: Bound<T>,
-    lower_bound_2: Bound<T>,
-) -> (Bound<T>, Bound<T>) {
-    match (&lower_bound_1, &lower_bound_2) {
-        (Bound::Open(sl), Bound::Open(rl)) => {
-            if sl <= rl {
-                (lower_bound_1, lower_bound_2)
-            } else {
-                (lower_bound_2, lower_bound_1)
-            }
-        }
-        (Bound::Closed(sl), Bound::Closed(rl)) => {
-            if sl <= rl {
-                (lower_bound_1, lower_bound_2)
-            } else {
-                (lower_bound_2, lower_bound_1)
-            }
-        }
-        (Bound::Open(sl), Bound::Closed(rl)) => {
-            if sl == rl {
-                (lower_bound_2, lower_bound_1)
-            } else if sl < rl {
-                (lower_bound_1, lower_bound_2)
-            } else {
-                (lower_bound_2, lower_bound_1)
-            }
-        }
-        (Bound::Closed(sl), Bound::Open(rl)) => {
-            if sl <= rl {
-                (lower_bound_1, lower_bound_2)
-            } else {
-                (lower_bound_2, lower_bound_1)
-            }
-        }
+impl<T> Interval<T> {
+    pub fn new(lower: Bound<T>, upper: Bound<T>) -> Self {
+        Interval { lower, upper }
+    }
+
+    /// `[lower, upper]`
+    pub fn closed(lower: T, upper: T) -> Self {
+        Interval::new(Bound::Closed(lower), Bound::Closed(upper))
+    }
+
+    /// `(lower, upper)`
+    pub fn open(lower: T, upper: T) -> Self {
+        Interval::new(Bound::Open(lower), Bound::Open(upper))
+    }
+
+    /// `[lower, upper)`
+    pub fn closed_open(lower: T, upper: T) -> Self {
+        Interval::new(Bound::Closed(lower), Bound::Open(upper))
+    }
+
+    /// `(lower, upper]`
+    pub fn open_closed(lower: T, upper: T) -> Self {
+        Interval::new(Bound::Open(lower), Bound::Closed(upper))
+    }
+
+    /// `[lower, ∞)`
+    pub fn at_least(lower: T) -> Self {
+        Interval::new(Bound::Closed(lower), Bound::Unbounded)
+    }
+
+    /// `(lower, ∞)`
+    pub fn greater_than(lower: T) -> Self {
+        Interval::new(Bound::Open(lower), Bound::Unbounded)
+    }
+
+    /// `(-∞, upper]`
+    pub fn at_most(upper: T) -> Self {
+        Interval::new(Bound::Unbounded, Bound::Closed(upper))
+    }
+
+    /// `(-∞, upper)`
+    pub fn less_than(upper: T) -> Self {
+        Interval::new(Bound::Unbounded, Bound::Open(upper))
+    }
+
+    /// `(-∞, ∞)`
+    pub fn unbounded() -> Self {
+        Interval::new(Bound::Unbounded, Bound::Unbounded)
+    }
+
+    pub fn lower(&self) -> &Bound<T> {
+        &self.lower
+    }
+
+    pub fn upper(&self) -> &Bound<T> {
+        &self.upper
     }
 }
 
-fn order_upper_bounds<T: PartialOrd>(
-    upper_bound_1: Bound<T>,
-    upper_bound_2: Bound<T>,
-) -> (Bound<T>, Bound<T>) {
-    match (&upper_bound_1, &upper_bound_2) {
-        (Bound::Open(su), Bound::Open(ru)) => {
-            if su <= ru {
-                (upper_bound_1, upper_bound_2)
-            } else {
-                (upper_bound_2, upper_bound_1)
-            }
-        }
-        (Bound::Closed(su), Bound::Closed(ru)) => {
-            if su <= ru {
-                (upper_bound_1, upper_bound_2)
-            } else {
-                (upper_bound_2, upper_bound_1)
-            }
+impl<T: Clone> Interval<T> {
+    /// `[value, value]`
+    pub fn singleton(value: T) -> Self {
+        Interval::closed(value.clone(), value)
+    }
+}
+
+impl<T: PartialOrd> Interval<T> {
+    pub fn is_empty(&self) -> bool {
+        let (Some(lower), Some(upper)) = (self.lower.value(), self.upper.value()) else {
+            return false;
+        };
+        match lower.partial_cmp(upper) {
+            Some(Ordering::Less) => false,
+            Some(Ordering::Equal) => !(self.lower.is_closed() && self.upper.is_closed()),
+            _ => true,
         }
-        (Bound::Open(su), Bound::Closed(ru)) => {
-            if su <= ru {
-                (upper_bound_1, upper_bound_2)
-            } else {
-                (upper_bound_2, upper_bound_1)
-            }
+    }
+}
+
+impl<T: Default + Clone + Sub<Output = T> + PartialOrd> Interval<T> {
+    /// Length of the interval, regardless of whether its ends are included,
+    /// zero if it is empty. `None` if the interval is unbounded.
+    pub fn measure(&self) -> Option<T> {
+        let (lower, upper) = (self.lower.value()?, self.upper.value()?);
+        if self.is_empty() {
+            return Some(T::default());
         }
-        (Bound::Closed(su), Bound::Open(ru)) => {
-            if su == ru {
-                (upper_bound_2, upper_bound_1)
-            } else if su < ru {
-                (upper_bound_1, upper_bound_2)
-            } else {
-                (upper_bound_2, upper_bound_1)
-            }
+        Some(upper.clone() - lower.clone())
+    }
+}
+
+impl<T: Discrete + Clone> Interval<T> {
+    /// The smallest value in the interval.
+    pub fn first(&self) -> Option<T> {
+        let first = match &self.lower {
+            Bound::Closed(lower) => lower.clone(),
+            Bound::Open(lower) => lower.successor()?,
+            Bound::Unbounded => T::MIN,
+        };
+        self.contains(&first).then_some(first)
+    }
+
+    /// The largest value in the interval.
+    pub fn last(&self) -> Option<T> {
+        let last = match &self.upper {
+            Bound::Closed(upper) => upper.clone(),
+            Bound::Open(upper) => upper.predecessor()?,
+            Bound::Unbounded => T::MAX,
+        };
+        self.contains(&last).then_some(last)
+    }
+
+    /// Number of values in the interval.
+    pub fn cardinality(&self) -> u128 {
+        match (self.first(), self.last()) {
+            (Some(first), Some(last)) => T::closed_count(&first, &last),
+            _ => 0,
         }
     }
 }
 
-impl<T: PartialOrd> Intersection for Interval<T> {
+impl<T> From<RangeInclusive<T>> for Interval<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (start, end) = range.into_inner();
+        Interval::closed(start, end)
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Interval::closed_open(range.start, range.end)
+    }
+}
+
+impl<T> From<RangeFrom<T>> for Interval<T> {
+    fn from(range: RangeFrom<T>) -> Self {
+        Interval::at_least(range.start)
+    }
+}
+
+impl<T> From<RangeTo<T>> for Interval<T> {
+    fn from(range: RangeTo<T>) -> Self {
+        Interval::less_than(range.end)
+    }
+}
+
+impl<T> From<RangeToInclusive<T>> for Interval<T> {
+    fn from(range: RangeToInclusive<T>) -> Self {
+        Interval::at_most(range.end)
+    }
+}
+
+impl<T> From<RangeFull> for Interval<T> {
+    fn from(_: RangeFull) -> Self {
+        Interval::unbounded()
+    }
+}
+
+impl<T: PartialOrd> Membership<T> for Interval<T> {
+    fn contains(&self, element: &T) -> bool {
+        let above_lower = match &self.lower {
+            Bound::Open(lower) => element > lower,
+            Bound::Closed(lower) => element >= lower,
+            Bound::Unbounded => true,
+        };
+        let below_upper = match &self.upper {
+            Bound::Open(upper) => element < upper,
+            Bound::Closed(upper) => element <= upper,
+            Bound::Unbounded => true,
+        };
+        above_lower && below_upper
+    }
+}
+
+impl<T: PartialOrd + Clone> Intersection for Interval<T> {
     type Output = Set<T>;
 
     fn intersect(self, rhs: Self) -> Self::Output {
-        if (self.lower.value() == rhs.lower.value() || self.upper.value() == rhs.upper.value())
-            || (self.contains(rhs.lower.value()) || self.contains(rhs.upper.value()))
-        {
-            let ordered_lower_bounds = order_lower_bounds(self.lower, rhs.lower);
-            let ordered_upper_bounds = order_upper_bounds(self.upper, rhs.upper);
-            Set::Interval(Interval {
-                lower: ordered_lower_bounds.1,
-                upper: ordered_upper_bounds.0,
-            })
-        } else if self.lower.value() == rhs.upper.value() {
-            match (&self.lower, &rhs.upper) {
-                (Bound::Open(_), Bound::Open(_))
-                | (Bound::Open(_), Bound::Closed(_))
-                | (Bound::Closed(_), Bound::Open(_)) => Set::Empty,
-                (Bound::Closed(_), Bound::Closed(_)) => Set::Interval(Interval {
-                    lower: self.lower,
-                    upper: rhs.upper,
-                }),
-            }
-        } else if self.upper.value() == rhs.lower.value() {
-            match (&self.upper, &rhs.lower) {
-                (Bound::Open(_), Bound::Open(_))
-                | (Bound::Open(_), Bound::Closed(_))
-                | (Bound::Closed(_), Bound::Open(_)) => Set::Empty,
-                (Bound::Closed(_), Bound::Closed(_)) => Set::Interval(Interval {
-                    lower: self.upper,
-                    upper: rhs.lower,
-                }),
-            }
-        } else {
-            Set::Empty
-        }
+        Set::from(Interval {
+            lower: max_lower(self.lower, rhs.lower),
+            upper: min_upper(self.upper, rhs.upper),
+        })
     }
 }
 
@@ -176,18 +365,18 @@ impl<T: PartialOrd + Clone> Difference for Interval<T> {
     type Output = Set<T>;
 
     fn diff(self, rhs: Self) -> Self::Output {
-        let intersection = self.clone().intersect(rhs);
-        match intersection {
-            Set::Empty => Set::Interval(self),
-            Set::Interval(int) => {
-                let ordered_lower_bounds = order_lower_bounds(self.lower, int.lower);
-                let ordered_upper_bounds = order_upper_bounds(self.upper, int.upper);
-                let int1 = Set::Interval(Interval { lower: ordered_lower_bounds.0, upper: ordered_lower_bounds.1 });
-                let int2 = Set::Interval(Interval { lower: ordered_upper_bounds.0, upper: ordered_upper_bounds.1 });
-                Set::Union(Box::new(int1), Box::new(int2))
-            },
-            Set::Union(_, _) => unreachable!("an interval's intersection with another intersection cannot be a set union - by construction"),
+        if rhs.is_empty() {
+            return Set::from(self);
         }
+        let below = rhs.lower.flip().map(|rhs_lower| Interval {
+            lower: self.lower.clone(),
+            upper: min_upper(self.upper.clone(), rhs_lower),
+        });
+        let above = rhs.upper.flip().map(|rhs_upper| Interval {
+            lower: max_lower(self.lower, rhs_upper),
+            upper: self.upper,
+        });
+        Set::from_iter(below.into_iter().chain(above))
     }
 }
 
@@ -195,38 +384,212 @@ impl<T: PartialOrd + Clone> Union for Interval<T> {
     type Output = Set<T>;
 
     fn union(self, rhs: Self) -> Self::Output {
-        let intersection = self.clone().intersect(rhs.clone());
-        match intersection {
-            Set::Empty => {
-                Set::Union(
-                    Box::new(Set::Interval(self)),
-                    Box::new(Set::Interval(rhs)),
-                )
-            },
-            Set::Interval(_) => {
-                let ordered_lower_bounds = order_lower_bounds(self.lower, rhs.lower);
-                let ordered_upper_bounds = order_upper_bounds(self.upper, rhs.upper);
-                Set::Interval(Interval { lower: ordered_lower_bounds.0, upper: ordered_upper_bounds.1 })
-            },
-            Set::Union(_, _) => unreachable!("an interval's intersection with another intersection cannot be a set union - by construction"),
-        }
+        Set::from_iter([self, rhs])
     }
 }
 
+/// A union of intervals, kept in canonical form: the intervals are non-empty,
+/// sorted, and neither overlap nor touch each other.
+///
+/// Equality compares that form, with values treated as continuous. For
+/// [`Discrete`] types that is not the same as holding the same values: the
+/// integers in `[1, 2] ∪ [3, 4]` are those in `[1, 4]`, and `(1, 2)` holds
+/// none of them but is kept. [`Set::normalize`] rewrites such a set so that
+/// equality does compare values.
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
-pub enum Set<T> {
-    Empty,
-    Interval(Interval<T>),
-    Union(Box<Set<T>>, Box<Set<T>>),
+pub struct Set<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Set<T> {
+    pub fn empty() -> Self {
+        Set {
+            intervals: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Iterate over the disjoint intervals making up the set, in increasing
+    /// order.
+    pub fn intervals(&self) -> std::slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+}
+
+impl<T> Default for Set<T> {
+    fn default() -> Self {
+        Set::empty()
+    }
+}
+
+impl<T: PartialOrd + Clone> Set<T> {
+    // `intervals` must already be sorted by their lower bounds
+    fn from_sorted(intervals: impl IntoIterator<Item = Interval<T>>) -> Self {
+        let mut merged: Vec<Interval<T>> = Vec::new();
+        for interval in intervals.into_iter().filter(|int| !int.is_empty()) {
+            match merged.last_mut() {
+                Some(last) if connects(&last.upper, &interval.lower) => {
+                    last.upper = max_upper(last.upper.clone(), interval.upper);
+                }
+                _ => merged.push(interval),
+            }
+        }
+        Set { intervals: merged }
+    }
+
+    /// The lower bound of the set, `None` if it is empty.
+    pub fn min(&self) -> Option<&Bound<T>> {
+        self.intervals.first().map(|int| &int.lower)
+    }
+
+    /// The upper bound of the set, `None` if it is empty.
+    pub fn max(&self) -> Option<&Bound<T>> {
+        self.intervals.last().map(|int| &int.upper)
+    }
+
+    /// Everything not in the set.
+    pub fn complement(&self) -> Self {
+        self.complement_within(Interval::unbounded())
+    }
+
+    /// Everything in `bounds` that is not in the set.
+    pub fn complement_within(&self, bounds: Interval<T>) -> Self {
+        Set::from(bounds).diff(self.clone())
+    }
+
+    /// The first maximal interval inside `bounds` not covered by the set.
+    pub fn first_gap(&self, bounds: &Interval<T>) -> Option<Interval<T>> {
+        let mut start = bounds.lower.clone();
+        for interval in &self.intervals {
+            if let Some(interval_lower) = interval.lower.clone().flip() {
+                let gap = Interval {
+                    lower: start.clone(),
+                    upper: min_upper(interval_lower, bounds.upper.clone()),
+                };
+                if !gap.is_empty() {
+                    return Some(gap);
+                }
+            }
+            start = max_lower(start, interval.upper.clone().flip()?);
+        }
+        let gap = Interval {
+            lower: start,
+            upper: bounds.upper.clone(),
+        };
+        (!gap.is_empty()).then_some(gap)
+    }
+}
+
+impl<T: Default + PartialOrd + Clone + Sub<Output = T> + Sum> Set<T> {
+    /// Total length of the intervals in the set, `None` if it is unbounded.
+    pub fn measure(&self) -> Option<T> {
+        self.intervals.iter().map(Interval::measure).sum()
+    }
+}
+
+impl<T: Discrete + Clone> Set<T> {
+    /// Number of values in the set, saturating at `u128::MAX`.
+    pub fn cardinality(&self) -> u128 {
+        self.intervals
+            .iter()
+            .map(Interval::cardinality)
+            .fold(0, u128::saturating_add)
+    }
+
+    /// The same values as closed intervals, runs with no value between them
+    /// merged into one, so that two sets holding the same values compare
+    /// equal once normalized. Unbounded ends become [`Discrete::MIN`] and
+    /// [`Discrete::MAX`].
+    pub fn normalize(&self) -> Self {
+        let mut intervals: Vec<Interval<T>> = Vec::new();
+        for interval in &self.intervals {
+            let (Some(first), Some(last)) = (interval.first(), interval.last()) else {
+                continue;
+            };
+            match intervals.last_mut() {
+                Some(Interval {
+                    upper: Bound::Closed(end),
+                    ..
+                }) if end.successor().as_ref() == Some(&first) => *end = last,
+                _ => intervals.push(Interval::closed(first, last)),
+            }
+        }
+        Set { intervals }
+    }
+
+    /// The smallest value in the set.
+    pub fn min_element(&self) -> Option<T> {
+        self.intervals.iter().find_map(Interval::first)
+    }
+
+    /// The largest value in the set.
+    pub fn max_element(&self) -> Option<T> {
+        self.intervals.iter().rev().find_map(Interval::last)
+    }
+
+    /// The smallest value in `bounds` that is not in the set.
+    ///
+    /// Unlike [`Set::first_gap`], this doesn't consider the space between
+    /// consecutive values, so `[1, 2]` and `[3, 4]` leave no gap.
+    pub fn first_missing(&self, bounds: &Interval<T>) -> Option<T> {
+        let mut candidate = bounds.first()?;
+        for interval in &self.intervals {
+            if interval.contains(&candidate) {
+                candidate = match &interval.upper {
+                    Bound::Closed(upper) => upper.successor()?,
+                    Bound::Open(upper) => upper.clone(),
+                    Bound::Unbounded => return None,
+                };
+            } else if interval
+                .lower
+                .value()
+                .is_some_and(|lower| lower > &candidate)
+            {
+                break;
+            }
+        }
+        bounds.contains(&candidate).then_some(candidate)
+    }
+}
+
+impl<T: PartialOrd + Clone> From<Interval<T>> for Set<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Set::from_sorted([interval])
+    }
+}
+
+impl<T: PartialOrd + Clone> FromIterator<Interval<T>> for Set<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut intervals: Vec<Interval<T>> = iter.into_iter().collect();
+        intervals.sort_by(|lhs, rhs| cmp_lower(&lhs.lower, &rhs.lower));
+        Set::from_sorted(intervals)
+    }
+}
+
+impl<T> IntoIterator for Set<T> {
+    type Item = Interval<T>;
+    type IntoIter = std::vec::IntoIter<Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Set<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = std::slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
 }
 
 impl<T: PartialOrd> Membership<T> for Set<T> {
     fn contains(&self, element: &T) -> bool {
-        match self {
-            Set::Empty => false,
-            Set::Interval(int) => int.contains(element),
-            Set::Union(set1, set2) => set1.contains(element) || set2.contains(element),
-        }
+        self.intervals.iter().any(|int| int.contains(element))
     }
 }
 
@@ -234,27 +597,22 @@ impl<T: PartialOrd + Clone> Intersection for Set<T> {
     type Output = Self;
 
     fn intersect(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (Set::Empty, _) | (_, Set::Empty) => Set::Empty,
-            (Set::Interval(s_int), Set::Interval(r_int)) => s_int.intersect(r_int),
-            (s_set @ Set::Interval(_), Set::Union(r_set1, r_set2)) => s_set
-                .clone()
-                .intersect(*r_set1)
-                .union(s_set.intersect(*r_set2)),
-            (Set::Union(s_set1, s_set2), r_set @ Set::Interval(_)) => s_set1
-                .intersect(r_set.clone())
-                .union(s_set2.intersect(r_set)),
-            (Set::Union(s_set1, s_set2), Set::Union(r_set1, r_set2)) => s_set1
-                .clone()
-                .intersect(*r_set1.clone())
-                .union(s_set1.intersect(*r_set2.clone()))
-                .union(
-                    s_set2
-                        .clone()
-                        .intersect(*r_set1)
-                        .union(s_set2.intersect(*r_set2)),
-                ),
+        let mut intersection = Vec::new();
+        let mut lhs = self.intervals.into_iter().peekable();
+        let mut rhs = rhs.intervals.into_iter().peekable();
+        while let (Some(l_int), Some(r_int)) = (lhs.peek(), rhs.peek()) {
+            intersection.push(Interval {
+                lower: max_lower(l_int.lower.clone(), r_int.lower.clone()),
+                upper: min_upper(l_int.upper.clone(), r_int.upper.clone()),
+            });
+            // the interval ending first cannot meet anything further along
+            if cmp_upper(&l_int.upper, &r_int.upper) == Ordering::Less {
+                lhs.next();
+            } else {
+                rhs.next();
+            }
         }
+        Set::from_sorted(intersection)
     }
 }
 
@@ -262,22 +620,15 @@ impl<T: PartialOrd + Clone> Difference for Set<T> {
     type Output = Self;
 
     fn diff(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (Set::Empty, _) => Set::Empty,
-            (s_set, Set::Empty) => s_set,
-            (Set::Interval(s_int), Set::Interval(r_int)) => s_int.diff(r_int),
-            (s_set @ Set::Interval(_), Set::Union(r_set1, r_set2)) => {
-                s_set.diff(*r_set1).diff(*r_set2)
-            }
-            (Set::Union(s_set1, s_set2), r_set @ Set::Interval(_)) => {
-                s_set1.diff(r_set.clone()).union(s_set2.diff(r_set))
-            }
-            (Set::Union(s_set1, s_set2), Set::Union(r_set1, r_set2)) => s_set1
-                .clone()
-                .diff(*r_set1.clone())
-                .union(s_set1.diff(*r_set2.clone()))
-                .union(s_set2.clone().diff(*r_set1).union(s_set2.diff(*r_set2))),
-        }
+        rhs.intervals.into_iter().fold(self, |remaining, r_int| {
+            // each piece stays in place, so the result is still sorted
+            Set::from_sorted(
+                remaining
+                    .intervals
+                    .into_iter()
+                    .flat_map(|l_int| l_int.diff(r_int.clone()).intervals),
+            )
+        })
     }
 }
 
@@ -285,28 +636,568 @@ impl<T: PartialOrd + Clone> Union for Set<T> {
     type Output = Self;
 
     fn union(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (Set::Empty, r_set) => r_set,
-            (s_set, Set::Empty) => s_set,
-            (Set::Interval(s_int), Set::Interval(r_int)) => s_int.union(r_int),
-            (s_set @ Set::Interval(_), Set::Union(r_set1, r_set2)) => Set::Union(
-                Box::new(s_set.clone().union(*r_set1)),
-                Box::new(s_set.union(*r_set2)),
-            ),
-            (Set::Union(s_set1, s_set2), r_set @ Set::Interval(_)) => Set::Union(
-                Box::new(s_set1.union(r_set.clone())),
-                Box::new(s_set2.union(r_set)),
-            ),
-            (Set::Union(s_set1, s_set2), Set::Union(r_set1, r_set2)) => Set::Union(
-                Box::new(Set::Union(
-                    Box::new(s_set1.clone().union(*r_set1.clone())),
-                    Box::new(s_set1.union(*r_set2.clone())),
-                )),
-                Box::new(Set::Union(
-                    Box::new(s_set2.clone().union(*r_set1)),
-                    Box::new(s_set2.union(*r_set2)),
-                )),
-            ),
+        let mut union = Vec::with_capacity(self.intervals.len() + rhs.intervals.len());
+        let mut lhs = self.intervals.into_iter().peekable();
+        let mut rhs = rhs.intervals.into_iter().peekable();
+        loop {
+            let next = match (lhs.peek(), rhs.peek()) {
+                (Some(l_int), Some(r_int)) => {
+                    if cmp_lower(&l_int.lower, &r_int.lower) == Ordering::Greater {
+                        rhs.next()
+                    } else {
+                        lhs.next()
+                    }
+                }
+                (Some(_), None) => lhs.next(),
+                (None, _) => rhs.next(),
+            };
+            match next {
+                Some(interval) => union.push(interval),
+                None => break,
+            }
+        }
+        Set::from_sorted(union)
+    }
+}
+
+/// An axis-aligned box in `D` dimensions: the points whose `i`th coordinate
+/// lies in the `i`th interval, for every axis `i`.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Cuboid<T, const D: usize> {
+    ranges: [Interval<T>; D],
+}
+
+impl<T, const D: usize> Cuboid<T, D> {
+    pub fn new(ranges: [Interval<T>; D]) -> Self {
+        Cuboid { ranges }
+    }
+
+    /// The interval covered along each axis.
+    pub fn ranges(&self) -> &[Interval<T>; D] {
+        &self.ranges
+    }
+}
+
+impl<T: PartialOrd, const D: usize> Cuboid<T, D> {
+    pub fn is_empty(&self) -> bool {
+        self.ranges.iter().any(Interval::is_empty)
+    }
+}
+
+impl<T: Default + Clone + Sub<Output = T> + Mul<Output = T> + PartialOrd, const D: usize>
+    Cuboid<T, D>
+{
+    /// Product of the lengths along each axis, `None` if the box is unbounded.
+    pub fn volume(&self) -> Option<T> {
+        if self.is_empty() {
+            return Some(T::default());
+        }
+        let mut measures = self.ranges.iter().map(Interval::measure);
+        let first = measures.next()??;
+        measures.try_fold(first, |volume, measure| Some(volume * measure?))
+    }
+}
+
+impl<T: Discrete + Clone, const D: usize> Cuboid<T, D> {
+    /// Number of lattice points in the box, saturating at `u128::MAX`.
+    pub fn cardinality(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(Interval::cardinality)
+            .fold(1, u128::saturating_mul)
+    }
+}
+
+impl<T: PartialOrd, const D: usize> Membership<[T; D]> for Cuboid<T, D> {
+    fn contains(&self, element: &[T; D]) -> bool {
+        self.ranges
+            .iter()
+            .zip(element)
+            .all(|(range, coordinate)| range.contains(coordinate))
+    }
+}
+
+impl<T: PartialOrd + Clone, const D: usize> Intersection for Cuboid<T, D> {
+    type Output = Option<Self>;
+
+    fn intersect(self, rhs: Self) -> Self::Output {
+        let mut ranges = self.ranges;
+        for (range, rhs_range) in ranges.iter_mut().zip(rhs.ranges) {
+            *range = Interval {
+                lower: max_lower(range.lower.clone(), rhs_range.lower),
+                upper: min_upper(range.upper.clone(), rhs_range.upper),
+            };
+        }
+        let intersection = Cuboid { ranges };
+        (!intersection.is_empty()).then_some(intersection)
+    }
+}
+
+impl<T: PartialOrd + Clone, const D: usize> Difference for Cuboid<T, D> {
+    type Output = Vec<Self>;
+
+    /// Split the part of `self` outside `rhs` into disjoint boxes.
+    ///
+    /// Axis by axis, the slabs of the remaining box lying outside `rhs` along
+    /// that axis are cut off, leaving at most `2 * D` pieces.
+    fn diff(self, rhs: Self) -> Self::Output {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        if self.clone().intersect(rhs.clone()).is_none() {
+            return vec![self];
+        }
+        let mut pieces = Vec::new();
+        let mut remaining = self;
+        for (axis, rhs_range) in rhs.ranges.into_iter().enumerate() {
+            let range = remaining.ranges[axis].clone();
+            for outside in range.clone().diff(rhs_range.clone()) {
+                let mut piece = remaining.clone();
+                piece.ranges[axis] = outside;
+                pieces.push(piece);
+            }
+            remaining.ranges[axis] = Interval {
+                lower: max_lower(range.lower, rhs_range.lower),
+                upper: min_upper(range.upper, rhs_range.upper),
+            };
+        }
+        pieces
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bound, Cuboid, Difference, Intersection, Interval, Membership, Set, Union};
+
+    fn test_set_1() -> Set<i64> {
+        Set::from_iter([
+            Interval::open(0, 3),
+            Interval::closed(5, 7),
+            Interval::closed_open(9, 12),
+        ])
+    }
+
+    #[test]
+    fn constructor_test() {
+        assert_eq!(
+            Interval::from(1..=3),
+            Interval::new(Bound::Closed(1), Bound::Closed(3))
+        );
+        assert_eq!(
+            Interval::from(1..3),
+            Interval::new(Bound::Closed(1), Bound::Open(3))
+        );
+        assert!(Interval::open(2, 2).is_empty());
+        assert!(!Interval::singleton(2).is_empty());
+        assert!(Interval::closed(3, 2).is_empty());
+    }
+
+    #[test]
+    fn normalize_test() {
+        let set: Set<i64> = [
+            Interval::closed(8, 9),
+            Interval::closed_open(1, 3),
+            Interval::closed(3, 4),
+            Interval::open(6, 7),
+            Interval::open(5, 6),
+            Interval::closed(2, 2),
+        ]
+        .into_iter()
+        .collect();
+        let intervals: Vec<_> = set.intervals().cloned().collect();
+        assert_eq!(
+            intervals,
+            vec![
+                Interval::closed(1, 4),
+                Interval::open(5, 6),
+                Interval::open(6, 7),
+                Interval::closed(8, 9),
+            ]
+        );
+        assert!(!set.contains(&6));
+        assert!(set.contains(&3));
+    }
+
+    #[test]
+    fn operations_test() {
+        let a = Set::from(Interval::closed(0, 10));
+        let b = Interval::open(2, 4).union(Interval::closed(6, 8));
+        assert_eq!(
+            a.clone().diff(b.clone()),
+            Set::from_iter([
+                Interval::closed(0, 2),
+                Interval::closed_open(4, 6),
+                Interval::open_closed(8, 10)
+            ])
+        );
+        assert_eq!(a.clone().intersect(b.clone()), b.clone());
+        assert_eq!(a.clone().union(b), a);
+        assert_eq!(
+            Interval::closed(0, 2).intersect(Interval::open(2, 4)),
+            Set::empty()
+        );
+    }
+
+    #[test]
+    fn cardinality_test() {
+        let set = test_set_1();
+        assert_eq!(set.cardinality(), 2 + 3 + 3);
+        assert_eq!(set.measure(), Some(3 + 2 + 3));
+        assert_eq!(set.min(), Some(&Bound::Open(0)));
+        assert_eq!(set.max(), Some(&Bound::Open(12)));
+        assert_eq!(set.min_element(), Some(1));
+        assert_eq!(set.max_element(), Some(11));
+        assert_eq!(Set::from(Interval::open(1, 2)).cardinality(), 0);
+        assert_eq!(Set::from(Interval::open(1, 2)).min_element(), None);
+        let reals = Set::from_iter([Interval::closed(0.5, 1.0), Interval::open(2.0, 2.25)]);
+        assert_eq!(reals.measure(), Some(0.75));
+        assert_eq!(Interval::closed(3, 2).measure(), Some(0));
+        assert_eq!(Interval::open(2.5, 2.5).measure(), Some(0.0));
+        assert_eq!(Interval::at_least(3).measure(), None);
+    }
+
+    #[test]
+    fn gap_test() {
+        let set = test_set_1();
+        let bounds = Interval::closed(0, 12);
+        assert_eq!(set.first_gap(&bounds), Some(Interval::singleton(0)));
+        assert_eq!(set.first_missing(&bounds), Some(0));
+        let bounds = Interval::closed(1, 12);
+        assert_eq!(set.first_gap(&bounds), Some(Interval::closed_open(3, 5)));
+        assert_eq!(set.first_missing(&bounds), Some(3));
+        assert_eq!(
+            set.complement_within(bounds),
+            Set::from_iter([
+                Interval::closed_open(3, 5),
+                Interval::open(7, 9),
+                Interval::singleton(12)
+            ])
+        );
+        let touching = Set::from_iter([Interval::closed(1, 2), Interval::closed(3, 4)]);
+        assert_eq!(
+            touching.first_gap(&Interval::closed(1, 4)),
+            Some(Interval::open(2, 3))
+        );
+        assert_eq!(touching.first_missing(&Interval::closed(1, 4)), None);
+        assert_eq!(touching.first_missing(&Interval::closed(1, 5)), Some(5));
+    }
+
+    #[test]
+    fn normalize_discrete_test() {
+        let touching = Set::from_iter([Interval::closed(1, 2), Interval::closed(3, 4)]);
+        assert_ne!(touching, Set::from(Interval::closed(1, 4)));
+        assert_eq!(touching.normalize(), Set::from(Interval::closed(1, 4)));
+        let open = Set::from_iter([Interval::open(1, 2), Interval::open_closed(4, 6)]);
+        assert_eq!(open.normalize(), Set::from(Interval::closed(5, 6)));
+        assert_eq!(Set::from(Interval::open(1, 2)).normalize(), Set::empty());
+        assert_eq!(
+            Set::from(Interval::less_than(0u8)).normalize(),
+            Set::empty()
+        );
+        assert_eq!(
+            Set::from(Interval::at_most(3u8)).normalize(),
+            Set::from(Interval::closed(0, 3))
+        );
+    }
+
+    #[test]
+    fn cuboid_test() {
+        let cube = Cuboid::new([
+            Interval::closed(0, 2),
+            Interval::closed(0, 2),
+            Interval::closed(0, 2),
+        ]);
+        let center = Cuboid::new([
+            Interval::singleton(1),
+            Interval::singleton(1),
+            Interval::singleton(1),
+        ]);
+        assert!(cube.contains(&[1, 2, 0]));
+        assert!(!cube.contains(&[1, 3, 0]));
+        assert_eq!(cube.cardinality(), 27);
+        assert_eq!(cube.volume(), Some(8));
+        assert_eq!(cube.clone().intersect(center.clone()), Some(center.clone()));
+        let shell = cube.clone().diff(center.clone());
+        assert_eq!(shell.len(), 6);
+        assert_eq!(shell.iter().map(Cuboid::cardinality).sum::<u128>(), 26);
+        assert!(shell.iter().all(|piece| !piece.contains(&[1, 1, 1])));
+        let far = Cuboid::new([
+            Interval::closed(5, 6),
+            Interval::closed(0, 2),
+            Interval::closed(0, 2),
+        ]);
+        assert_eq!(cube.clone().intersect(far.clone()), None);
+        assert_eq!(cube.clone().diff(far), vec![cube.clone()]);
+        assert!(cube.clone().diff(cube).is_empty());
+        let slab = Cuboid::new([Interval::closed(0, 1), Interval::at_least(0)]);
+        assert_eq!(slab.volume(), None);
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    //! Checks the set operations against a brute-force model.
+    //!
+    //! Interval endpoints are even numbers, so sampling every integer in the
+    //! domain visits each endpoint and a point strictly between each pair of
+    //! neighbouring endpoints. That is enough to tell apart any two sets built
+    //! from such intervals, open and closed ends included.
+
+    use proptest::prelude::*;
+
+    use super::{Bound, Cuboid, Difference, Intersection, Interval, Membership, Set, Union};
+
+    const DOMAIN: std::ops::RangeInclusive<i64> = -2..=22;
+
+    fn finite_bound() -> impl Strategy<Value = Bound<i64>> {
+        (0..=10i64, any::<bool>()).prop_map(|(value, closed)| match closed {
+            true => Bound::Closed(2 * value),
+            false => Bound::Open(2 * value),
+        })
+    }
+
+    fn bound() -> impl Strategy<Value = Bound<i64>> {
+        prop_oneof![
+            10 => finite_bound(),
+            1 => Just(Bound::Unbounded),
+        ]
+    }
+
+    fn finite_interval() -> impl Strategy<Value = Interval<i64>> {
+        (finite_bound(), finite_bound()).prop_map(|(lower, upper)| Interval::new(lower, upper))
+    }
+
+    fn interval() -> impl Strategy<Value = Interval<i64>> {
+        (bound(), bound()).prop_map(|(lower, upper)| Interval::new(lower, upper))
+    }
+
+    fn set() -> impl Strategy<Value = Set<i64>> {
+        prop::collection::vec(interval(), 0..5).prop_map(Set::from_iter)
+    }
+
+    fn model(set: &Set<i64>) -> Vec<bool> {
+        DOMAIN.map(|point| set.contains(&point)).collect()
+    }
+
+    fn universe() -> Set<i64> {
+        Set::from(Interval::closed(0, 20))
+    }
+
+    fn is_canonical(set: &Set<i64>) -> bool {
+        set.intervals().all(|int| !int.is_empty())
+            && set
+                .intervals
+                .windows(2)
+                .all(|pair| super::cmp_lower(&pair[0].lower, &pair[1].lower).is_lt())
+            && !set
+                .intervals
+                .windows(2)
+                .any(|pair| super::connects(&pair[0].upper, &pair[1].lower))
+    }
+
+    proptest! {
+        #[test]
+        fn interval_membership(int in interval(), point in DOMAIN) {
+            let above_lower = match int.lower() {
+                Bound::Open(lower) => point > *lower,
+                Bound::Closed(lower) => point >= *lower,
+                Bound::Unbounded => true,
+            };
+            let below_upper = match int.upper() {
+                Bound::Open(upper) => point < *upper,
+                Bound::Closed(upper) => point <= *upper,
+                Bound::Unbounded => true,
+            };
+            prop_assert_eq!(int.contains(&point), above_lower && below_upper);
+            prop_assert_eq!(Set::from(int).contains(&point), above_lower && below_upper);
+        }
+
+        #[test]
+        fn canonical_form(a in set()) {
+            prop_assert!(is_canonical(&a));
+        }
+
+        #[test]
+        fn union_matches_model(a in set(), b in set()) {
+            let expected: Vec<bool> = model(&a).iter().zip(model(&b)).map(|(x, y)| *x || y).collect();
+            let union = a.union(b);
+            prop_assert!(is_canonical(&union));
+            prop_assert_eq!(model(&union), expected);
+        }
+
+        #[test]
+        fn intersection_matches_model(a in set(), b in set()) {
+            let expected: Vec<bool> = model(&a).iter().zip(model(&b)).map(|(x, y)| *x && y).collect();
+            let intersection = a.intersect(b);
+            prop_assert!(is_canonical(&intersection));
+            prop_assert_eq!(model(&intersection), expected);
+        }
+
+        #[test]
+        fn difference_matches_model(a in set(), b in set()) {
+            let expected: Vec<bool> = model(&a).iter().zip(model(&b)).map(|(x, y)| *x && !y).collect();
+            let difference = a.diff(b);
+            prop_assert!(is_canonical(&difference));
+            prop_assert_eq!(model(&difference), expected);
+        }
+
+        #[test]
+        fn interval_operations_match_set_operations(a in interval(), b in interval()) {
+            let (set_a, set_b) = (Set::from(a), Set::from(b));
+            prop_assert_eq!(a.union(b), set_a.clone().union(set_b.clone()));
+            prop_assert_eq!(a.intersect(b), set_a.clone().intersect(set_b.clone()));
+            prop_assert_eq!(a.diff(b), set_a.diff(set_b));
+        }
+
+        #[test]
+        fn equal_models_are_equal_sets(a in set(), b in set()) {
+            prop_assert_eq!(model(&a) == model(&b), a == b);
+        }
+
+        #[test]
+        fn commutativity(a in set(), b in set()) {
+            prop_assert_eq!(a.clone().union(b.clone()), b.clone().union(a.clone()));
+            prop_assert_eq!(a.clone().intersect(b.clone()), b.intersect(a));
+        }
+
+        #[test]
+        fn associativity(a in set(), b in set(), c in set()) {
+            prop_assert_eq!(
+                a.clone().union(b.clone()).union(c.clone()),
+                a.clone().union(b.clone().union(c.clone()))
+            );
+            prop_assert_eq!(
+                a.clone().intersect(b.clone()).intersect(c.clone()),
+                a.intersect(b.intersect(c))
+            );
+        }
+
+        #[test]
+        fn distributivity(a in set(), b in set(), c in set()) {
+            prop_assert_eq!(
+                a.clone().intersect(b.clone().union(c.clone())),
+                a.clone().intersect(b.clone()).union(a.intersect(c))
+            );
+        }
+
+        #[test]
+        fn de_morgan(a in set(), b in set()) {
+            prop_assert_eq!(
+                universe().diff(a.clone().union(b.clone())),
+                universe().diff(a.clone()).intersect(universe().diff(b.clone()))
+            );
+            prop_assert_eq!(
+                universe().diff(a.clone().intersect(b.clone())),
+                universe().diff(a).union(universe().diff(b))
+            );
+        }
+
+        #[test]
+        fn difference_laws(a in set(), b in set()) {
+            prop_assert_eq!(a.clone().diff(b.clone()).intersect(b.clone()), Set::empty());
+            prop_assert_eq!(a.clone().diff(b.clone()).union(a.clone().intersect(b)), a.clone());
+            prop_assert_eq!(a.clone().diff(a), Set::empty());
+        }
+
+        #[test]
+        fn idempotence(a in set()) {
+            prop_assert_eq!(a.clone().union(a.clone()), a.clone());
+            prop_assert_eq!(a.clone().intersect(a.clone()), a);
+        }
+
+        #[test]
+        fn complement_matches_model(a in set()) {
+            let expected: Vec<bool> = model(&a).iter().map(|x| !x).collect();
+            let complement = a.complement();
+            prop_assert!(is_canonical(&complement));
+            prop_assert_eq!(model(&complement), expected);
+            prop_assert_eq!(complement.complement(), a);
+        }
+
+        #[test]
+        fn measure_matches_model(int in interval(), a in set()) {
+            // every stretch of length 2 between even endpoints holds exactly
+            // one odd point
+            let odd_points = |set: &Set<i64>| {
+                DOMAIN.filter(|point| point % 2 != 0 && set.contains(point)).count() as i64
+            };
+            let bounded = int.lower().value().is_some() && int.upper().value().is_some();
+            prop_assert_eq!(int.measure(), bounded.then(|| 2 * odd_points(&Set::from(int))));
+            let bounded = a.min().is_none_or(|min| min.value().is_some())
+                && a.max().is_none_or(|max| max.value().is_some());
+            prop_assert_eq!(a.measure(), bounded.then(|| 2 * odd_points(&a)));
+        }
+
+        #[test]
+        fn complement_and_gaps(a in set()) {
+            let bounds = Interval::closed(0, 20);
+            let complement = a.complement_within(bounds);
+            prop_assert_eq!(a.first_gap(&bounds), complement.intervals().next().cloned());
+        }
+
+        #[test]
+        fn integer_queries_match_model(intervals in prop::collection::vec(finite_interval(), 0..5)) {
+            // halve the endpoints so that open ends exclude whole integers
+            let halve = |bound: &Bound<i64>| match bound {
+                Bound::Open(value) => Bound::Open(value / 2),
+                Bound::Closed(value) => Bound::Closed(value / 2),
+                Bound::Unbounded => Bound::Unbounded,
+            };
+            let a: Set<i64> = intervals
+                .iter()
+                .map(|int| Interval::new(halve(int.lower()), halve(int.upper())))
+                .collect();
+            let members: Vec<i64> = (-1..=11).filter(|point| a.contains(point)).collect();
+            prop_assert_eq!(a.cardinality(), members.len() as u128);
+            prop_assert_eq!(a.min_element(), members.first().copied());
+            prop_assert_eq!(a.max_element(), members.last().copied());
+            let bounds = Interval::closed(0, 10);
+            let missing = (0..=10).find(|point| !a.contains(point));
+            prop_assert_eq!(a.first_missing(&bounds), missing);
+        }
+
+        #[test]
+        fn normalized_sets_compare_values(
+            a in prop::collection::vec(finite_interval(), 0..5),
+            b in prop::collection::vec(finite_interval(), 0..5),
+        ) {
+            // halve the endpoints so that neighbouring integers end up in
+            // intervals that only touch as integers
+            let halve = |int: &Interval<i64>| {
+                let halve = |bound: &Bound<i64>| match bound {
+                    Bound::Open(value) => Bound::Open(value / 2),
+                    Bound::Closed(value) => Bound::Closed(value / 2),
+                    Bound::Unbounded => Bound::Unbounded,
+                };
+                Interval::new(halve(int.lower()), halve(int.upper()))
+            };
+            let a: Set<i64> = a.iter().map(halve).collect();
+            let b: Set<i64> = b.iter().map(halve).collect();
+            let members = |set: &Set<i64>| (-1..=11).filter(|point| set.contains(point)).collect::<Vec<_>>();
+            let normalized = a.normalize();
+            prop_assert_eq!(members(&normalized), members(&a));
+            prop_assert!(normalized.intervals().all(|int| int.lower().is_closed() && int.upper().is_closed()));
+            prop_assert_eq!(members(&a) == members(&b), normalized == b.normalize());
+        }
+
+        #[test]
+        fn cuboid_difference_partitions(
+            a in [interval(), interval()].prop_map(Cuboid::new),
+            b in [interval(), interval()].prop_map(Cuboid::new),
+        ) {
+            let intersection = a.clone().intersect(b.clone());
+            let pieces = a.clone().diff(b.clone());
+            prop_assert!(pieces.len() <= 4);
+            prop_assert!(pieces.iter().all(|piece| !piece.is_empty()));
+            for x in DOMAIN {
+                for y in DOMAIN {
+                    let point = [x, y];
+                    let covering = pieces.iter().filter(|piece| piece.contains(&point)).count();
+                    prop_assert_eq!(covering, usize::from(a.contains(&point) && !b.contains(&point)));
+                    let in_both = intersection.as_ref().is_some_and(|int| int.contains(&point));
+                    prop_assert_eq!(in_both, a.contains(&point) && b.contains(&point));
+                }
+            }
         }
     }
 }
@@ -0,0 +1,454 @@
+//! An in-memory filesystem rebuilt from a terminal transcript of `cd` and
+//! `ls` commands, as in 2022 day 7.
+
+use std::{
+    collections::{btree_map, BTreeMap},
+    fmt::{self, Display, Write},
+    str::FromStr,
+};
+
+use parse::{ErrorKind, ParseError, Span};
+use thiserror::Error;
+
+/// An absolute path: the names leading from the root to an entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VPath(Vec<String>);
+
+impl VPath {
+    pub fn root() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The path of the entry `name` inside this directory.
+    pub fn join(&self, name: &str) -> Self {
+        let mut components = self.0.clone();
+        components.push(name.to_string());
+        Self(components)
+    }
+
+    /// The directory containing this path, `None` for the root.
+    pub fn parent(&self) -> Option<Self> {
+        let (_, parent) = self.0.split_last()?;
+        Some(Self(parent.to_vec()))
+    }
+
+    /// The last component, `None` for the root.
+    pub fn name(&self) -> Option<&str> {
+        self.0.last().map(String::as_str)
+    }
+
+    pub fn components(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    /// How many directories down from the root the path is.
+    pub fn depth(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl Display for VPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_root() {
+            return f.write_str("/");
+        }
+        for component in &self.0 {
+            write!(f, "/{component}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for VPath {
+    type Err = VfsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let relative = s
+            .strip_prefix('/')
+            .ok_or_else(|| VfsError::RelativePath(s.to_string()))?;
+        Ok(Self(
+            relative
+                .split('/')
+                .filter(|component| !component.is_empty())
+                .map(str::to_string)
+                .collect(),
+        ))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum VfsError {
+    #[error("{0} is not absolute")]
+    RelativePath(String),
+    #[error("{0} does not exist")]
+    NotFound(VPath),
+    #[error("{0} is a file, not a directory")]
+    NotADirectory(VPath),
+    #[error("{0} is a directory, not a file")]
+    IsADirectory(VPath),
+    #[error("{path} was listed with size {old} before, now with {new}")]
+    SizeConflict { path: VPath, old: u64, new: u64 },
+    #[error("the root has no parent directory")]
+    AboveRoot,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    /// The entries of a directory by name.
+    Dir(BTreeMap<String, Node>),
+    /// The size of a file.
+    File(u64),
+}
+
+impl Node {
+    fn empty_dir() -> Self {
+        Node::Dir(BTreeMap::new())
+    }
+
+    /// The size of a file or the total size of everything in a directory.
+    pub fn size(&self) -> u64 {
+        match self {
+            Node::Dir(entries) => entries.values().map(Node::size).sum(),
+            Node::File(size) => *size,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    Dir,
+    File,
+}
+
+/// A file or directory as reported by [`Vfs::walk`], directories with the
+/// total size of their contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub path: VPath,
+    pub kind: EntryKind,
+    pub size: u64,
+}
+
+impl Entry {
+    pub fn is_dir(&self) -> bool {
+        self.kind == EntryKind::Dir
+    }
+}
+
+/// A filesystem holding nothing but names and file sizes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vfs {
+    root: Node,
+}
+
+impl Default for Vfs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Vfs {
+    /// A filesystem with nothing but an empty root directory.
+    pub fn new() -> Self {
+        Self {
+            root: Node::empty_dir(),
+        }
+    }
+
+    pub fn get(&self, path: &VPath) -> Option<&Node> {
+        path.components()
+            .try_fold(&self.root, |node, name| match node {
+                Node::Dir(entries) => entries.get(name),
+                Node::File(_) => None,
+            })
+    }
+
+    /// The entries of the directory at `path`.
+    fn dir_mut(&mut self, path: &VPath) -> Result<&mut BTreeMap<String, Node>, VfsError> {
+        let mut node = &mut self.root;
+        for name in path.components() {
+            node = match node {
+                Node::Dir(entries) => entries
+                    .get_mut(name)
+                    .ok_or_else(|| VfsError::NotFound(path.clone()))?,
+                Node::File(_) => return Err(VfsError::NotFound(path.clone())),
+            };
+        }
+        match node {
+            Node::Dir(entries) => Ok(entries),
+            Node::File(_) => Err(VfsError::NotADirectory(path.clone())),
+        }
+    }
+
+    /// Create the directory `path` inside an existing one, nothing happens if
+    /// it exists already.
+    pub fn mkdir(&mut self, path: &VPath) -> Result<(), VfsError> {
+        let (Some(parent), Some(name)) = (path.parent(), path.name()) else {
+            return Ok(());
+        };
+        match self.dir_mut(&parent)?.entry(name.to_string()) {
+            btree_map::Entry::Vacant(entry) => {
+                entry.insert(Node::empty_dir());
+                Ok(())
+            }
+            btree_map::Entry::Occupied(entry) => match entry.get() {
+                Node::Dir(_) => Ok(()),
+                Node::File(_) => Err(VfsError::NotADirectory(path.clone())),
+            },
+        }
+    }
+
+    /// Create the file `path` inside an existing directory. Adding it again
+    /// with the same size does nothing, with a different size is an error.
+    pub fn add_file(&mut self, path: &VPath, size: u64) -> Result<(), VfsError> {
+        let (Some(parent), Some(name)) = (path.parent(), path.name()) else {
+            return Err(VfsError::IsADirectory(path.clone()));
+        };
+        match self.dir_mut(&parent)?.entry(name.to_string()) {
+            btree_map::Entry::Vacant(entry) => {
+                entry.insert(Node::File(size));
+                Ok(())
+            }
+            btree_map::Entry::Occupied(entry) => match *entry.get() {
+                Node::File(old) if old == size => Ok(()),
+                Node::File(old) => Err(VfsError::SizeConflict {
+                    path: path.clone(),
+                    old,
+                    new: size,
+                }),
+                Node::Dir(_) => Err(VfsError::IsADirectory(path.clone())),
+            },
+        }
+    }
+
+    /// The size of the file or the total size of the directory at `path`.
+    pub fn size(&self, path: &VPath) -> Option<u64> {
+        self.get(path).map(Node::size)
+    }
+
+    /// Every entry, starting with the root, each directory followed by its
+    /// contents in order of their names.
+    pub fn walk(&self) -> impl Iterator<Item = Entry> {
+        fn visit(path: VPath, node: &Node, entries: &mut Vec<Entry>) -> u64 {
+            match node {
+                Node::File(size) => {
+                    entries.push(Entry {
+                        path,
+                        kind: EntryKind::File,
+                        size: *size,
+                    });
+                    *size
+                }
+                Node::Dir(children) => {
+                    let index = entries.len();
+                    entries.push(Entry {
+                        path: path.clone(),
+                        kind: EntryKind::Dir,
+                        size: 0,
+                    });
+                    let size = children
+                        .iter()
+                        .map(|(name, child)| visit(path.join(name), child, entries))
+                        .sum();
+                    entries[index].size = size;
+                    size
+                }
+            }
+        }
+
+        let mut entries = Vec::new();
+        visit(VPath::root(), &self.root, &mut entries);
+        entries.into_iter()
+    }
+
+    /// The entries matching `predicate`, in the order of [`Vfs::walk`].
+    pub fn find(&self, mut predicate: impl FnMut(&Entry) -> bool) -> impl Iterator<Item = Entry> {
+        self.walk().filter(move |entry| predicate(entry))
+    }
+
+    /// The total size of every directory, like `du` does.
+    pub fn du(&self) -> Vec<(VPath, u64)> {
+        self.find(Entry::is_dir)
+            .map(|entry| (entry.path, entry.size))
+            .collect()
+    }
+
+    /// Draw the filesystem the way the puzzle does:
+    ///
+    /// ```text
+    /// - / (dir)
+    ///   - a (dir)
+    ///     - f (file, size=29116)
+    /// ```
+    pub fn tree(&self) -> String {
+        let mut tree = String::new();
+        for entry in self.walk() {
+            let indent = "  ".repeat(entry.path.depth());
+            let name = entry.path.name().unwrap_or("/");
+            // Writing to a String can't fail.
+            let _ = match entry.kind {
+                EntryKind::Dir => writeln!(tree, "{indent}- {name} (dir)"),
+                EntryKind::File => writeln!(tree, "{indent}- {name} (file, size={})", entry.size),
+            };
+        }
+        tree
+    }
+
+    /// Rebuild the filesystem a terminal session explored:
+    ///
+    /// ```text
+    /// $ cd /
+    /// $ ls
+    /// dir a
+    /// 14848514 b.txt
+    /// $ cd a
+    /// ```
+    ///
+    /// Changing into a directory that wasn't listed creates it. Changing
+    /// into a file, above the root or listing an entry differently than
+    /// before is reported at the offending line.
+    pub fn replay(transcript: &str) -> Result<Self, ParseError> {
+        let mut vfs = Self::new();
+        let mut cwd = VPath::root();
+        let mut listing = false;
+        for line in Span::new(transcript).lines() {
+            let at_line = |err: VfsError| line.error(ErrorKind::Custom(err.to_string()));
+            if let Ok(command) = line.strip_prefix("$ ") {
+                listing = false;
+                if let Ok(dir) = command.strip_prefix("cd ") {
+                    cwd = match dir.as_str() {
+                        "/" => VPath::root(),
+                        ".." => cwd.parent().ok_or(VfsError::AboveRoot).map_err(at_line)?,
+                        name => cwd.join(name),
+                    };
+                    vfs.mkdir(&cwd).map_err(at_line)?;
+                } else if command.as_str() == "ls" {
+                    listing = true;
+                } else {
+                    return Err(command.expected("cd or ls"));
+                }
+            } else if !listing {
+                return Err(line.expected("a command"));
+            } else if let Ok(name) = line.strip_prefix("dir ") {
+                vfs.mkdir(&cwd.join(name.as_str())).map_err(at_line)?;
+            } else {
+                let (size, name) = line.split_once(" ")?;
+                vfs.add_file(&cwd.join(name.as_str()), size.parse()?)
+                    .map_err(at_line)?;
+            }
+        }
+        Ok(vfs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EntryKind, VPath, Vfs, VfsError};
+
+    fn path(s: &str) -> VPath {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn path_test() {
+        let p = path("/a/e/i");
+        assert_eq!(p.to_string(), "/a/e/i");
+        assert_eq!(p.name(), Some("i"));
+        assert_eq!(p.depth(), 3);
+        assert_eq!(p.parent(), Some(path("/a/e")));
+        assert_eq!(VPath::root().join("a").join("e"), path("/a/e/"));
+        assert_eq!(VPath::root().to_string(), "/");
+        assert_eq!(VPath::root().parent(), None);
+        assert_eq!(
+            "a/e".parse::<VPath>(),
+            Err(VfsError::RelativePath("a/e".into()))
+        );
+    }
+
+    #[test]
+    fn build_test() {
+        let mut vfs = Vfs::new();
+        vfs.mkdir(&path("/a")).unwrap();
+        vfs.add_file(&path("/a/f"), 10).unwrap();
+        vfs.add_file(&path("/g"), 5).unwrap();
+        vfs.add_file(&path("/a/f"), 10).unwrap();
+        assert_eq!(vfs.size(&path("/")), Some(15));
+        assert_eq!(vfs.size(&path("/a")), Some(10));
+        assert_eq!(vfs.size(&path("/b")), None);
+        assert_eq!(
+            vfs.add_file(&path("/a/f"), 11),
+            Err(VfsError::SizeConflict {
+                path: path("/a/f"),
+                old: 10,
+                new: 11
+            })
+        );
+        assert_eq!(
+            vfs.mkdir(&path("/a/f")),
+            Err(VfsError::NotADirectory(path("/a/f")))
+        );
+        assert_eq!(
+            vfs.add_file(&path("/a"), 1),
+            Err(VfsError::IsADirectory(path("/a")))
+        );
+        assert_eq!(
+            vfs.add_file(&path("/b/c"), 1),
+            Err(VfsError::NotFound(path("/b")))
+        );
+    }
+
+    #[test]
+    fn query_test() {
+        let vfs = Vfs::replay("$ cd /\n$ ls\ndir x\n3 y\n$ cd x\n$ ls\n4 z\n").unwrap();
+        let walked: Vec<(String, EntryKind, u64)> = vfs
+            .walk()
+            .map(|entry| (entry.path.to_string(), entry.kind, entry.size))
+            .collect();
+        assert_eq!(
+            walked,
+            [
+                ("/".to_string(), EntryKind::Dir, 7),
+                ("/x".to_string(), EntryKind::Dir, 4),
+                ("/x/z".to_string(), EntryKind::File, 4),
+                ("/y".to_string(), EntryKind::File, 3),
+            ]
+        );
+        assert_eq!(vfs.du(), [(path("/"), 7), (path("/x"), 4)]);
+        let small_files: Vec<VPath> = vfs
+            .find(|entry| !entry.is_dir() && entry.size < 4)
+            .map(|entry| entry.path)
+            .collect();
+        assert_eq!(small_files, [path("/y")]);
+        assert_eq!(
+            vfs.tree(),
+            "- / (dir)\n  - x (dir)\n    - z (file, size=4)\n  - y (file, size=3)\n"
+        );
+    }
+
+    #[test]
+    fn replay_error_test() {
+        let error = Vfs::replay("$ cd /\n$ ls\n3 y\n$ cd y\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 1: /y is a file, not a directory"
+        );
+        let error = Vfs::replay("$ ls\n3 y\n$ ls\n4 y\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 1: /y was listed with size 3 before, now with 4"
+        );
+        let error = Vfs::replay("$ cd ..\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: the root has no parent directory"
+        );
+        let error = Vfs::replay("$ cd /\n3 y\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        let error = Vfs::replay("$ rm -rf /\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+    }
+}
@@ -1,39 +1,27 @@
-use std::{collections::HashMap, fs::File, io::Read};
+use std::{collections::HashMap, fs};
 
-fn main() {
-    let mut input = String::new();
-    File::open("res/day01")
-        .unwrap()
-        .read_to_string(&mut input)
-        .unwrap();
+use aoc::Solution;
+use parse::{ParseError, Span};
+
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/day01")?;
     println!("Day 01");
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    aoc::solve::<Day01>(&input)
 }
 
-fn part1(input: &str) -> u32 {
-    input
+fn part1(input: &str) -> Result<u32, ParseError> {
+    Span::new(input)
         .lines()
         .map(|line| {
-            let digit1 = line
-                .chars()
-                .find(|ch| ch.is_numeric())
-                .unwrap()
-                .to_digit(10)
-                .unwrap();
-            let digit2 = line
-                .chars()
-                .rev()
-                .find(|ch| ch.is_numeric())
-                .unwrap()
-                .to_digit(10)
-                .unwrap();
-            digit1 * 10 + digit2
+            let mut digits = line.as_str().chars().filter_map(|ch| ch.to_digit(10));
+            let digit1 = digits.next().ok_or_else(|| line.expected("a digit"))?;
+            let digit2 = digits.next_back().unwrap_or(digit1);
+            Ok(digit1 * 10 + digit2)
         })
         .sum()
 }
 
-fn part2(input: &str) -> u32 {
+fn part2(input: &str) -> Result<u32, ParseError> {
     const DIGITS_THREE: [&str; 3] = ["one", "two", "six"];
     const DIGITS_FOUR: [&str; 3] = ["four", "five", "nine"];
     const DIGITS_FIVE: [&str; 3] = ["three", "seven", "eight"];
@@ -48,9 +36,10 @@ fn part2(input: &str) -> u32 {
         ("eight", 8),
         ("nine", 9),
     ]);
-    input
+    Span::new(input)
         .lines()
-        .map(|line| {
+        .map(|span| {
+            let line = span.as_str();
             let characters: Vec<char> = line.chars().collect();
             let mut digits = Vec::new();
             let mut i = 0;
@@ -89,19 +78,32 @@ fn part2(input: &str) -> u32 {
                     }
                 }
             }
-            digits.first().unwrap() * 10 + digits.last().unwrap()
+            match (digits.first(), digits.last()) {
+                (Some(first), Some(last)) => Ok(first * 10 + last),
+                _ => Err(span.expected("a digit or a spelled out digit")),
+            }
         })
         .sum()
 }
 
-#[test]
-fn test_part1() {
-    let input = include_str!("test_input01");
-    assert_eq!(142, part1(input));
-}
+struct Day01;
 
-#[test]
-fn test_part2() {
-    let input = include_str!("test_input02");
-    assert_eq!(281, part2(input));
+impl Solution for Day01 {
+    type Input = String;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> aoc::Result<u32> {
+        Ok(part1(input)?)
+    }
+
+    fn part2(input: &Self::Input) -> aoc::Result<u32> {
+        Ok(part2(input)?)
+    }
 }
+
+aoc::example_tests!(Day01, 2023, 1);
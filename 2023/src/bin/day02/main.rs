@@ -1,6 +1,12 @@
-use std::{collections::HashMap, fs::File, io::Read, str::FromStr};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    fs,
+    str::FromStr,
+};
 
-use regex::Regex;
+use aoc::Solution;
+use parse::{ParseError, Span};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Color {
@@ -12,6 +18,12 @@ enum Color {
 #[derive(Debug, Clone, Copy)]
 struct ParseColorError;
 
+impl Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected red, green or blue")
+    }
+}
+
 impl FromStr for Color {
     type Err = ParseColorError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -24,69 +36,30 @@ impl FromStr for Color {
     }
 }
 
-fn main() {
-    let mut input = String::new();
-    File::open("res/day02")
-        .unwrap()
-        .read_to_string(&mut input)
-        .unwrap();
-    let games = parse_input(&input);
+fn main() -> aoc::Result<()> {
+    let input = fs::read_to_string("res/day02")?;
     println!("Day 02");
-    println!("Part 1: {}", part1(&games));
-    println!("Part 2: {}", part2(&games));
+    aoc::solve::<Day02>(&input)
 }
 
-fn parse_input(input: &str) -> Vec<Vec<HashMap<Color, u8>>> {
-    let pattern = Regex::new(
-        r"([0-9]+) (red|green|blue)(, ([0-9]+) (red|green|blue))?(, ([0-9]+) (red|green|blue))?;?",
-    )
-    .unwrap();
-    let mut games = Vec::new();
-    for line in input.lines() {
-        let mut sets = Vec::new();
-        for cap in pattern.captures_iter(line) {
-            let mut set: HashMap<Color, u8> = HashMap::new();
-            match (cap.get(1), cap.get(2)) {
-                (Some(count), Some(color)) => {
-                    set.insert(
-                        color.as_str().parse().unwrap(),
-                        count.as_str().parse().unwrap(),
-                    );
-                }
-                (None, _) => (),
-                (Some(_), None) => unreachable!(
-                    "regex pattern makes it impossible for count to exist without the color name"
-                ),
-            }
-            match (cap.get(4), cap.get(5)) {
-                (Some(count1), Some(color1)) => {
-                    set.insert(
-                        color1.as_str().parse().unwrap(),
-                        count1.as_str().parse().unwrap(),
-                    );
-                }
-                (None, _) => (),
-                (Some(_), None) => unreachable!(
-                    "regex pattern makes it impossible for count to exist without the color name"
-                ),
-            }
-            match (cap.get(7), cap.get(8)) {
-                (Some(count1), Some(color1)) => {
-                    set.insert(
-                        color1.as_str().parse().unwrap(),
-                        count1.as_str().parse().unwrap(),
-                    );
-                }
-                (None, _) => (),
-                (Some(_), None) => unreachable!(
-                    "regex pattern makes it impossible for count to exist without the color name"
-                ),
-            }
-            sets.push(set);
-        }
-        games.push(sets);
-    }
-    games
+fn parse_input(input: &str) -> Result<Vec<Vec<HashMap<Color, u8>>>, ParseError> {
+    Span::new(input)
+        .lines()
+        .map(|line| {
+            let (game, sets) = line.key_value(":")?;
+            game.strip_prefix("Game ")?.parse::<u32>()?;
+            sets.split(";")
+                .map(|set| {
+                    set.split(",")
+                        .map(|cubes| {
+                            let (count, color) = cubes.trim().split_once(" ")?;
+                            Ok((color.parse()?, count.parse()?))
+                        })
+                        .collect()
+                })
+                .collect()
+        })
+        .collect()
 }
 
 fn game_required_cubes(sets: &[HashMap<Color, u8>]) -> HashMap<Color, u8> {
@@ -125,14 +98,34 @@ fn part2(games: &[Vec<HashMap<Color, u8>>]) -> u32 {
         .sum()
 }
 
+struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Vec<HashMap<Color, u8>>>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> aoc::Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(games: &Self::Input) -> aoc::Result<u32> {
+        Ok(part1(games))
+    }
+
+    fn part2(games: &Self::Input) -> aoc::Result<u32> {
+        Ok(part2(games))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
-    use crate::{parse_input, part1, part2, Color};
+    use crate::{parse_input, Color};
 
-    fn test_input01() -> &'static str {
-        include_str!("test_input01")
+    fn test_input01() -> String {
+        aoc::example_input(2023, 2, 1)
     }
 
     #[test]
@@ -164,25 +157,18 @@ mod tests {
                 HashMap::from([(Color::Red, 1), (Color::Green, 2), (Color::Blue, 2)]),
             ],
         ];
-        let actual = parse_input(input);
+        let actual = parse_input(&input).unwrap();
         assert_eq!(expected, actual);
     }
 
     #[test]
-    fn part1_sums_possible_game_ids() {
-        let input = test_input01();
-        let games = parse_input(input);
-        let expected = 8;
-        let actual = part1(&games);
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn part2_sums_powers_of_games() {
-        let input = test_input01();
-        let games = parse_input(input);
-        let expected = 2286;
-        let actual = part2(&games);
-        assert_eq!(expected, actual);
+    fn parsing_reports_position() {
+        let error = parse_input("Game 1: 3 blue, 4 red\nGame 2: 1 blue; 2 purple").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 19: invalid value \"purple\": expected red, green or blue"
+        );
     }
 }
+
+aoc::example_tests!(Day02, 2023, 2);
@@ -0,0 +1,254 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::Result;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_INPUTS_DIR: &str = "inputs";
+/// The site asks for automated requests to be spaced out, this is the
+/// default gap between two of them.
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(3);
+
+const USER_AGENT: &str = "github.com/scimas/adventofcode input downloader";
+/// Touched on every request, so the rate limit holds across processes
+/// sharing an inputs directory.
+const LAST_REQUEST_MARKER: &str = ".last-request";
+
+/// Fetches puzzle inputs and caches them as `<inputs dir>/<year>/day<DD>`.
+///
+/// A cached input is always used as is, the site is only asked for inputs
+/// that aren't on disk yet.
+#[derive(Debug, Clone)]
+pub struct Downloader {
+    session: Option<String>,
+    base_url: String,
+    inputs_dir: PathBuf,
+    min_interval: Duration,
+}
+
+impl Downloader {
+    pub fn new(session: impl Into<String>, inputs_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            session: Some(session.into()),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            inputs_dir: inputs_dir.into(),
+            min_interval: DEFAULT_MIN_INTERVAL,
+        }
+    }
+
+    /// Configured by the environment: the session token comes from
+    /// `AOC_SESSION`, the inputs directory from `AOC_INPUTS_DIR` and the base
+    /// URL from `AOC_BASE_URL`. Without a session token only cached inputs
+    /// can be read.
+    pub fn from_env() -> Self {
+        Self {
+            session: env::var("AOC_SESSION").ok(),
+            base_url: env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string()),
+            inputs_dir: env::var_os("AOC_INPUTS_DIR")
+                .map_or_else(|| PathBuf::from(DEFAULT_INPUTS_DIR), PathBuf::from),
+            min_interval: DEFAULT_MIN_INTERVAL,
+        }
+    }
+
+    /// Download from `base_url` instead of the real site.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// The least time to leave between two requests.
+    pub fn min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Where the input of `year`'s `day` is cached.
+    pub fn input_path(&self, year: u16, day: u8) -> PathBuf {
+        self.inputs_dir
+            .join(year.to_string())
+            .join(format!("day{day:02}"))
+    }
+
+    /// The input of `year`'s `day`, downloaded first if it isn't cached.
+    pub fn input(&self, year: u16, day: u8) -> Result<String> {
+        let path = self.input_path(year, day);
+        match fs::read_to_string(&path) {
+            Ok(input) => return Ok(input),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => (),
+            Err(err) => return Err(err.into()),
+        }
+        let input = self.download(year, day)?;
+        // Written aside and renamed, so an interrupted write is never
+        // mistaken for a cached input.
+        let partial = path.with_extension("part");
+        fs::create_dir_all(path.parent().unwrap_or(Path::new(".")))?;
+        fs::write(&partial, &input)?;
+        fs::rename(&partial, &path)?;
+        Ok(input)
+    }
+
+    fn download(&self, year: u16, day: u8) -> io::Result<String> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let session = self.session.as_ref().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{url} is not cached and there is no session token to download it with"),
+            )
+        })?;
+        self.wait_for_rate_limit()?;
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={session}"))
+            .set("User-Agent", USER_AGENT)
+            .call();
+        match response {
+            Ok(response) => response.into_string(),
+            Err(ureq::Error::Status(status, response)) => {
+                let reason = response.into_string().unwrap_or_default();
+                Err(io::Error::other(format!(
+                    "{url} responded with status {status}: {}",
+                    reason.trim()
+                )))
+            }
+            Err(err) => Err(io::Error::other(format!("could not fetch {url}: {err}"))),
+        }
+    }
+
+    /// Sleep until `min_interval` has passed since the last request made
+    /// with this inputs directory, then mark a new one as made.
+    fn wait_for_rate_limit(&self) -> io::Result<()> {
+        let marker = self.inputs_dir.join(LAST_REQUEST_MARKER);
+        if let Ok(last_request) = fs::metadata(&marker).and_then(|meta| meta.modified()) {
+            let elapsed = SystemTime::now()
+                .duration_since(last_request)
+                .unwrap_or_default();
+            if let Some(remaining) = self.min_interval.checked_sub(elapsed) {
+                thread::sleep(remaining);
+            }
+        }
+        fs::create_dir_all(&self.inputs_dir)?;
+        fs::write(marker, "")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        path::PathBuf,
+        process,
+        sync::mpsc,
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use super::Downloader;
+
+    /// A stand-in for the site answering `responses` in order, the request
+    /// lines and cookies it got are sent back through the receiver.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut cookie = String::new();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(value) = header.strip_prefix("Cookie: ") {
+                        cookie = value.trim().to_string();
+                    }
+                }
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+                sender
+                    .send((request_line.trim().to_string(), cookie))
+                    .unwrap();
+            }
+        });
+        (base_url, receiver)
+    }
+
+    fn inputs_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-download-{}-{name}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn cache_test() {
+        let (base_url, requests) = serve(vec![(200, "1 2 3\n")]);
+        let dir = inputs_dir("cache");
+        let downloader = Downloader::new("abc", &dir)
+            .base_url(base_url)
+            .min_interval(Duration::ZERO);
+        assert_eq!(downloader.input(2016, 5).unwrap(), "1 2 3\n");
+        assert_eq!(
+            requests.recv().unwrap(),
+            (
+                "GET /2016/day/5/input HTTP/1.1".to_string(),
+                "session=abc".to_string()
+            )
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("2016").join("day05")).unwrap(),
+            "1 2 3\n"
+        );
+        // The stand-in only answers once, a second request would fail.
+        assert_eq!(downloader.input(2016, 5).unwrap(), "1 2 3\n");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn rate_limit_test() {
+        let (base_url, requests) = serve(vec![(200, "first"), (200, "second")]);
+        let dir = inputs_dir("rate-limit");
+        let downloader = Downloader::new("abc", &dir)
+            .base_url(base_url)
+            .min_interval(Duration::from_millis(300));
+        let start = Instant::now();
+        assert_eq!(downloader.input(2022, 1).unwrap(), "first");
+        assert_eq!(downloader.input(2022, 2).unwrap(), "second");
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        assert_eq!(requests.iter().count(), 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn failure_test() {
+        let (base_url, _requests) = serve(vec![(400, "Please log in."), (200, "42")]);
+        let dir = inputs_dir("failure");
+        let downloader = Downloader::new("expired", &dir)
+            .base_url(base_url)
+            .min_interval(Duration::ZERO);
+        let error = downloader.input(2021, 7).unwrap_err();
+        assert!(error.to_string().ends_with("status 400: Please log in."));
+        assert!(!downloader.input_path(2021, 7).exists());
+        assert_eq!(downloader.input(2021, 7).unwrap(), "42");
+
+        let offline = Downloader {
+            session: None,
+            ..downloader
+        };
+        assert_eq!(offline.input(2021, 7).unwrap(), "42");
+        assert!(offline.input(2021, 8).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,76 @@
+use std::{
+    fmt::{self, Debug, Display},
+    io,
+};
+
+use parse::ParseError;
+use thiserror::Error;
+
+/// Why a solution couldn't produce its answer.
+///
+/// `Debug` prints the same message as `Display`, so an error returned from
+/// `main` is reported readably.
+#[derive(Error)]
+pub enum Error {
+    /// Reading the input failed.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The input is malformed, see [`ParseError`] for where.
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    /// The input parsed, but describes something the puzzle rules don't
+    /// allow, or a simulation got into a state it can't continue from.
+    #[error("invalid puzzle state: {0}")]
+    InvalidState(String),
+    /// The search for the answer ran out of candidates.
+    #[error("no solution found: {0}")]
+    NoSolution(String),
+}
+
+impl Error {
+    pub fn invalid_state(reason: impl Display) -> Self {
+        Error::InvalidState(reason.to_string())
+    }
+
+    pub fn no_solution(reason: impl Display) -> Self {
+        Error::NoSolution(reason.to_string())
+    }
+}
+
+impl Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use parse::Span;
+
+    use super::{Error, Result};
+
+    fn parse_number(input: &str) -> Result<u32> {
+        Ok(Span::new(input).trim().parse()?)
+    }
+
+    #[test]
+    fn conversion_test() {
+        assert_eq!(parse_number(" 12").unwrap(), 12);
+        let error = parse_number("\n x").unwrap_err();
+        assert!(matches!(error, Error::Parse(ref err) if err.line == 2));
+        assert_eq!(
+            format!("{error:?}"),
+            "line 2, column 2: invalid value \"x\": invalid digit found in string"
+        );
+        let error = Error::from(io::Error::new(io::ErrorKind::NotFound, "no input"));
+        assert_eq!(error.to_string(), "no input");
+        assert_eq!(
+            Error::no_solution("every sensor covers the beacon").to_string(),
+            "no solution found: every sensor covers the beacon"
+        );
+    }
+}
@@ -0,0 +1,182 @@
+//! The examples from the puzzle texts, kept as data under `fixtures/`:
+//!
+//! ```text
+//! fixtures/<year>/day<DD>/example<N>/input
+//! fixtures/<year>/day<DD>/example<N>/part1
+//! fixtures/<year>/day<DD>/example<N>/part2
+//! ```
+//!
+//! An answer file holds what the part's answer displays as. Examples that
+//! only apply to one part leave out the other part's file.
+
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{Result, Solution};
+
+const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures");
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub number: u8,
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+fn day_dir(year: u16, day: u8) -> PathBuf {
+    Path::new(FIXTURES_DIR)
+        .join(year.to_string())
+        .join(format!("day{day:02}"))
+}
+
+fn read_answer(path: PathBuf) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(answer) => Ok(Some(answer.trim_end_matches('\n').to_string())),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Every example of `year`'s `day`, in order of their numbers.
+pub fn examples(year: u16, day: u8) -> Result<Vec<Example>> {
+    let mut examples = Vec::new();
+    for entry in fs::read_dir(day_dir(year, day))? {
+        let path = entry?.path();
+        let number = path
+            .file_name()
+            .and_then(|name| name.to_str()?.strip_prefix("example")?.parse().ok());
+        let Some(number) = number else {
+            continue;
+        };
+        examples.push(Example {
+            number,
+            input: fs::read_to_string(path.join("input"))?,
+            part1: read_answer(path.join("part1"))?,
+            part2: read_answer(path.join("part2"))?,
+        });
+    }
+    examples.sort_by_key(|example| example.number);
+    Ok(examples)
+}
+
+/// The input of example `number` of `year`'s `day`, for tests of the pieces
+/// of a solution.
+///
+/// # Panics
+///
+/// If there is no such example.
+pub fn example_input(year: u16, day: u8, number: u8) -> String {
+    let path = day_dir(year, day)
+        .join(format!("example{number}"))
+        .join("input");
+    fs::read_to_string(&path).unwrap_or_else(|err| panic!("could not read {path:?}: {err}"))
+}
+
+#[track_caller]
+fn check<A: Display>(
+    year: u16,
+    day: u8,
+    part: u8,
+    expected: impl Fn(&Example) -> Option<&String>,
+    solve: impl Fn(&str) -> Result<A>,
+) {
+    let examples =
+        examples(year, day).unwrap_or_else(|err| panic!("no examples for {year} day {day}: {err}"));
+    let mut failures = Vec::new();
+    for example in &examples {
+        let Some(expected) = expected(example) else {
+            continue;
+        };
+        match solve(&example.input) {
+            Ok(answer) if answer.to_string() == *expected => (),
+            Ok(answer) => failures.push(format!(
+                "example {}: expected\n{expected}\nfound\n{answer}",
+                example.number
+            )),
+            Err(err) => failures.push(format!("example {}: {err}", example.number)),
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{year} day {day} part {part}:\n{}",
+        failures.join("\n")
+    );
+}
+
+/// Check `S`'s part 1 against every example of `year`'s `day` with a part 1
+/// answer.
+///
+/// # Panics
+///
+/// If the examples can't be read or an answer differs.
+#[track_caller]
+pub fn check_part1<S: Solution>(year: u16, day: u8) {
+    check(
+        year,
+        day,
+        1,
+        |example| example.part1.as_ref(),
+        |input| S::part1(&S::parse(input)?),
+    );
+}
+
+/// Check `S`'s part 2 against every example of `year`'s `day` with a part 2
+/// answer.
+///
+/// # Panics
+///
+/// If the examples can't be read or an answer differs.
+#[track_caller]
+pub fn check_part2<S: Solution>(year: u16, day: u8) {
+    check(
+        year,
+        day,
+        2,
+        |example| example.part2.as_ref(),
+        |input| S::part2(&S::parse(input)?),
+    );
+}
+
+/// Generate `part1` and `part2` tests checking `$solution` against the
+/// examples of `$year`'s `$day`.
+///
+/// ```ignore
+/// aoc::example_tests!(Day01, 2022, 1);
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:ident, $year:literal, $day:literal) => {
+        #[cfg(test)]
+        mod example_tests {
+            #[test]
+            fn part1() {
+                $crate::check_part1::<super::$solution>($year, $day);
+            }
+
+            #[test]
+            fn part2() {
+                $crate::check_part2::<super::$solution>($year, $day);
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{example_input, examples};
+
+    #[test]
+    fn examples_test() {
+        let examples = examples(2022, 6).unwrap();
+        let numbers: Vec<u8> = examples.iter().map(|example| example.number).collect();
+        assert_eq!(numbers, [1, 2, 3, 4, 5]);
+        assert_eq!(examples[0].input, example_input(2022, 6, 1));
+        assert_eq!(examples[0].part1.as_deref(), Some("7"));
+        assert_eq!(examples[0].part2.as_deref(), Some("19"));
+        assert!(super::examples(1900, 1).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! Pieces shared by the solutions of every year: the error type they all
+//! return, the interface they share, a way to run many of them in one go,
+//! a way to get their inputs and a way to check them against the examples.
+//!
+//! Downloading inputs needs the `download` feature, so that only the
+//! solutions which fetch their input pull in an HTTP client.
+
+#[cfg(feature = "download")]
+mod download;
+mod error;
+mod examples;
+mod solution;
+
+use std::{env, fs, io, process::ExitCode};
+
+#[cfg(feature = "download")]
+pub use download::{Downloader, DEFAULT_BASE_URL, DEFAULT_INPUTS_DIR, DEFAULT_MIN_INTERVAL};
+pub use error::{Error, Result};
+pub use examples::{check_part1, check_part2, example_input, examples, Example};
+pub use solution::{solve, Solution};
+
+/// The `main` of one day's solution.
+pub type DayMain = fn() -> Result<()>;
+
+/// Run each named solution in turn, reporting failures on stderr instead of
+/// stopping at the first one. Fails if any of them did.
+pub fn run_all<'a>(solutions: impl IntoIterator<Item = (&'a str, DayMain)>) -> ExitCode {
+    let mut failures = 0;
+    for (name, solve) in solutions {
+        if let Err(err) = solve() {
+            eprintln!("{name}: {err}");
+            failures += 1;
+        }
+    }
+    if failures == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Read the input file named by the first command line argument.
+pub fn read_input_arg() -> Result<String> {
+    let path = env::args().nth(1).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "expected the input file as the first argument",
+        )
+    })?;
+    fs::read_to_string(&path)
+        .map_err(|err| io::Error::new(err.kind(), format!("could not read {path:?}: {err}")).into())
+}
+
+/// The input of `year`'s `day` from the cache, downloaded first if needed,
+/// see [`Downloader::from_env`] for the configuration.
+#[cfg(feature = "download")]
+pub fn puzzle_input(year: u16, day: u8) -> Result<String> {
+    Downloader::from_env().input(year, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::ExitCode;
+
+    use super::{run_all, DayMain, Error, Result};
+
+    fn solved() -> Result<()> {
+        Ok(())
+    }
+
+    fn stuck() -> Result<()> {
+        Err(Error::invalid_state("stack 4 is empty"))
+    }
+
+    #[test]
+    fn run_all_test() {
+        let solutions: [(&str, DayMain); 3] =
+            [("day01", solved), ("day02", stuck), ("day03", solved)];
+        assert_eq!(run_all(solutions), ExitCode::FAILURE);
+        let solutions: [(&str, DayMain); 2] = [("day01", solved), ("day03", solved)];
+        assert_eq!(run_all(solutions), ExitCode::SUCCESS);
+    }
+}
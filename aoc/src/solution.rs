@@ -0,0 +1,35 @@
+use std::fmt::Display;
+
+use crate::Result;
+
+/// A day's solution split into parsing the input and answering each part,
+/// so it can be run and checked against its examples the same way as every
+/// other day.
+pub trait Solution {
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+}
+
+/// Parse `input` and print the answers to both parts.
+pub fn solve<S: Solution>(input: &str) -> Result<()> {
+    let input = S::parse(input)?;
+    print_answer(1, S::part1(&input)?);
+    print_answer(2, S::part2(&input)?);
+    Ok(())
+}
+
+/// Answers spanning several lines, like letters drawn on a screen, start on
+/// their own line.
+fn print_answer(part: u8, answer: impl Display) {
+    let answer = answer.to_string();
+    if answer.contains('\n') {
+        println!("Part {part}:\n{answer}");
+    } else {
+        println!("Part {part}: {answer}");
+    }
+}
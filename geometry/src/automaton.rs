@@ -0,0 +1,359 @@
+use crate::{Bounds, Direction8, Grid, SparseGrid, Vector2};
+
+/// A space of cells that an [`Automaton`] can evolve one generation at a
+/// time.
+pub trait Lattice: Sized {
+    type Position: Copy;
+    type Cell;
+
+    /// Positions whose cells are recomputed in the next generation.
+    fn candidates(&self) -> Vec<Self::Position>;
+
+    fn cell(&self, position: Self::Position) -> &Self::Cell;
+
+    /// The next generation, `cells[i]` being the new cell at
+    /// `positions[i]` for the positions returned by `candidates`.
+    fn next_generation(&self, positions: &[Self::Position], cells: Vec<Self::Cell>) -> Self;
+}
+
+impl<T> Lattice for Grid<T> {
+    type Position = Vector2<i64>;
+    type Cell = T;
+
+    fn candidates(&self) -> Vec<Vector2<i64>> {
+        self.positions().collect()
+    }
+
+    fn cell(&self, position: Vector2<i64>) -> &T {
+        &self[position]
+    }
+
+    fn next_generation(&self, _positions: &[Vector2<i64>], cells: Vec<T>) -> Self {
+        Grid::new(self.width(), self.height(), cells)
+            .expect("one cell per position of the previous generation")
+    }
+}
+
+/// Only cells within one step of the stored ones are recomputed, so the rule
+/// must leave a default cell with only default neighbours unchanged. Cells
+/// equal to the default are dropped from the next generation.
+impl<T: Clone + PartialEq, const D: usize> Lattice for SparseGrid<T, D> {
+    type Position = [i64; D];
+    type Cell = T;
+
+    fn candidates(&self) -> Vec<[i64; D]> {
+        self.bounds()
+            .map_or_else(Vec::new, |bounds| bounds.expand(1).positions().collect())
+    }
+
+    fn cell(&self, position: [i64; D]) -> &T {
+        self.get(position)
+    }
+
+    fn next_generation(&self, positions: &[[i64; D]], cells: Vec<T>) -> Self {
+        let mut next = SparseGrid::new(self.default_value().clone());
+        for (&position, cell) in positions.iter().zip(cells) {
+            if cell != *self.default_value() {
+                next.insert(position, cell);
+            }
+        }
+        next
+    }
+}
+
+/// Which cells a rule gets to look at when updating a cell.
+pub trait Neighborhood<L: Lattice> {
+    fn neighbors(&self, lattice: &L, position: L::Position) -> Vec<L::Position>;
+}
+
+/// Every cell at most one step away along every axis, i.e. the 8 cells
+/// around a cell in the plane, or 26 in space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Adjacent;
+
+/// Every cell exactly one step away along a single axis, i.e. the 4 cells
+/// around a cell in the plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Orthogonal;
+
+/// The first cell in each of the eight directions that the predicate does
+/// not see through.
+#[derive(Debug, Clone, Copy)]
+pub struct LineOfSight<F>(pub F);
+
+impl<T> Neighborhood<Grid<T>> for Adjacent {
+    fn neighbors(&self, grid: &Grid<T>, position: Vector2<i64>) -> Vec<Vector2<i64>> {
+        grid.all_neighbors(position).map(|(p, _)| p).collect()
+    }
+}
+
+impl<T> Neighborhood<Grid<T>> for Orthogonal {
+    fn neighbors(&self, grid: &Grid<T>, position: Vector2<i64>) -> Vec<Vector2<i64>> {
+        grid.orthogonal_neighbors(position)
+            .map(|(p, _)| p)
+            .collect()
+    }
+}
+
+impl<T, F: Fn(&T) -> bool> Neighborhood<Grid<T>> for LineOfSight<F> {
+    fn neighbors(&self, grid: &Grid<T>, position: Vector2<i64>) -> Vec<Vector2<i64>> {
+        Direction8::ALL
+            .iter()
+            .filter_map(|direction| {
+                grid.ray(position, direction.to_grid_vector())
+                    .find(|(_, cell)| !(self.0)(cell))
+                    .map(|(p, _)| p)
+            })
+            .collect()
+    }
+}
+
+impl<T: Clone + PartialEq, const D: usize> Neighborhood<SparseGrid<T, D>> for Adjacent {
+    fn neighbors(&self, _grid: &SparseGrid<T, D>, position: [i64; D]) -> Vec<[i64; D]> {
+        Bounds::point(position)
+            .expand(1)
+            .positions()
+            .filter(|&neighbor| neighbor != position)
+            .collect()
+    }
+}
+
+impl<T: Clone + PartialEq, const D: usize> Neighborhood<SparseGrid<T, D>> for Orthogonal {
+    fn neighbors(&self, _grid: &SparseGrid<T, D>, position: [i64; D]) -> Vec<[i64; D]> {
+        (0..D)
+            .flat_map(|axis| {
+                [-1, 1].map(|step| {
+                    let mut neighbor = position;
+                    neighbor[axis] += step;
+                    neighbor
+                })
+            })
+            .collect()
+    }
+}
+
+/// Evolves a lattice by applying a rule to every cell and its neighbours at
+/// once, generation after generation.
+///
+/// The rule gets the current cell and its neighbours and returns the cell
+/// for the next generation.
+#[derive(Debug, Clone)]
+pub struct Automaton<L, N, R> {
+    lattice: L,
+    neighborhood: N,
+    rule: R,
+    generation: usize,
+}
+
+impl<L, N, R> Automaton<L, N, R>
+where
+    L: Lattice,
+    L::Cell: PartialEq,
+    N: Neighborhood<L>,
+    R: Fn(&L::Cell, &[&L::Cell]) -> L::Cell,
+{
+    pub fn new(lattice: L, neighborhood: N, rule: R) -> Self {
+        Automaton {
+            lattice,
+            neighborhood,
+            rule,
+            generation: 0,
+        }
+    }
+
+    pub fn lattice(&self) -> &L {
+        &self.lattice
+    }
+
+    /// Mutable access between generations, e.g. for updates the rule can't
+    /// express.
+    pub fn lattice_mut(&mut self) -> &mut L {
+        &mut self.lattice
+    }
+
+    pub fn into_lattice(self) -> L {
+        self.lattice
+    }
+
+    /// Number of generations stepped so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    fn next_cell(&self, position: L::Position) -> L::Cell {
+        let neighbors: Vec<&L::Cell> = self
+            .neighborhood
+            .neighbors(&self.lattice, position)
+            .into_iter()
+            .map(|neighbor| self.lattice.cell(neighbor))
+            .collect();
+        (self.rule)(self.lattice.cell(position), &neighbors)
+    }
+
+    fn advance(&mut self, positions: Vec<L::Position>, cells: Vec<L::Cell>) -> usize {
+        let changed = positions
+            .iter()
+            .zip(&cells)
+            .filter(|(&position, cell)| self.lattice.cell(position) != *cell)
+            .count();
+        self.lattice = self.lattice.next_generation(&positions, cells);
+        self.generation += 1;
+        changed
+    }
+
+    /// Advance one generation, returning how many cells changed.
+    pub fn step(&mut self) -> usize {
+        let positions = self.lattice.candidates();
+        let cells = positions
+            .iter()
+            .map(|&position| self.next_cell(position))
+            .collect();
+        self.advance(positions, cells)
+    }
+
+    /// Advance `generations` generations.
+    pub fn run(&mut self, generations: usize) -> &L {
+        for _ in 0..generations {
+            self.step();
+        }
+        &self.lattice
+    }
+
+    /// Advance until a generation leaves every cell unchanged, returning the
+    /// number of generations that did change something. Never returns if the
+    /// lattice doesn't settle.
+    pub fn run_to_fixpoint(&mut self) -> usize {
+        let mut changing = 0;
+        while self.step() > 0 {
+            changing += 1;
+        }
+        changing
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<L, N, R> Automaton<L, N, R>
+where
+    L: Lattice + Sync,
+    L::Position: Send + Sync,
+    L::Cell: PartialEq + Send,
+    N: Neighborhood<L> + Sync,
+    R: Fn(&L::Cell, &[&L::Cell]) -> L::Cell + Sync,
+{
+    /// Same as [`step`](Self::step), computing the new cells on the rayon
+    /// thread pool.
+    pub fn par_step(&mut self) -> usize {
+        use rayon::prelude::*;
+
+        let positions = self.lattice.candidates();
+        let cells = positions
+            .par_iter()
+            .map(|&position| self.next_cell(position))
+            .collect();
+        self.advance(positions, cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Adjacent, Automaton, LineOfSight, Neighborhood, Orthogonal};
+    use crate::{Grid, SparseGrid, Vector2};
+
+    fn life(&alive: &bool, neighbors: &[&bool]) -> bool {
+        let count = neighbors.iter().filter(|&&&n| n).count();
+        count == 3 || (alive && count == 2)
+    }
+
+    fn alive_cells(rows: &[&str]) -> Grid<bool> {
+        let rows = rows
+            .iter()
+            .map(|row| row.chars().map(|ch| ch == '#').collect())
+            .collect();
+        Grid::from_rows(rows).unwrap()
+    }
+
+    #[test]
+    fn dense_test() {
+        let blinker = alive_cells(&[".....", "..#..", "..#..", "..#..", "....."]);
+        let mut automaton = Automaton::new(blinker.clone(), Adjacent, life);
+        assert_eq!(automaton.step(), 4);
+        assert_eq!(
+            automaton
+                .lattice()
+                .map(|&alive| if alive { '#' } else { '.' })
+                .to_string(),
+            ".....\n.....\n.###.\n.....\n.....\n"
+        );
+        automaton.run(3);
+        assert_eq!(automaton.generation(), 4);
+        assert_eq!(automaton.lattice(), &blinker);
+
+        // flood fill, each cell taking the largest value around it
+        let grid = Grid::from_fn(4, 3, |p| if p == Vector2::new(1, 1) { 1 } else { 0 });
+        let spread = |&cell: &u8, neighbors: &[&u8]| neighbors.iter().fold(cell, |a, &&b| a.max(b));
+        let mut automaton = Automaton::new(grid, Orthogonal, spread);
+        assert_eq!(automaton.run_to_fixpoint(), 3);
+        assert_eq!(automaton.generation(), 4);
+        assert!(automaton.lattice().iter().all(|&cell| cell == 1));
+    }
+
+    #[test]
+    fn line_of_sight_test() {
+        let grid = alive_cells(&["#.#.", "....", "#..#"]);
+        let sight = LineOfSight(|&seat: &bool| !seat);
+        let seen = |x, y| sight.neighbors(&grid, Vector2::new(x, y)).len();
+        assert_eq!(seen(0, 0), 2);
+        assert_eq!(seen(0, 2), 3);
+        assert_eq!(seen(3, 2), 1);
+        assert_eq!(seen(1, 0), 3);
+    }
+
+    #[test]
+    fn sparse_test() {
+        let mut glider = SparseGrid::new(false);
+        glider.extend([[1, 0], [2, 1], [0, 2], [1, 2], [2, 2]].map(|p| (p, true)));
+        let mut automaton = Automaton::new(glider.clone(), Adjacent, life);
+        automaton.run(4);
+        let moved: Vec<_> = glider
+            .iter()
+            .map(|([x, y], _)| ([x + 1, y + 1], true))
+            .collect();
+        assert_eq!(automaton.lattice(), &SparseGrid::from_iter(moved));
+
+        let mut cube = SparseGrid::<bool, 3>::new(false);
+        cube.extend([[1, 0, 0], [2, 1, 0], [0, 2, 0], [1, 2, 0], [2, 2, 0]].map(|p| (p, true)));
+        let mut automaton = Automaton::new(cube, Adjacent, life);
+        assert_eq!(automaton.run(1).len(), 11);
+
+        let mut seed = SparseGrid::<bool, 2>::new(false);
+        seed.insert([0, 0], true);
+        let grow = |&cell: &bool, neighbors: &[&bool]| cell || neighbors.iter().any(|&&n| n);
+        let mut automaton = Automaton::new(seed, Orthogonal, grow);
+        assert_eq!(automaton.run(2).len(), 13);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_test() {
+        let grid = Grid::from_fn(16, 16, |p| (p.x * 7 + p.y * 3) % 5 == 0);
+        let mut serial = Automaton::new(grid.clone(), Adjacent, life);
+        let mut parallel = Automaton::new(grid, Adjacent, life);
+        for _ in 0..10 {
+            assert_eq!(serial.step(), parallel.par_step());
+            assert_eq!(serial.lattice(), parallel.lattice());
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn sparse_parallel_test() {
+        let mut cube = SparseGrid::<bool, 3>::new(false);
+        cube.extend([[1, 0, 0], [2, 1, 0], [0, 2, 0], [1, 2, 0], [2, 2, 0]].map(|p| (p, true)));
+        let mut serial = Automaton::new(cube.clone(), Adjacent, life);
+        let mut parallel = Automaton::new(cube, Adjacent, life);
+        for _ in 0..4 {
+            assert_eq!(serial.step(), parallel.par_step());
+            assert_eq!(serial.lattice(), parallel.lattice());
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{Coordinate, Vector2};
+
+/// One of the four axis-aligned directions in the plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// All directions, clockwise from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Parse the usual single character spellings: `N`/`U`/`^`, `E`/`R`/`>`,
+    /// `S`/`D`/`v` and `W`/`L`/`<`.
+    pub fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            'N' | 'U' | '^' => Some(Direction::North),
+            'E' | 'R' | '>' => Some(Direction::East),
+            'S' | 'D' | 'v' => Some(Direction::South),
+            'W' | 'L' | '<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Unit step in this direction, north being positive `y`.
+    pub fn to_vector<T: Coordinate>(self) -> Vector2<T> {
+        match self {
+            Direction::North => Vector2::new(T::ZERO, T::ONE),
+            Direction::East => Vector2::new(T::ONE, T::ZERO),
+            Direction::South => Vector2::new(T::ZERO, -T::ONE),
+            Direction::West => Vector2::new(-T::ONE, T::ZERO),
+        }
+    }
+
+    /// Unit step in this direction on a grid whose rows grow downwards, like
+    /// [`Grid`](crate::Grid): north is the row above, negative `y`.
+    pub fn to_grid_vector<T: Coordinate>(self) -> Vector2<T> {
+        flip_y(self.to_vector())
+    }
+}
+
+fn flip_y<T: Coordinate>(vector: Vector2<T>) -> Vector2<T> {
+    Vector2::new(vector.x, -vector.y)
+}
+
+/// One of the four axis-aligned or four diagonal directions in the plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// All directions, clockwise from north.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    /// Eighth of a turn counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    /// Eighth of a turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// Single step in this direction, diagonal steps moving along both axes.
+    pub fn to_vector<T: Coordinate>(self) -> Vector2<T> {
+        let (north, east) = (Direction::North.to_vector(), Direction::East.to_vector());
+        match self {
+            Direction8::North => north,
+            Direction8::NorthEast => north + east,
+            Direction8::East => east,
+            Direction8::SouthEast => east - north,
+            Direction8::South => -north,
+            Direction8::SouthWest => -north - east,
+            Direction8::West => -east,
+            Direction8::NorthWest => north - east,
+        }
+    }
+
+    /// Single step in this direction on a grid whose rows grow downwards, see
+    /// [`Direction::to_grid_vector`].
+    pub fn to_grid_vector<T: Coordinate>(self) -> Vector2<T> {
+        flip_y(self.to_vector())
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Direction8::ALL[2 * direction as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Direction8};
+    use crate::{Grid, Vector2};
+
+    #[test]
+    fn turn_test() {
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+        assert_eq!(Direction::East.opposite(), Direction::West);
+        assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::SouthWest.opposite(), Direction8::NorthEast);
+        for direction in Direction::ALL {
+            let step: Vector2<i32> = direction.to_vector();
+            assert_eq!(direction.turn_left().to_vector(), step.rotate_left());
+            assert_eq!(direction.turn_right().to_vector(), step.rotate_right());
+            assert_eq!(Direction8::from(direction).to_vector(), step);
+        }
+    }
+
+    #[test]
+    fn vector_test() {
+        assert_eq!(Direction::from_char('^'), Some(Direction::North));
+        assert_eq!(Direction::from_char('L'), Some(Direction::West));
+        assert_eq!(Direction::from_char('x'), None);
+        assert_eq!(Direction::South.to_vector(), Vector2::new(0, -1));
+        assert_eq!(Direction8::SouthEast.to_vector(), Vector2::new(1, -1));
+        let ring: Vec<Vector2<i64>> = Direction8::ALL.iter().map(|d| d.to_vector()).collect();
+        assert_eq!(ring.len(), 8);
+        assert!(ring.iter().all(|v| v.chebyshev_norm() == 1));
+    }
+
+    #[test]
+    fn grid_vector_test() {
+        let grid = Grid::from_rows(
+            ["abc", "def", "ghi"]
+                .map(|row| row.chars().collect())
+                .to_vec(),
+        )
+        .unwrap();
+        let center = Vector2::new(1, 1);
+        let neighbor = |direction: Direction| grid[center + direction.to_grid_vector()];
+        assert_eq!(neighbor(Direction::North), 'b');
+        assert_eq!(neighbor(Direction::East), 'f');
+        assert_eq!(neighbor(Direction::South), 'h');
+        assert_eq!(neighbor(Direction::West), 'd');
+        assert_eq!(grid[center + Direction8::NorthEast.to_grid_vector()], 'c');
+        for direction in Direction::ALL {
+            // turns of the enum hold on a grid too, only the vector's own
+            // rotations swap
+            let step: Vector2<i64> = direction.to_grid_vector();
+            assert_eq!(direction.turn_right().to_grid_vector(), step.rotate_left());
+            assert_eq!(Direction8::from(direction).to_grid_vector(), step);
+        }
+    }
+}
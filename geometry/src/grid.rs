@@ -0,0 +1,396 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+use thiserror::Error;
+
+use crate::Vector2;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum GridError {
+    #[error("expected {expected} cells, found {found}")]
+    WrongCellCount { expected: usize, found: usize },
+    #[error("row {row} has {found} cells, expected {expected}")]
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+/// A rectangular grid of cells stored in row-major order.
+///
+/// Cells are addressed by [`Vector2`] positions with `x` the column and `y`
+/// the row, rows growing downwards from `0` at the top. Out-of-bounds lookups
+/// return `None` rather than wrapping or panicking, except through `Index`.
+///
+/// Character maps from the puzzle input are parsed with `parse::Span::grid`,
+/// which reports bad cells at their line and column.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid with `cells` listed row by row.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Result<Self, GridError> {
+        if cells.len() != width * height {
+            return Err(GridError::WrongCellCount {
+                expected: width * height,
+                found: cells.len(),
+            });
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Vector2<i64>) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| Vector2::new(col as i64, row as i64)))
+            .map(&mut f)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        let mut cells = Vec::with_capacity(width * height);
+        for (row, cells_in_row) in rows.into_iter().enumerate() {
+            if cells_in_row.len() != width {
+                return Err(GridError::RaggedRow {
+                    row,
+                    expected: width,
+                    found: cells_in_row.len(),
+                });
+            }
+            cells.extend(cells_in_row);
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: Vector2<i64>) -> bool {
+        (0..self.width as i64).contains(&position.x)
+            && (0..self.height as i64).contains(&position.y)
+    }
+
+    fn offset(&self, position: Vector2<i64>) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.y as usize * self.width + position.x as usize)
+    }
+
+    pub fn get(&self, position: Vector2<i64>) -> Option<&T> {
+        self.offset(position).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, position: Vector2<i64>) -> Option<&mut T> {
+        self.offset(position).map(|offset| &mut self.cells[offset])
+    }
+
+    /// The cell at `position` on a grid that repeats in every direction.
+    pub fn get_wrapping(&self, position: Vector2<i64>) -> Option<&T> {
+        if self.cells.is_empty() {
+            return None;
+        }
+        self.get(Vector2::new(
+            position.x.rem_euclid(self.width as i64),
+            position.y.rem_euclid(self.height as i64),
+        ))
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Vector2<i64>> {
+        let width = self.width;
+        (0..self.cells.len())
+            .map(move |offset| Vector2::new((offset % width) as i64, (offset / width) as i64))
+    }
+
+    /// All cells in row-major order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// All cells along with their positions, in row-major order.
+    pub fn indexed(&self) -> impl Iterator<Item = (Vector2<i64>, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(col < self.width, "column {col} out of bounds");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// Cells on the diagonal running down and to the right through
+    /// `position`, from its top end.
+    pub fn diagonal(&self, position: Vector2<i64>) -> impl Iterator<Item = &T> {
+        let back = position.x.min(position.y);
+        let start = position - Vector2::new(back, back);
+        self.get(start)
+            .into_iter()
+            .chain(self.ray(start, Vector2::new(1, 1)).map(|(_, cell)| cell))
+    }
+
+    /// Cells on the diagonal running down and to the left through
+    /// `position`, from its top end.
+    pub fn anti_diagonal(&self, position: Vector2<i64>) -> impl Iterator<Item = &T> {
+        let back = (self.width as i64 - 1 - position.x).min(position.y);
+        let start = position + Vector2::new(back, -back);
+        self.get(start)
+            .into_iter()
+            .chain(self.ray(start, Vector2::new(-1, 1)).map(|(_, cell)| cell))
+    }
+
+    /// The in-bounds cells of the 4-neighbourhood of `position`.
+    pub fn orthogonal_neighbors(
+        &self,
+        position: Vector2<i64>,
+    ) -> impl Iterator<Item = (Vector2<i64>, &T)> {
+        position
+            .orthogonal_neighbors()
+            .into_iter()
+            .filter_map(|neighbor| self.get(neighbor).map(|cell| (neighbor, cell)))
+    }
+
+    /// The in-bounds cells of the 8-neighbourhood of `position`.
+    pub fn all_neighbors(
+        &self,
+        position: Vector2<i64>,
+    ) -> impl Iterator<Item = (Vector2<i64>, &T)> {
+        position
+            .all_neighbors()
+            .into_iter()
+            .filter_map(|neighbor| self.get(neighbor).map(|cell| (neighbor, cell)))
+    }
+
+    /// Cells met walking from `from` in steps of `step` until leaving the
+    /// grid, not including `from` itself.
+    pub fn ray(&self, from: Vector2<i64>, step: Vector2<i64>) -> Ray<'_, T> {
+        Ray {
+            grid: self,
+            position: from,
+            step,
+        }
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Mirror the grid in its main diagonal, so that rows become columns.
+    pub fn transpose(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |position| {
+            self[Vector2::new(position.y, position.x)].clone()
+        })
+    }
+
+    /// Quarter turn clockwise as displayed, the first column becoming the
+    /// first row reversed.
+    pub fn rotate_right(&self) -> Self {
+        let last_row = self.height as i64 - 1;
+        Grid::from_fn(self.height, self.width, |position| {
+            self[Vector2::new(position.y, last_row - position.x)].clone()
+        })
+    }
+
+    /// Quarter turn counterclockwise as displayed, the last column becoming
+    /// the first row.
+    pub fn rotate_left(&self) -> Self {
+        let last_col = self.width as i64 - 1;
+        Grid::from_fn(self.height, self.width, |position| {
+            self[Vector2::new(last_col - position.y, position.x)].clone()
+        })
+    }
+}
+
+impl<T> Index<Vector2<i64>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Vector2<i64>) -> &Self::Output {
+        self.get(position)
+            .unwrap_or_else(|| panic!("position {position} out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Vector2<i64>> for Grid<T> {
+    fn index_mut(&mut self, position: Vector2<i64>) -> &mut Self::Output {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("position {position} out of bounds"))
+    }
+}
+
+/// Renders one line per row, with each cell's own rendering concatenated.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the positions and cells along a straight line, see
+/// [`Grid::ray`].
+#[derive(Debug, Clone)]
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    position: Vector2<i64>,
+    step: Vector2<i64>,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Vector2<i64>, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.step == Vector2::ZERO {
+            return None;
+        }
+        self.position += self.step;
+        let grid: &'a Grid<T> = self.grid;
+        grid.get(self.position).map(|cell| (self.position, cell))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, GridError};
+    use crate::Vector2;
+
+    fn test_grid_1() -> Grid<u32> {
+        Grid::from_rows(vec![
+            vec![1, 2, 3],
+            vec![4, 5, 6],
+            vec![7, 8, 9],
+            vec![10, 11, 12],
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn construction_test() {
+        let grid = test_grid_1();
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid[Vector2::new(2, 1)], 6);
+        assert_eq!(grid.get(Vector2::new(3, 1)), None);
+        assert_eq!(grid.get(Vector2::new(0, -1)), None);
+        assert_eq!(grid.get_wrapping(Vector2::new(3, -1)), Some(&10));
+        assert_eq!(
+            Grid::from_rows(vec![vec![1, 2], vec![3]]),
+            Err(GridError::RaggedRow {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Grid::new(2, 2, vec![1, 2, 3]),
+            Err(GridError::WrongCellCount {
+                expected: 4,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn lines_test() {
+        let grid = test_grid_1();
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), [3, 6, 9, 12]);
+        assert_eq!(grid.rows().count(), 4);
+        assert_eq!(grid.columns().count(), 3);
+        let diagonal: Vec<u32> = grid.diagonal(Vector2::new(1, 2)).copied().collect();
+        assert_eq!(diagonal, [4, 8, 12]);
+        let anti_diagonal: Vec<u32> = grid.anti_diagonal(Vector2::new(1, 1)).copied().collect();
+        assert_eq!(anti_diagonal, [3, 5, 7]);
+        let ray: Vec<_> = grid.ray(Vector2::new(0, 0), Vector2::new(1, 2)).collect();
+        assert_eq!(ray, [(Vector2::new(1, 2), &8)]);
+    }
+
+    #[test]
+    fn neighbors_test() {
+        let grid = test_grid_1();
+        let mut corner: Vec<u32> = grid
+            .orthogonal_neighbors(Vector2::new(0, 0))
+            .map(|(_, &cell)| cell)
+            .collect();
+        corner.sort_unstable();
+        assert_eq!(corner, [2, 4]);
+        assert_eq!(grid.all_neighbors(Vector2::new(0, 0)).count(), 3);
+        assert_eq!(grid.all_neighbors(Vector2::new(1, 1)).count(), 8);
+        assert_eq!(grid.all_neighbors(Vector2::new(2, 3)).count(), 3);
+    }
+
+    #[test]
+    fn transform_test() {
+        let grid = Grid::new(3, 2, vec![1, 2, 3, 4, 5, 6]).unwrap();
+        assert_eq!(
+            grid.transpose(),
+            Grid::new(2, 3, vec![1, 4, 2, 5, 3, 6]).unwrap()
+        );
+        assert_eq!(
+            grid.rotate_right(),
+            Grid::new(2, 3, vec![4, 1, 5, 2, 6, 3]).unwrap()
+        );
+        assert_eq!(
+            grid.rotate_left(),
+            Grid::new(2, 3, vec![3, 6, 2, 5, 1, 4]).unwrap()
+        );
+        assert_eq!(grid.rotate_left().rotate_right(), grid);
+        assert_eq!(grid.map(|cell| cell * 2).iter().sum::<i32>(), 42);
+        assert_eq!(grid.to_string(), "123\n456\n");
+    }
+}
@@ -0,0 +1,21 @@
+//! Integer vectors, directions, dense and sparse grids and a cellular
+//! automaton engine shared by the puzzle solutions.
+//!
+//! Unless stated otherwise, `x` grows to the right and `y` grows upwards.
+//! Grid-style inputs where rows grow downwards, [`Grid`] included, treat `y`
+//! as the row and take their steps from [`Direction::to_grid_vector`], so
+//! that north is still the row above and turning a [`Direction`] still
+//! turns the right way. Only [`Vector2::rotate_left`] and
+//! [`Vector2::rotate_right`] swap there.
+
+mod automaton;
+mod direction;
+mod grid;
+mod sparse;
+mod vector;
+
+pub use automaton::{Adjacent, Automaton, Lattice, LineOfSight, Neighborhood, Orthogonal};
+pub use direction::{Direction, Direction8};
+pub use grid::{Grid, GridError, Ray};
+pub use sparse::{Bounds, SparseGrid};
+pub use vector::{Coordinate, Vector2, Vector3, Vector4};
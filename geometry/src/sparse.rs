@@ -0,0 +1,333 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    sync::OnceLock,
+};
+
+/// Inclusive axis-aligned box of integer points in `D` dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds<const D: usize> {
+    pub min: [i64; D],
+    pub max: [i64; D],
+}
+
+impl<const D: usize> Bounds<D> {
+    /// The box from `min` to `max`, which is empty unless `min` is at most
+    /// `max` along every axis.
+    pub fn new(min: [i64; D], max: [i64; D]) -> Self {
+        Bounds { min, max }
+    }
+
+    /// The box containing just `point`.
+    pub fn point(point: [i64; D]) -> Self {
+        Bounds {
+            min: point,
+            max: point,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (0..D).any(|axis| self.min[axis] > self.max[axis])
+    }
+
+    pub fn contains(&self, point: [i64; D]) -> bool {
+        (0..D).all(|axis| (self.min[axis]..=self.max[axis]).contains(&point[axis]))
+    }
+
+    /// Grow the box just enough to contain `point`.
+    pub fn include(&mut self, point: [i64; D]) {
+        for (axis, &coordinate) in point.iter().enumerate() {
+            self.min[axis] = self.min[axis].min(coordinate);
+            self.max[axis] = self.max[axis].max(coordinate);
+        }
+    }
+
+    /// The box grown by `margin` on every side, shrunk if negative.
+    pub fn expand(&self, margin: i64) -> Self {
+        Bounds {
+            min: self.min.map(|coordinate| coordinate - margin),
+            max: self.max.map(|coordinate| coordinate + margin),
+        }
+    }
+
+    /// Number of points along each axis.
+    pub fn size(&self) -> [usize; D] {
+        std::array::from_fn(|axis| (self.max[axis] - self.min[axis] + 1).max(0) as usize)
+    }
+
+    /// Every point in the box in row-major order, i.e. with the first axis
+    /// varying fastest and the last axis slowest.
+    pub fn positions(&self) -> impl Iterator<Item = [i64; D]> {
+        let Bounds { min, max } = *self;
+        let first = (!self.is_empty()).then_some(min);
+        std::iter::successors(first, move |&point| {
+            let mut next = point;
+            for axis in 0..D {
+                if next[axis] < max[axis] {
+                    next[axis] += 1;
+                    return Some(next);
+                }
+                next[axis] = min[axis];
+            }
+            None
+        })
+    }
+}
+
+/// An unbounded grid in `D` dimensions that only stores the cells it was
+/// given, every other cell reading as a default value.
+///
+/// Positions are `[i64; D]` arrays, so the vector types convert straight
+/// into them. The bounding box of the stored cells is kept up to date as
+/// cells come and go. In two dimensions `x` is the column and `y` the row,
+/// rows growing downwards as in [`Grid`](crate::Grid).
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T, const D: usize> {
+    cells: HashMap<[i64; D], T>,
+    default: T,
+    // emptied when a cell on the edge of the box is removed, the box is then
+    // recomputed on the next call to `bounds`
+    bounds: OnceLock<Option<Bounds<D>>>,
+}
+
+impl<T, const D: usize> SparseGrid<T, D> {
+    /// An empty grid where every cell reads as `default`.
+    pub fn new(default: T) -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            default,
+            bounds: OnceLock::from(None),
+        }
+    }
+
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    /// Number of stored cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Whether a cell is stored at `position`.
+    pub fn contains(&self, position: impl Into<[i64; D]>) -> bool {
+        self.cells.contains_key(&position.into())
+    }
+
+    /// The cell at `position`, or the default value if none is stored.
+    pub fn get(&self, position: impl Into<[i64; D]>) -> &T {
+        self.cells.get(&position.into()).unwrap_or(&self.default)
+    }
+
+    /// The stored cell at `position`, if any.
+    pub fn get_mut(&mut self, position: impl Into<[i64; D]>) -> Option<&mut T> {
+        self.cells.get_mut(&position.into())
+    }
+
+    /// Store `value` at `position`, returning the cell it replaced.
+    pub fn insert(&mut self, position: impl Into<[i64; D]>, value: T) -> Option<T> {
+        let position = position.into();
+        match self.bounds.get_mut() {
+            Some(Some(bounds)) => bounds.include(position),
+            Some(bounds) => *bounds = Some(Bounds::point(position)),
+            None => {}
+        }
+        self.cells.insert(position, value)
+    }
+
+    /// Remove the cell at `position`, returning it if one was stored.
+    pub fn remove(&mut self, position: impl Into<[i64; D]>) -> Option<T> {
+        let position = position.into();
+        let removed = self.cells.remove(&position);
+        if removed.is_some() {
+            if let Some(Some(bounds)) = self.bounds.get() {
+                let on_edge = (0..D).any(|axis| {
+                    position[axis] == bounds.min[axis] || position[axis] == bounds.max[axis]
+                });
+                if on_edge {
+                    self.bounds.take();
+                }
+            }
+        }
+        removed
+    }
+
+    /// Smallest box containing every stored cell, `None` if there are none.
+    pub fn bounds(&self) -> Option<Bounds<D>> {
+        *self.bounds.get_or_init(|| {
+            let mut positions = self.cells.keys();
+            positions.next().map(|&first| {
+                let mut bounds = Bounds::point(first);
+                positions.for_each(|&position| bounds.include(position));
+                bounds
+            })
+        })
+    }
+
+    /// The stored cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = ([i64; D], &T)> {
+        self.cells.iter().map(|(&position, cell)| (position, cell))
+    }
+
+    /// Every cell within the bounds, default ones included, in row-major
+    /// order.
+    pub fn iter_bounds(&self) -> impl Iterator<Item = ([i64; D], &T)> {
+        self.bounds()
+            .into_iter()
+            .flat_map(|bounds| bounds.positions())
+            .map(|position| (position, self.get(position)))
+    }
+}
+
+impl<T> SparseGrid<T, 2> {
+    /// Draw the bounded region one row per line, top row first.
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut out = String::new();
+        if let Some(bounds) = self.bounds() {
+            let [width, _] = bounds.size();
+            for (i, (_, cell)) in self.iter_bounds().enumerate() {
+                out.push(f(cell));
+                if (i + 1) % width == 0 {
+                    out.push('\n');
+                }
+            }
+        }
+        out
+    }
+}
+
+impl<T: Default, const D: usize> Default for SparseGrid<T, D> {
+    fn default() -> Self {
+        SparseGrid::new(T::default())
+    }
+}
+
+impl<T: PartialEq, const D: usize> PartialEq for SparseGrid<T, D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.default == other.default && self.cells == other.cells
+    }
+}
+
+impl<T: Eq, const D: usize> Eq for SparseGrid<T, D> {}
+
+impl<T, P: Into<[i64; D]>, const D: usize> Extend<(P, T)> for SparseGrid<T, D> {
+    fn extend<I: IntoIterator<Item = (P, T)>>(&mut self, iter: I) {
+        for (position, value) in iter {
+            self.insert(position, value);
+        }
+    }
+}
+
+impl<T: Default, P: Into<[i64; D]>, const D: usize> FromIterator<(P, T)> for SparseGrid<T, D> {
+    fn from_iter<I: IntoIterator<Item = (P, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::default();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T: Display> Display for SparseGrid<T, 2> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(bounds) = self.bounds() {
+            let [width, _] = bounds.size();
+            for (i, (_, cell)) in self.iter_bounds().enumerate() {
+                write!(f, "{cell}")?;
+                if (i + 1) % width == 0 {
+                    writeln!(f)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bounds, SparseGrid};
+    use crate::{Vector2, Vector3};
+
+    #[test]
+    fn bounds_test() {
+        let mut grid = SparseGrid::new('.');
+        assert_eq!(grid.bounds(), None);
+        grid.insert(Vector2::new(2, -1), '#');
+        grid.insert(Vector2::new(-3, 4), '#');
+        grid.insert(Vector2::new(0, 0), '#');
+        assert_eq!(grid.bounds(), Some(Bounds::new([-3, -1], [2, 4])));
+        grid.remove([0, 0]);
+        assert_eq!(grid.bounds(), Some(Bounds::new([-3, -1], [2, 4])));
+        grid.remove([2, -1]);
+        assert_eq!(grid.bounds(), Some(Bounds::point([-3, 4])));
+        grid.insert([1, 1], '#');
+        assert_eq!(grid.bounds(), Some(Bounds::new([-3, 1], [1, 4])));
+        grid.remove([-3, 4]);
+        grid.remove([1, 1]);
+        assert_eq!(grid.bounds(), None);
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn get_test() {
+        let mut grid: SparseGrid<u8, 3> = [(Vector3::new(1, 2, 3), 7)].into_iter().collect();
+        assert_eq!(*grid.get([1, 2, 3]), 7);
+        assert_eq!(*grid.get(Vector3::new(0, 0, 0)), 0);
+        assert!(grid.contains([1, 2, 3]));
+        assert!(!grid.contains([0, 0, 0]));
+        *grid.get_mut([1, 2, 3]).unwrap() += 1;
+        assert_eq!(grid.insert([1, 2, 3], 9), Some(8));
+        assert_eq!(grid.get_mut([0, 0, 0]), None);
+        assert_eq!(grid.len(), 1);
+    }
+
+    #[test]
+    fn iteration_test() {
+        let positions: Vec<_> = Bounds::new([0, 0, 0], [1, 2, 1]).positions().collect();
+        assert_eq!(positions.len(), 12);
+        assert_eq!(
+            &positions[..4],
+            [[0, 0, 0], [1, 0, 0], [0, 1, 0], [1, 1, 0]]
+        );
+        assert_eq!(positions[11], [1, 2, 1]);
+        assert_eq!(Bounds::new([0, 1], [3, 0]).positions().count(), 0);
+        assert!(Bounds::point([5, 5]).expand(1).contains([4, 6]));
+        assert_eq!(Bounds::point([5, 5]).expand(1).size(), [3, 3]);
+
+        let mut grid = SparseGrid::new(0);
+        grid.insert([1, 0], 1);
+        grid.insert([0, 1], 2);
+        let cells: Vec<_> = grid.iter_bounds().collect();
+        assert_eq!(
+            cells,
+            [([0, 0], &0), ([1, 0], &1), ([0, 1], &2), ([1, 1], &0)]
+        );
+    }
+
+    #[test]
+    fn render_test() {
+        let mut grid = SparseGrid::new('.');
+        grid.extend([([10, 5], '#'), ([12, 6], '#'), ([11, 7], 'o')]);
+        let expected = "#..\n..#\n.o.\n";
+        assert_eq!(grid.render(|&ch| ch), expected);
+        assert_eq!(grid.to_string(), expected);
+        assert_eq!(SparseGrid::<char, 2>::new('.').to_string(), "");
+    }
+
+    #[test]
+    fn shared_between_threads_test() {
+        let mut grid = SparseGrid::new(0);
+        grid.extend([([0, 0], 1), ([3, 2], 1)]);
+        grid.remove([3, 2]);
+        let bounds = std::thread::scope(|scope| {
+            let threads: Vec<_> = (0..2).map(|_| scope.spawn(|| grid.bounds())).collect();
+            threads
+                .into_iter()
+                .map(|thread| thread.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(bounds, [Some(Bounds::point([0, 0])); 2]);
+    }
+}
@@ -0,0 +1,371 @@
+use std::{
+    fmt::{self, Display},
+    iter::Sum,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+/// Signed integer types usable as vector components.
+pub trait Coordinate:
+    Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+
+    fn signum(self) -> Self {
+        match self.cmp(&Self::ZERO) {
+            std::cmp::Ordering::Less => -Self::ONE,
+            std::cmp::Ordering::Equal => Self::ZERO,
+            std::cmp::Ordering::Greater => Self::ONE,
+        }
+    }
+}
+
+macro_rules! impl_coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_coordinate!(i8, i16, i32, i64, i128, isize);
+
+macro_rules! vector {
+    ($(#[$meta:meta])* $name:ident, $dim:literal, $($field:ident),+) => {
+        $(#[$meta])*
+        #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name<T> {
+            $(pub $field: T),+
+        }
+
+        impl<T> $name<T> {
+            pub const fn new($($field: T),+) -> Self {
+                $name { $($field),+ }
+            }
+        }
+
+        impl<T: Coordinate> $name<T> {
+            pub const ZERO: Self = $name { $($field: T::ZERO),+ };
+
+            /// Sum of the absolute values of the components.
+            pub fn manhattan_norm(self) -> T {
+                T::ZERO $(+ self.$field.abs())+
+            }
+
+            /// Largest absolute value among the components.
+            pub fn chebyshev_norm(self) -> T {
+                T::ZERO $(.max(self.$field.abs()))+
+            }
+
+            pub fn manhattan_distance(self, other: Self) -> T {
+                (self - other).manhattan_norm()
+            }
+
+            pub fn chebyshev_distance(self, other: Self) -> T {
+                (self - other).chebyshev_norm()
+            }
+
+            /// Sign of each component, i.e. the single step along every axis
+            /// that moves towards `self` from the origin.
+            pub fn signum(self) -> Self {
+                $name { $($field: self.$field.signum()),+ }
+            }
+
+            /// Points one step away along exactly one axis, the
+            /// 4-neighbourhood in two dimensions.
+            pub fn orthogonal_neighbors(self) -> Vec<Self> {
+                let mut neighbors = Vec::with_capacity(2 * $dim);
+                $(
+                    let step = $name { $field: T::ONE, ..Self::ZERO };
+                    neighbors.push(self - step);
+                    neighbors.push(self + step);
+                )+
+                neighbors
+            }
+
+            /// Points at most one step away along every axis, the
+            /// 8-neighbourhood in two dimensions.
+            pub fn all_neighbors(self) -> Vec<Self> {
+                let mut offsets = vec![Self::ZERO];
+                $(
+                    offsets = offsets
+                        .into_iter()
+                        .flat_map(|offset| {
+                            [-T::ONE, T::ZERO, T::ONE].map(|delta| $name { $field: delta, ..offset })
+                        })
+                        .collect();
+                )+
+                offsets
+                    .into_iter()
+                    .filter(|&offset| offset != Self::ZERO)
+                    .map(|offset| self + offset)
+                    .collect()
+            }
+        }
+
+        impl<T> From<[T; $dim]> for $name<T> {
+            fn from(components: [T; $dim]) -> Self {
+                let [$($field),+] = components;
+                $name { $($field),+ }
+            }
+        }
+
+        impl<T> From<$name<T>> for [T; $dim] {
+            fn from(vector: $name<T>) -> Self {
+                [$(vector.$field),+]
+            }
+        }
+
+        /// The component along an axis by number, `x` being axis 0, for code
+        /// that treats every axis the same way.
+        impl<T> Index<usize> for $name<T> {
+            type Output = T;
+
+            fn index(&self, axis: usize) -> &T {
+                [$(&self.$field),+]
+                    .into_iter()
+                    .nth(axis)
+                    .unwrap_or_else(|| panic!("axis {axis} out of range for {} dimensions", $dim))
+            }
+        }
+
+        impl<T> IndexMut<usize> for $name<T> {
+            fn index_mut(&mut self, axis: usize) -> &mut T {
+                [$(&mut self.$field),+]
+                    .into_iter()
+                    .nth(axis)
+                    .unwrap_or_else(|| panic!("axis {axis} out of range for {} dimensions", $dim))
+            }
+        }
+
+        impl<T: Add<Output = T>> Add for $name<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                $name { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $name<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                $name { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $name<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                $name { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T: Mul<Output = T> + Copy> Mul<T> for $name<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self::Output {
+                $name { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $name<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$field += rhs.$field;)+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $name<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$field -= rhs.$field;)+
+            }
+        }
+
+        impl<T: MulAssign + Copy> MulAssign<T> for $name<T> {
+            fn mul_assign(&mut self, rhs: T) {
+                $(self.$field *= rhs;)+
+            }
+        }
+
+        impl<T: Coordinate> Sum for $name<T> {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, Add::add)
+            }
+        }
+
+        impl<T: Display> Display for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let components = [$(self.$field.to_string()),+];
+                write!(f, "({})", components.join(", "))
+            }
+        }
+    };
+}
+
+vector!(
+    /// A point or displacement in the plane.
+    Vector2, 2, x, y
+);
+vector!(
+    /// A point or displacement in space.
+    Vector3, 3, x, y, z
+);
+vector!(
+    /// A point or displacement in four dimensions, e.g. space and time.
+    Vector4, 4, x, y, z, w
+);
+
+impl<T: Coordinate> Vector2<T> {
+    /// Quarter turn counterclockwise about the origin.
+    pub fn rotate_left(self) -> Self {
+        Vector2::new(-self.y, self.x)
+    }
+
+    /// Quarter turn clockwise about the origin.
+    pub fn rotate_right(self) -> Self {
+        Vector2::new(self.y, -self.x)
+    }
+
+    /// Rotate by `quarter_turns` counterclockwise, clockwise if negative.
+    pub fn rotate(self, quarter_turns: i32) -> Self {
+        match quarter_turns.rem_euclid(4) {
+            0 => self,
+            1 => self.rotate_left(),
+            2 => -self,
+            _ => self.rotate_right(),
+        }
+    }
+}
+
+impl<T: Coordinate> Vector3<T> {
+    /// Quarter turn about the `x` axis, counterclockwise when looking from
+    /// positive `x` towards the origin.
+    pub fn rotate_x(self) -> Self {
+        Vector3::new(self.x, -self.z, self.y)
+    }
+
+    /// Quarter turn about the `y` axis, counterclockwise when looking from
+    /// positive `y` towards the origin.
+    pub fn rotate_y(self) -> Self {
+        Vector3::new(self.z, self.y, -self.x)
+    }
+
+    /// Quarter turn about the `z` axis, counterclockwise when looking from
+    /// positive `z` towards the origin.
+    pub fn rotate_z(self) -> Self {
+        Vector3::new(-self.y, self.x, self.z)
+    }
+
+    /// The images of `self` under all 24 rotations that map the axes onto
+    /// axes, starting with the identity.
+    pub fn orientations(self) -> Vec<Self> {
+        // roll about the x axis, then pick where the x axis ends up
+        std::iter::successors(Some(self), |v| Some(v.rotate_x()))
+            .take(4)
+            .flat_map(|v| {
+                [
+                    v,
+                    v.rotate_z(),
+                    v.rotate_z().rotate_z(),
+                    v.rotate_z().rotate_z().rotate_z(),
+                    v.rotate_y(),
+                    v.rotate_y().rotate_y().rotate_y(),
+                ]
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{Vector2, Vector3, Vector4};
+
+    #[test]
+    fn arithmetic_test() {
+        let mut v = Vector2::new(3, -4);
+        assert_eq!(v + Vector2::new(1, 1), Vector2::new(4, -3));
+        assert_eq!(v - Vector2::new(1, 1), Vector2::new(2, -5));
+        assert_eq!(-v, Vector2::new(-3, 4));
+        assert_eq!(v * 2, Vector2::new(6, -8));
+        v += Vector2::new(1, 0);
+        v *= 3;
+        assert_eq!(v, Vector2::new(12, -12));
+        let total: Vector3<i32> = [Vector3::new(1, 2, 3), Vector3::new(-1, 0, 5)]
+            .into_iter()
+            .sum();
+        assert_eq!(total, Vector3::new(0, 2, 8));
+        assert_eq!(<[i64; 4]>::from(Vector4::from([1, 2, 3, 4])), [1, 2, 3, 4]);
+        assert_eq!(Vector3::new(1, -2, 3).to_string(), "(1, -2, 3)");
+        let mut v = Vector3::new(1, -2, 3);
+        v[2] += 1;
+        assert_eq!((v[0], v[1], v[2]), (1, -2, 4));
+    }
+
+    #[test]
+    fn norm_test() {
+        let v = Vector3::new(3, -7, 2);
+        assert_eq!(v.manhattan_norm(), 12);
+        assert_eq!(v.chebyshev_norm(), 7);
+        assert_eq!(v.signum(), Vector3::new(1, -1, 1));
+        assert_eq!(
+            Vector2::new(1, 1).manhattan_distance(Vector2::new(-2, 5)),
+            7
+        );
+        assert_eq!(
+            Vector2::new(1, 1).chebyshev_distance(Vector2::new(-2, 5)),
+            4
+        );
+    }
+
+    #[test]
+    fn neighbors_test() {
+        let origin = Vector2::<i64>::ZERO;
+        let four: HashSet<_> = origin.orthogonal_neighbors().into_iter().collect();
+        assert_eq!(
+            four,
+            HashSet::from([
+                Vector2::new(1, 0),
+                Vector2::new(-1, 0),
+                Vector2::new(0, 1),
+                Vector2::new(0, -1)
+            ])
+        );
+        let eight = Vector2::new(5, 5).all_neighbors();
+        assert_eq!(eight.len(), 8);
+        assert!(eight
+            .iter()
+            .all(|&v| Vector2::new(5, 5).chebyshev_distance(v) == 1));
+        assert_eq!(Vector3::new(0, 0, 0).orthogonal_neighbors().len(), 6);
+        assert_eq!(Vector3::new(0, 0, 0).all_neighbors().len(), 26);
+        assert_eq!(Vector4::new(0, 0, 0, 0).all_neighbors().len(), 80);
+    }
+
+    #[test]
+    fn rotation_test() {
+        let v = Vector2::new(2, 1);
+        assert_eq!(v.rotate_left(), Vector2::new(-1, 2));
+        assert_eq!(v.rotate_right(), Vector2::new(1, -2));
+        assert_eq!(v.rotate(2), Vector2::new(-2, -1));
+        assert_eq!(v.rotate(-1), v.rotate_right());
+        assert_eq!(v.rotate(5), v.rotate_left());
+        let x = Vector3::new(1, 0, 0);
+        assert_eq!(x.rotate_z(), Vector3::new(0, 1, 0));
+        assert_eq!(x.rotate_y(), Vector3::new(0, 0, -1));
+        assert_eq!(Vector3::new(0, 1, 0).rotate_x(), Vector3::new(0, 0, 1));
+        let orientations = Vector3::new(1, 2, 3).orientations();
+        assert_eq!(orientations[0], Vector3::new(1, 2, 3));
+        assert_eq!(orientations.iter().collect::<HashSet<_>>().len(), 24);
+    }
+}
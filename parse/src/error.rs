@@ -0,0 +1,28 @@
+use thiserror::Error;
+
+/// What went wrong while parsing, see [`ParseError`] for where.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("expected {expected}, found {found:?}")]
+    Expected { expected: String, found: String },
+    #[error("invalid value {found:?}: {reason}")]
+    InvalidValue { found: String, reason: String },
+    #[error("expected {expected} values, found {found}")]
+    WrongCount { expected: usize, found: usize },
+    #[error("unexpected character {0:?}")]
+    UnexpectedChar(char),
+    #[error("row has {found} cells, expected {expected}")]
+    RaggedRow { expected: usize, found: usize },
+    #[error("{0}")]
+    Custom(String),
+}
+
+/// Malformed input, located by 1-based line and column. Columns count
+/// characters, not bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("line {line}, column {column}: {kind}")]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ErrorKind,
+}
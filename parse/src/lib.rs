@@ -0,0 +1,13 @@
+//! Helpers for the shapes puzzle inputs come in: lines, blank-line separated
+//! blocks, separated lists, `key: value` records, integers scattered through
+//! a line and character grids.
+//!
+//! Everything works on [`Span`]s, string slices that remember where in the
+//! input they start, so malformed input is reported as a [`ParseError`]
+//! with the line and column it was found at.
+
+mod error;
+mod span;
+
+pub use error::{ErrorKind, ParseError};
+pub use span::Span;
@@ -0,0 +1,464 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use geometry::Grid;
+
+use crate::{ErrorKind, ParseError};
+
+/// A slice of the puzzle input that knows the line and column it starts at.
+///
+/// Spans are cheap to copy, and every span carved out of another one keeps
+/// its position in the original input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    text: &'a str,
+    line: usize,
+    column: usize,
+}
+
+/// Walks forward through the text of a span, keeping track of line and
+/// column so that carving out many pieces stays linear in the length.
+struct Cursor<'a> {
+    span: Span<'a>,
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(span: Span<'a>) -> Self {
+        Cursor {
+            span,
+            offset: 0,
+            line: span.line,
+            column: span.column,
+        }
+    }
+
+    /// The piece from byte `start` to byte `end` of the text, `start` being
+    /// no earlier than on the previous call.
+    fn span(&mut self, start: usize, end: usize) -> Span<'a> {
+        for ch in self.span.text[self.offset..start].chars() {
+            if ch == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        self.offset = start;
+        Span {
+            text: &self.span.text[start..end],
+            line: self.line,
+            column: self.column,
+        }
+    }
+}
+
+impl<'a> Span<'a> {
+    /// The whole input, starting at line 1, column 1.
+    pub fn new(text: &'a str) -> Self {
+        Span {
+            text,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// An error of the given kind located at the start of the span.
+    pub fn error(&self, kind: ErrorKind) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            kind,
+        }
+    }
+
+    /// An error saying that `expected` was expected instead of the span.
+    pub fn expected(&self, expected: impl Into<String>) -> ParseError {
+        self.error(ErrorKind::Expected {
+            expected: expected.into(),
+            found: self.text.to_string(),
+        })
+    }
+
+    fn offset_of(&self, piece: &str) -> usize {
+        piece.as_ptr() as usize - self.text.as_ptr() as usize
+    }
+
+    /// The span of `piece`, which must be a slice of this span's text.
+    fn sub(&self, piece: &'a str) -> Span<'a> {
+        let start = self.offset_of(piece);
+        Cursor::new(*self).span(start, start + piece.len())
+    }
+
+    pub fn trim(&self) -> Span<'a> {
+        self.sub(self.text.trim())
+    }
+
+    /// The lines of the span, as split by [`str::lines`].
+    pub fn lines(&self) -> impl Iterator<Item = Span<'a>> {
+        let span = *self;
+        self.text.lines().enumerate().map(move |(i, line)| Span {
+            text: line,
+            line: span.line + i,
+            column: if i == 0 { span.column } else { 1 },
+        })
+    }
+
+    /// Runs of non-blank lines, separated by one or more blank lines.
+    pub fn blocks(&self) -> impl Iterator<Item = Span<'a>> {
+        let span = *self;
+        let is_blank = |line: &Span| line.text.trim().is_empty();
+        let mut lines = self.lines().peekable();
+        std::iter::from_fn(move || {
+            while lines.next_if(is_blank).is_some() {}
+            let first = lines.next()?;
+            let mut last = first;
+            while let Some(line) = lines.next_if(|line| !is_blank(line)) {
+                last = line;
+            }
+            let start = span.offset_of(first.text);
+            let end = span.offset_of(last.text) + last.text.len();
+            Some(Span {
+                text: &span.text[start..end],
+                line: first.line,
+                column: first.column,
+            })
+        })
+    }
+
+    /// The pieces between occurrences of `separator`, as split by
+    /// [`str::split`].
+    pub fn split<'s>(&self, separator: &'s str) -> impl Iterator<Item = Span<'a>> + 's
+    where
+        'a: 's,
+    {
+        let span = *self;
+        let mut cursor = Cursor::new(span);
+        self.text.split(separator).map(move |piece| {
+            let start = span.offset_of(piece);
+            cursor.span(start, start + piece.len())
+        })
+    }
+
+    /// The pieces between runs of whitespace.
+    pub fn words(&self) -> impl Iterator<Item = Span<'a>> {
+        let span = *self;
+        let mut cursor = Cursor::new(span);
+        self.text.split_whitespace().map(move |word| {
+            let start = span.offset_of(word);
+            cursor.span(start, start + word.len())
+        })
+    }
+
+    /// The parts before and after the first `separator`.
+    pub fn split_once(&self, separator: &str) -> Result<(Span<'a>, Span<'a>), ParseError> {
+        let (before, after) = self
+            .text
+            .split_once(separator)
+            .ok_or_else(|| self.expected(format!("{separator:?}")))?;
+        Ok((self.sub(before), self.sub(after)))
+    }
+
+    pub fn strip_prefix(&self, prefix: &str) -> Result<Span<'a>, ParseError> {
+        self.text
+            .strip_prefix(prefix)
+            .map(|rest| self.sub(rest))
+            .ok_or_else(|| self.expected(format!("{prefix:?} at the start")))
+    }
+
+    pub fn strip_suffix(&self, suffix: &str) -> Result<Span<'a>, ParseError> {
+        self.text
+            .strip_suffix(suffix)
+            .map(|rest| self.sub(rest))
+            .ok_or_else(|| self.expected(format!("{suffix:?} at the end")))
+    }
+
+    /// The first character and the rest of the span, `None` if the span is
+    /// empty.
+    pub fn split_first(&self) -> Option<(char, Span<'a>)> {
+        let first = self.text.chars().next()?;
+        Some((first, self.sub(&self.text[first.len_utf8()..])))
+    }
+
+    /// Parse the whole span with [`FromStr`].
+    pub fn parse<T: FromStr>(&self) -> Result<T, ParseError>
+    where
+        T::Err: Display,
+    {
+        self.text.parse().map_err(|err: T::Err| {
+            self.error(ErrorKind::InvalidValue {
+                found: self.text.to_string(),
+                reason: err.to_string(),
+            })
+        })
+    }
+
+    /// Parse every piece of a `separator` separated list, ignoring
+    /// whitespace around the pieces.
+    pub fn parse_list<T: FromStr>(&self, separator: &str) -> Result<Vec<T>, ParseError>
+    where
+        T::Err: Display,
+    {
+        self.split(separator)
+            .map(|item| item.trim().parse())
+            .collect()
+    }
+
+    /// The key and value of a `key: value` style pair, without surrounding
+    /// whitespace.
+    pub fn key_value(&self, separator: &str) -> Result<(Span<'a>, Span<'a>), ParseError> {
+        let (key, value) = self.split_once(separator)?;
+        Ok((key.trim(), value.trim()))
+    }
+
+    /// Whitespace separated `key:value` fields, e.g. `hcl:#ae17e1 iyr:2013`
+    /// with `":"` as the separator.
+    pub fn fields<'s>(
+        &self,
+        separator: &'s str,
+    ) -> impl Iterator<Item = Result<(Span<'a>, Span<'a>), ParseError>> + 's
+    where
+        'a: 's,
+    {
+        self.words().map(move |word| word.key_value(separator))
+    }
+
+    /// Every integer in the span, whatever surrounds them.
+    ///
+    /// A `-` right before the digits makes the integer negative, unless it
+    /// follows a letter or digit, so that `x=-2` is `-2` but `2-4` is `2` and
+    /// `4`.
+    pub fn integers<T: FromStr>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T::Err: Display,
+    {
+        let bytes = self.text.as_bytes();
+        let mut cursor = Cursor::new(*self);
+        let mut integers = Vec::new();
+        let mut end = 0;
+        while end < bytes.len() {
+            if !bytes[end].is_ascii_digit() {
+                end += 1;
+                continue;
+            }
+            let mut start = end;
+            while end < bytes.len() && bytes[end].is_ascii_digit() {
+                end += 1;
+            }
+            let negative = start > 0
+                && bytes[start - 1] == b'-'
+                && (start == 1 || !bytes[start - 2].is_ascii_alphanumeric());
+            if negative {
+                start -= 1;
+            }
+            integers.push(cursor.span(start, end).parse()?);
+        }
+        Ok(integers)
+    }
+
+    /// Exactly `N` integers, found as by [`integers`](Self::integers).
+    pub fn integer_array<T: FromStr, const N: usize>(&self) -> Result<[T; N], ParseError>
+    where
+        T::Err: Display,
+    {
+        let integers = self.integers()?;
+        let found = integers.len();
+        integers
+            .try_into()
+            .map_err(|_| self.error(ErrorKind::WrongCount { expected: N, found }))
+    }
+
+    /// A grid with a cell per character, ignoring blank lines and whitespace
+    /// around each row. `cell` rejects a character by returning `None`.
+    pub fn grid<T>(&self, mut cell: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for row in self
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+        {
+            let mut row_width = 0;
+            for (column, ch) in (row.column..).zip(row.text.chars()) {
+                cells.push(cell(ch).ok_or(ParseError {
+                    line: row.line,
+                    column,
+                    kind: ErrorKind::UnexpectedChar(ch),
+                })?);
+                row_width += 1;
+            }
+            match width {
+                Some(expected) if expected != row_width => {
+                    return Err(row.error(ErrorKind::RaggedRow {
+                        expected,
+                        found: row_width,
+                    }))
+                }
+                _ => width = Some(row_width),
+            }
+            height += 1;
+        }
+        Ok(Grid::new(width.unwrap_or(0), height, cells).expect("rows were checked to be even"))
+    }
+}
+
+impl Display for Span<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Span;
+    use crate::{ErrorKind, ParseError};
+
+    fn position(span: Span) -> (usize, usize) {
+        (span.line(), span.column())
+    }
+
+    #[test]
+    fn lines_and_blocks_test() {
+        let input = Span::new("1000\n2000\n\n\n4000\n\n5000\n6000\n");
+        let lines: Vec<_> = input.lines().map(|line| line.as_str()).collect();
+        assert_eq!(lines, ["1000", "2000", "", "", "4000", "", "5000", "6000"]);
+        let blocks: Vec<_> = input.blocks().collect();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].as_str(), "1000\n2000");
+        assert_eq!(position(blocks[1]), (5, 1));
+        assert_eq!(blocks[2].as_str(), "5000\n6000");
+        let last: Vec<_> = blocks[2].lines().map(position).collect();
+        assert_eq!(last, [(7, 1), (8, 1)]);
+    }
+
+    #[test]
+    fn split_test() {
+        let input = Span::new("a,b\nc, d,e");
+        let pieces: Vec<_> = input
+            .split(",")
+            .map(|piece| (piece.as_str(), position(piece)))
+            .collect();
+        assert_eq!(
+            pieces,
+            [
+                ("a", (1, 1)),
+                ("b\nc", (1, 3)),
+                (" d", (2, 3)),
+                ("e", (2, 6))
+            ]
+        );
+        let line = input.lines().nth(1).unwrap();
+        assert_eq!(line.parse_list::<char>(","), Ok(vec!['c', 'd', 'e']));
+        let (before, after) = line.split_once(", ").unwrap();
+        assert_eq!((before.as_str(), position(after)), ("c", (2, 4)));
+        assert_eq!(
+            line.split_once(";"),
+            Err(ParseError {
+                line: 2,
+                column: 1,
+                kind: ErrorKind::Expected {
+                    expected: "\";\"".to_string(),
+                    found: "c, d,e".to_string()
+                }
+            })
+        );
+        assert_eq!(position(Span::new("\n  x ").trim()), (2, 3));
+        let (first, rest) = line.split_first().unwrap();
+        assert_eq!(
+            (first, rest.as_str(), position(rest)),
+            ('c', ", d,e", (2, 2))
+        );
+        assert_eq!(Span::new("").split_first(), None);
+    }
+
+    #[test]
+    fn record_test() {
+        let input = Span::new("Game 3: 8 green\necl:gry  pid:860033327\nbyr1937");
+        let mut lines = input.lines();
+        let (key, value) = lines.next().unwrap().key_value(":").unwrap();
+        assert_eq!(key.strip_prefix("Game ").unwrap().parse(), Ok(3));
+        assert_eq!((value.as_str(), position(value)), ("8 green", (1, 9)));
+        let fields: Vec<_> = lines
+            .next()
+            .unwrap()
+            .fields(":")
+            .map(|field| field.map(|(k, v)| (k.as_str(), position(v))))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(fields, [("ecl", (2, 5)), ("pid", (2, 14))]);
+        let error = lines.next().unwrap().fields(":").next().unwrap();
+        assert_eq!(error.map_err(|err| (err.line, err.column)), Err((3, 1)));
+    }
+
+    #[test]
+    fn integers_test() {
+        let line = Span::new("Sensor at x=2, y=-18: closest beacon is at x=-2, y=15");
+        assert_eq!(line.integers(), Ok(vec![2, -18, -2, 15]));
+        assert_eq!(line.integer_array::<i64, 4>(), Ok([2, -18, -2, 15]));
+        assert_eq!(
+            line.integer_array::<i64, 3>().map_err(|err| err.kind),
+            Err(ErrorKind::WrongCount {
+                expected: 3,
+                found: 4
+            })
+        );
+        assert_eq!(Span::new("2-4,6-8").integers(), Ok(vec![2u32, 4, 6, 8]));
+        assert_eq!(
+            Span::new("move 1 from 2 to 1").integers(),
+            Ok(vec![1, 2, 1])
+        );
+        let error = Span::new("ok\nx=5, y=-3").integers::<u8>().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 8));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 8: invalid value \"-3\": invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn grid_test() {
+        let digit = |ch: char| ch.to_digit(10);
+        let grid = Span::new("\n  123\n  456\n").grid(digit).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        let error = Span::new("123\n4x6").grid(digit).unwrap_err();
+        assert_eq!(
+            error,
+            ParseError {
+                line: 2,
+                column: 2,
+                kind: ErrorKind::UnexpectedChar('x')
+            }
+        );
+        let error = Span::new("123\n45").grid(digit).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(
+            error.kind,
+            ErrorKind::RaggedRow {
+                expected: 3,
+                found: 2
+            }
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use std::{collections::VecDeque, hash::Hash};
+
+use crate::{Path, SearchTree};
+
+fn explore<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> (SearchTree<N, usize>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut tree = SearchTree::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !tree.contains(&start) {
+            tree.costs.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+    while let Some(node) = queue.pop_front() {
+        if goal(&node) {
+            return (tree, Some(node));
+        }
+        let depth = tree.costs[&node] + 1;
+        for neighbor in neighbors(&node) {
+            if !tree.contains(&neighbor) {
+                tree.costs.insert(neighbor.clone(), depth);
+                tree.parents.insert(neighbor.clone(), node.clone());
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    (tree, None)
+}
+
+/// Shortest path in number of edges from any of `starts` to a node
+/// satisfying `goal`.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let (tree, end) = explore(starts, neighbors, goal);
+    tree.path_to(&end?)
+}
+
+/// Every node reachable from `starts`, with the number of edges on the
+/// shortest path to it.
+pub fn bfs_all<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+) -> SearchTree<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    explore(starts, neighbors, |_| false).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bfs, bfs_all};
+
+    // 0 -> 1 -> 2 -> 3, 0 -> 4 -> 3, 5 -> 3
+    fn successors(node: &u32) -> Vec<u32> {
+        match node {
+            0 => vec![1, 4],
+            1 => vec![2],
+            2 | 4 | 5 => vec![3],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn bfs_test() {
+        let path = bfs([0], successors, |&node| node == 3).unwrap();
+        assert_eq!(path.nodes, [0, 4, 3]);
+        assert_eq!(path.cost, 2);
+        assert_eq!(bfs([3], successors, |&node| node == 0), None);
+        let path = bfs([1, 5], successors, |&node| node == 3).unwrap();
+        assert_eq!(path.nodes, [5, 3]);
+        assert_eq!(bfs([3], successors, |&node| node == 3).unwrap().cost, 0);
+    }
+
+    #[test]
+    fn bfs_all_test() {
+        let tree = bfs_all([0], successors);
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.cost(&2), Some(2));
+        assert_eq!(tree.cost(&5), None);
+        assert_eq!(tree.parent(&1), Some(&0));
+        assert_eq!(tree.parent(&0), None);
+        assert_eq!(tree.furthest().map(|(_, cost)| cost), Some(2));
+        assert_eq!(tree.path_to(&2).unwrap().nodes, [0, 1, 2]);
+    }
+}
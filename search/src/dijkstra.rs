@@ -0,0 +1,181 @@
+use std::{cmp::Ordering, collections::BinaryHeap, hash::Hash};
+
+use crate::{Cost, Path, SearchTree};
+
+/// Node waiting in the open set, ordered so that `BinaryHeap` pops the lowest
+/// priority first.
+struct Entry<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+fn explore<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> (SearchTree<N, C>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut tree = SearchTree::new();
+    let mut open = BinaryHeap::new();
+    for start in starts {
+        if !tree.contains(&start) {
+            tree.costs.insert(start.clone(), C::default());
+            open.push(Entry {
+                priority: heuristic(&start),
+                cost: C::default(),
+                node: start,
+            });
+        }
+    }
+    while let Some(Entry { cost, node, .. }) = open.pop() {
+        if tree.costs[&node] < cost {
+            // a cheaper path to this node was found after it was queued
+            continue;
+        }
+        if goal(&node) {
+            return (tree, Some(node));
+        }
+        for (neighbor, weight) in neighbors(&node) {
+            let neighbor_cost = cost + weight;
+            if tree
+                .cost(&neighbor)
+                .is_none_or(|known| neighbor_cost < known)
+            {
+                tree.costs.insert(neighbor.clone(), neighbor_cost);
+                tree.parents.insert(neighbor.clone(), node.clone());
+                open.push(Entry {
+                    priority: neighbor_cost + heuristic(&neighbor),
+                    cost: neighbor_cost,
+                    node: neighbor,
+                });
+            }
+        }
+    }
+    (tree, None)
+}
+
+/// Cheapest path from any of `starts` to a node satisfying `goal`, the
+/// neighbour function returning each neighbour with the cost of the edge
+/// leading to it.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (tree, end) = explore(starts, neighbors, |_| C::default(), goal);
+    tree.path_to(&end?)
+}
+
+/// Every node reachable from `starts`, with the cost of the cheapest path to
+/// it.
+pub fn dijkstra_all<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+) -> SearchTree<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    explore(starts, neighbors, |_| C::default(), |_| false).0
+}
+
+/// Like [`dijkstra`], exploring nodes in order of their cost plus the
+/// `heuristic` estimate of the remaining cost to a goal.
+///
+/// The path is only guaranteed to be the cheapest if the heuristic never
+/// overestimates, and never drops by more than the weight of an edge when
+/// following it.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (tree, end) = explore(starts, neighbors, heuristic, goal);
+    tree.path_to(&end?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{astar, dijkstra, dijkstra_all};
+
+    // a -1-> b -1-> c -1-> d, a -5-> d, e -1-> d
+    fn successors(node: &char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 1), ('d', 5)],
+            'b' => vec![('c', 1)],
+            'c' | 'e' => vec![('d', 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn dijkstra_test() {
+        let path = dijkstra(['a'], successors, |&node| node == 'd').unwrap();
+        assert_eq!(path.nodes, ['a', 'b', 'c', 'd']);
+        assert_eq!(path.cost, 3);
+        let path = dijkstra(['a', 'e'], successors, |&node| node == 'd').unwrap();
+        assert_eq!(path.nodes, ['e', 'd']);
+        assert_eq!(dijkstra(['d'], successors, |&node| node == 'a'), None);
+
+        let tree = dijkstra_all(['a'], successors);
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.cost(&'d'), Some(3));
+        assert_eq!(tree.furthest(), Some((&'d', 3)));
+    }
+
+    #[test]
+    fn astar_test() {
+        // open plane with a wall at x = 2 for -5 <= y <= 5
+        let neighbors = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|&(x, y)| x != 2 || y.abs() > 5)
+                .map(|p| (p, 1))
+                .collect::<Vec<_>>()
+        };
+        let goal = (4, 0);
+        let manhattan = |&(x, y): &(i32, i32)| (goal.0 - x).abs() + (goal.1 - y).abs();
+        let path = astar([(0, 0)], neighbors, manhattan, |&p| p == goal).unwrap();
+        assert_eq!(path.cost, 16);
+        assert_eq!(path.nodes.len(), 17);
+        assert!(path.nodes.contains(&(2, 6)) || path.nodes.contains(&(2, -6)));
+    }
+}
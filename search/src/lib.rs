@@ -0,0 +1,89 @@
+//! Graph searches over graphs given by a neighbour function.
+//!
+//! Nodes only need to be hashable, and the graph is never built up front:
+//! every search takes a closure returning the neighbours of a node, weighted
+//! by the cost of the edge for the cost-aware searches. All searches start
+//! from any number of nodes at once.
+
+mod bfs;
+mod dijkstra;
+mod reversed;
+
+use std::{collections::HashMap, hash::Hash, ops::Add};
+
+pub use bfs::{bfs, bfs_all};
+pub use dijkstra::{astar, dijkstra, dijkstra_all};
+pub use reversed::Reversed;
+
+/// Edge weights and path lengths, `Default` being a cost of zero.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+/// A path from one of the start nodes, both ends included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+/// The nodes a search reached, with the cost of the best path found to each
+/// and the node that path came through.
+#[derive(Debug, Clone)]
+pub struct SearchTree<N, C> {
+    costs: HashMap<N, C>,
+    parents: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Cost> SearchTree<N, C> {
+    fn new() -> Self {
+        SearchTree {
+            costs: HashMap::new(),
+            parents: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.costs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.costs.is_empty()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.costs.contains_key(node)
+    }
+
+    /// Cost of the best path to `node`, `None` if it wasn't reached.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    /// The node before `node` on its best path, `None` for start nodes.
+    pub fn parent(&self, node: &N) -> Option<&N> {
+        self.parents.get(node)
+    }
+
+    /// The reached nodes with their costs, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> {
+        self.costs.iter().map(|(node, &cost)| (node, cost))
+    }
+
+    /// A reached node that is the most expensive to get to.
+    pub fn furthest(&self) -> Option<(&N, C)> {
+        self.iter().max_by_key(|&(_, cost)| cost)
+    }
+
+    /// The best path to `node`, starting from whichever start node it was
+    /// reached from.
+    pub fn path_to(&self, node: &N) -> Option<Path<N, C>> {
+        let cost = self.cost(node)?;
+        let mut nodes = vec![node.clone()];
+        while let Some(parent) = self.parent(nodes.last().unwrap()) {
+            nodes.push(parent.clone());
+        }
+        nodes.reverse();
+        Some(Path { nodes, cost })
+    }
+}
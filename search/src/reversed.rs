@@ -0,0 +1,54 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// The predecessors of every node of a finite graph, for searching against
+/// the direction of its edges, e.g. from a single goal back to every
+/// possible start at once.
+#[derive(Debug, Clone)]
+pub struct Reversed<N> {
+    predecessors: HashMap<N, Vec<N>>,
+}
+
+impl<N: Clone + Eq + Hash> Reversed<N> {
+    /// Reverse every edge leaving `nodes`.
+    pub fn new<I>(nodes: impl IntoIterator<Item = N>, mut neighbors: impl FnMut(&N) -> I) -> Self
+    where
+        I: IntoIterator<Item = N>,
+    {
+        let mut predecessors: HashMap<N, Vec<N>> = HashMap::new();
+        for node in nodes {
+            for neighbor in neighbors(&node) {
+                predecessors.entry(neighbor).or_default().push(node.clone());
+            }
+        }
+        Reversed { predecessors }
+    }
+
+    /// Nodes with an edge to `node`, ready to be used as the neighbour
+    /// function of a search.
+    pub fn predecessors(&self, node: &N) -> impl Iterator<Item = N> + '_ {
+        self.predecessors.get(node).into_iter().flatten().cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Reversed;
+    use crate::bfs;
+
+    #[test]
+    fn reversed_test() {
+        // i -> i + 1 and i -> 2i below 20
+        let successors = |&i: &u32| {
+            [i + 1, 2 * i]
+                .into_iter()
+                .filter(move |&j| j < 20 && j != i)
+        };
+        let reversed = Reversed::new(0..20, successors);
+        let mut predecessors: Vec<_> = reversed.predecessors(&8).collect();
+        predecessors.sort_unstable();
+        assert_eq!(predecessors, [4, 7]);
+        assert_eq!(reversed.predecessors(&0).count(), 0);
+        let path = bfs([19], |i| reversed.predecessors(i), |&i| i == 1).unwrap();
+        assert_eq!(path.nodes, [19, 18, 9, 8, 4, 2, 1]);
+    }
+}